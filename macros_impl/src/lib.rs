@@ -1,7 +1,7 @@
 use crate::parse::Parser;
 use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
-use syn::{Expr, Generics, ItemTrait, PatType, Path, Type, Visibility};
+use syn::{Block, Expr, Generics, ItemTrait, PatType, Path, Type, Visibility};
 
 #[cfg(test)]
 mod tests;
@@ -13,24 +13,61 @@ mod output;
 /// 
 /// # Errors
 /// Can return a [`syn::Error`] if it fails to parse the input or rejects some part of the input
-pub fn rpc(_args: TokenStream, input: ItemTrait) -> syn::Result<impl ToTokens> {
-    let parser = Parser;
+pub fn rpc(args: TokenStream, input: ItemTrait) -> syn::Result<impl ToTokens> {
+    let parser = Parser::new(args)?;
     parser.rpc(input)
 }
 
+#[allow(clippy::struct_excessive_bools, reason = "Each of these is an independent option of the macro")]
 struct Rpc {
     docs: Vec<Expr>,
     vis: Visibility,
     generics: Generics,
     name: Ident,
     methods: Vec<Method>,
+    /// Encode the request and response enums as externally tagged enums, so that
+    /// non-self-describing formats (eg: postcard) identify each method by its variant index
+    variant_index: bool,
+    /// Generate a request type for the server which borrows its arguments from the request body
+    borrow: bool,
+    /// Generate struct variants for the request enums, so that each argument is keyed by its name
+    named_args: bool,
+    /// The path to the `trait_rpc` crate, this is `::trait_rpc` unless it is declared with
+    /// `#[rpc(crate = ...)]`
+    krate: Path,
+    /// Generate the async and blocking clients
+    client: bool,
+    /// Generate the server trait and its handler
+    server: bool,
+    /// Extra traits to derive for the request and response, as declared with `#[rpc(derive(...))]`
+    derives: Vec<Path>,
 }
 
 struct Method {
     docs: Vec<Expr>,
     name: Ident,
+    /// The name of the method, as it is encoded, this is declared with `#[rpc(rename = ...)]`
+    /// unless it follows `rename_all`
+    wire_name: String,
+    /// The other names which the method is decoded from, as declared with `#[rpc(alias = ...)]`
+    aliases: Vec<String>,
     args: Vec<PatType>,
+    /// How each of the args borrows from the request body, this is only set for services declared
+    /// with `#[rpc(borrow)]`
+    borrows: Vec<Option<Borrow>>,
+    /// The reference which the client takes for each of the args, the client sends it without
+    /// copying it. This is set for every reference, which the server receives as the owned value
+    /// unless it is borrowed from the request body
+    refs: Vec<Option<Type>>,
+    /// The default of each of the args, which the server uses when the arg is missing from the
+    /// request, as declared with `#[rpc(default)]` or `#[rpc(default = ...)]`
+    defaults: Vec<Option<ArgDefault>>,
     ret: ReturnType,
+    /// The application error type which this method may return instead of its response
+    error: Option<Type>,
+    /// The default implementation of this method, which the server trait runs unless the server
+    /// overrides it
+    body: Option<Block>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -38,4 +75,30 @@ enum ReturnType {
     Simple(Type),
     Nested { service: Path },
     Streaming(Type),
+    /// The requests of a supertrait, which are delegated to the supertrait's service
+    Inherited { service: Path },
+}
+
+/// An argument which borrows from the request body
+struct Borrow {
+    /// True for `Cow<'_, T>`, false for a reference
+    cow: bool,
+    ty: Borrowed,
+}
+
+/// The type which is borrowed by an argument
+enum Borrowed {
+    Str,
+    Bytes,
+    /// Any other type, this may only be borrowed with a [Cow](std::borrow::Cow), which is
+    /// deserialised as owned unless it is `str` or `[u8]`
+    Other(Box<Type>),
+}
+
+/// The value of an argument which is missing from the request
+enum ArgDefault {
+    /// Use [`Default::default`]
+    Default,
+    /// Use the given expression, which has the type of the argument as it is declared
+    Value(Box<Expr>),
 }
@@ -1,8 +1,8 @@
-use crate::{ReturnType, Rpc};
+use crate::{ArgDefault, Borrow, Borrowed, Method, ReturnType, Rpc};
 use convert_case::ccase;
-use proc_macro2::{Ident, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::{ToTokens, format_ident, quote};
-use syn::{Field, FieldMutability, Visibility, parse_quote, Generics};
+use syn::{parse_quote, Generics, Lifetime, Pat, Type};
 
 macro_rules! ident_ccase {
     ($case:ident, $ident:expr) => {
@@ -15,6 +15,8 @@ impl ToTokens for Rpc {
     fn to_token_stream(&self) -> TokenStream {
         let service = &self.name;
         let module = ident_ccase!(snake, service);
+        let krate = &self.krate;
+        let serde_crate = self.krate_path("serde");
 
         let generics = &self.generics;
         let gen_params: Vec<_> = generics.params.iter().collect();
@@ -47,71 +49,126 @@ impl ToTokens for Rpc {
 
         let imports = {
             let vis = &self.vis;
+            let clients = self.client.then_some([&async_client, &blocking_client]).into_iter().flatten();
+            let server = self.server.then_some(&server);
             quote!(
                 #vis use #module::{
                     #service,
-                    #async_client,
-                    #blocking_client,
+                    #(#clients,)*
                     #server
                 };
             )
         };
 
+        // the request variants are structs when args are named, so they are matched with braces
+        let any_fields = if self.named_args {
+            quote!({ .. })
+        } else {
+            quote!((..))
+        };
         let (request_variants, request_streaming): (Vec<_>, Vec<_>) = self.methods.iter().map(|method| {
-            let snake_name = method.name.to_string();
+            let wire_name = &method.wire_name;
+            let aliases = &method.aliases;
             let name = ident_ccase!(pascal, method.name);
-            let mut fields: Vec<_> = method
-                .args
-                .iter()
-                .map(|pat| Field {
-                    attrs: vec![],
-                    vis: Visibility::Inherited,
-                    mutability: FieldMutability::None,
-                    ident: None,
-                    colon_token: None,
-                    ty: *pat.ty.clone(),
+            let fields = method
+                .owned_types()
+                .into_iter()
+                .zip(&method.borrows)
+                .zip(method.default_attrs(false))
+                .map(|((ty, borrow), default)| {
+                    // bytes are serialised as bytes so that they can be borrowed by the server
+                    let attrs = match borrow {
+                        Some(Borrow { ty: Borrowed::Bytes, .. }) => {
+                            let bytes = self.krate_path("borrow::bytes");
+                            quote!(#[serde(with = #bytes)] #default)
+                        }
+                        _ => default,
+                    };
+                    (attrs, ty.to_token_stream())
                 })
                 .collect();
-            if let ReturnType::Nested {
-                service: ret,
-            } = &method.ret
-            {
-                fields.push(parse_quote! {
-                    <#ret as Rpc>::Request
-                });
-            }
-            let streaming = matches!(method.ret, ReturnType::Streaming(_));
+            let nested = if let ReturnType::Nested { service: ret } | ReturnType::Inherited { service: ret } = &method.ret {
+                Some((TokenStream::new(), quote!(<#ret as Rpc>::Request)))
+            } else {
+                None
+            };
+            let fields = method.declare_fields(self.named_args, fields, nested);
+            // the inherited requests may be streaming, which is known only by the supertrait
+            let streaming = if let ReturnType::Inherited { .. } = method.ret {
+                let request = method.request_fields(self.named_args, vec![], Some(quote!(request)));
+                quote!(Self::#name #request => #krate::Request::is_streaming_response(request))
+            } else {
+                let streaming = matches!(method.ret, ReturnType::Streaming(_));
+                quote!(Self::#name #any_fields => #streaming)
+            };
             (
                 quote!(
-                    #[serde(rename = #snake_name)]
-                    #name(#(#fields),*)
+                    #[serde(rename = #wire_name)]
+                    #(#[serde(alias = #aliases)])*
+                    #name #fields
                 ),
-                quote!(
-                    Self::#name(..) => #streaming
-                )
+                streaming,
             )
         }).unzip();
 
+        let default_fns = self.methods.iter().map(|method| method.default_fns(self.borrows()));
+
         let response_variants = self.methods.iter().map(|method| {
-            let snake_name = method.name.to_string();
+            let wire_name = &method.wire_name;
+            let aliases = &method.aliases;
             let name = ident_ccase!(pascal, method.name);
             let ret = match &method.ret {
-                ReturnType::Simple(ty) | ReturnType::Streaming(ty) => ty.clone(),
-                ReturnType::Nested {
-                    service: path,
-                } => {
+                ReturnType::Simple(ty) | ReturnType::Streaming(ty) => method.with_error(ty),
+                ReturnType::Nested { service: path } | ReturnType::Inherited { service: path } => {
                     parse_quote!(<#path as Rpc>::Response)
                 }
             };
             quote!(
-                #[serde(rename = #snake_name)]
+                #[serde(rename = #wire_name)]
+                #(#[serde(alias = #aliases)])*
                 #name(#ret)
             )
         });
-        let response_to_name = self.methods.iter().map(|method| {
-            let name = method.name.to_string();
+        let variant_to_name = self.methods.iter().map(|method| {
+            let name = &method.wire_name;
             let variant = ident_ccase!(pascal, method.name);
             quote!(Self::#variant(..) => #name)
+        }).collect::<Vec<_>>();
+        let request_to_name = self.methods.iter().map(|method| {
+            let name = &method.wire_name;
+            let variant = ident_ccase!(pascal, method.name);
+            quote!(Self::#variant #any_fields => #name)
+        }).collect::<Vec<_>>();
+        let method_info = self.methods.iter().map(|method| {
+            let name = &method.wire_name;
+            let args = method.args.iter().map(|pat| match &*pat.pat {
+                Pat::Ident(pat) => pat.ident.to_string(),
+                pat => pat.to_token_stream().to_string(),
+            });
+            let streaming = matches!(method.ret, ReturnType::Streaming(_));
+            let (error, nested) = match &method.ret {
+                ReturnType::Nested { service } | ReturnType::Inherited { service } => (false, quote!(Some(<<#service as Rpc>::Request as #krate::Request>::METHODS))),
+                ReturnType::Simple(_) | ReturnType::Streaming(_) => (method.error.is_some(), quote!(None)),
+            };
+            let named = self.named_args;
+            let aliases = &method.aliases;
+            quote!(#krate::MethodInfo {
+                name: #name,
+                aliases: &[#(#aliases),*],
+                args: &[#(#args),*],
+                named: #named,
+                streaming: #streaming,
+                error: #error,
+                nested: #nested,
+            })
+        });
+        let response_is_error = self.methods.iter().map(|method| {
+            let variant = ident_ccase!(pascal, method.name);
+            match (&method.ret, &method.error) {
+                (ReturnType::Nested { .. } | ReturnType::Inherited { .. }, _) => quote!(Self::#variant(response) => #krate::Response::is_error(response)),
+                (_, Some(_)) => quote!(Self::#variant(response) => response.is_err()),
+                (_, None) => quote!(Self::#variant(..) => false),
+            }
         });
 
         let server_fns = self.methods.iter().map(|method| {
@@ -121,58 +178,95 @@ impl ToTokens for Rpc {
             let docs = quote! {
                 #(#[doc = #docs])*
             };
+            // a method with a default body is run as an async block, so that it may await
+            let body = method.body.as_ref().map_or_else(
+                || quote!(;),
+                |body| {
+                    let refs = method.default_body_refs();
+                    if refs.is_empty() {
+                        quote!({ async move #body })
+                    } else {
+                        quote!({ async move { #(#refs)* #body } })
+                    }
+                },
+            );
             match &method.ret {
                 ReturnType::Simple(ret) => {
+                    let ret = method.with_error(ret);
                     quote! {
                         #docs
-                        fn #name(&self #(,#params)*) -> impl Future<Output=#ret> + Send;
+                        fn #name(&self #(,#params)*) -> impl Future<Output=#ret> + Send #body
                     }
                 }
                 ReturnType::Nested { service: path } => {
                     quote! {
                         #docs
-                        fn #name(&self #(,#params)*) -> impl Future<Output = impl IntoHandler<#path>> + Send;
+                        fn #name(&self #(,#params)*) -> impl Future<Output = impl IntoHandler<#path>> + Send #body
                     }
                 }
                 ReturnType::Streaming(ret) => {
+                    let ret = method.with_error(ret);
                     quote! {
                         #docs
                         fn #name(&self, sink: impl Sink<#ret, Error = Infallible> + Send + 'static #(,#params)*) -> impl Future<Output=()> + Send;
                     }
                 }
+                ReturnType::Inherited { .. } => TokenStream::new(),
             }
         });
         let (handle_arms, stream_handle_arms): (Vec<_>, Vec<_>) = self.methods.iter().map(|method| {
             let name = &method.name;
             let variant = ident_ccase!(pascal, method.name);
-            let params = method.args.iter().map(|pat| &pat.pat).collect::<Vec<_>>();
+            let params = method.params();
+            let args = method.args_from_owned();
             let handle = match &method.ret {
                 ReturnType::Nested { service: _ } => {
+                    let fields = method.request_fields(self.named_args, params, Some(quote!(request)));
                     quote! {
-                        Request::#variant(#(#params, )*request) => {
-                            let response = self.0.#name(#(#params),*).await.into_handler().handle(request).await;
+                        Request::#variant #fields => {
+                            let response = self.0.#name(#(#args),*).await.into_handler().handle(request).await;
                             Response::#variant(response)
                         },
                     }
                 }
                 ReturnType::Simple(_) => {
+                    let fields = method.request_fields(self.named_args, params, None);
                     quote! {
-                        Request::#variant(#(#params),*) => Response::#variant(self.0.#name(#(#params),*).await),
+                        Request::#variant #fields => Response::#variant(self.0.#name(#(#args),*).await),
                     }
                 }
                 ReturnType::Streaming(_) => {
                     quote! {}
                 }
+                ReturnType::Inherited { service } => {
+                    let fields = method.request_fields(self.named_args, vec![], Some(quote!(request)));
+                    quote! {
+                        Request::#variant #fields => {
+                            let response = <#service as RpcWithServer<&_Server>>::handler(&self.0).handle(request).await;
+                            Response::#variant(response)
+                        },
+                    }
+                }
             };
             let streaming_handle = match &method.ret {
                 ReturnType::Nested { .. } | ReturnType::Simple(..) => {
                     quote! {}
                 }
+                ReturnType::Inherited { service } => {
+                    let fields = method.request_fields(self.named_args, vec![], Some(quote!(request)));
+                    quote! {
+                        Request::#variant #fields => {
+                            let sink = sink.with(async |value| Result::<_, S::Error>::Ok(Response::#variant(value)));
+                            <#service as RpcWithServer<&_Server>>::handler(&self.0).handle_stream_response(request, sink).await;
+                        },
+                    }
+                }
                 ReturnType::Streaming(_) => {
+                    let fields = method.request_fields(self.named_args, method.params(), None);
                     quote! {
-                        Request::#variant(#(#params),*) => {
+                        Request::#variant #fields => {
                             let sink = sink.with(async |value| Result::<_, S::Error>::Ok(Response::#variant(value)));
-                            self.0.#name(sink, #(#params),*).await;
+                            self.0.#name(sink, #(#args),*).await;
                         },
                     }
                 }
@@ -180,94 +274,161 @@ impl ToTokens for Rpc {
             (handle, streaming_handle)
         }).unzip();
 
-        let async_client_fns = self.client_fns(true, generics);
-        let blocking_client_fns = self.client_fns(false, generics);
-
-        quote! {
-            #[allow(unused_imports, reason = "These might not always be used, but they should be available in this module anyway")]
-            #imports
-
-            #[allow(unused_imports, reason = "These might not always be used, but it's easier to include always")]
-            mod #module {
-                use super::*;
-                use std::convert::Infallible;
-                use std::marker::PhantomData;
-                use ::trait_rpc::{
-                    client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
-                    futures::sink::{Sink, SinkExt},
-                    futures::stream::{Stream, StreamExt},
-                    serde::{Deserialize, Serialize},
-                    server::{Handler, IntoHandler},
-                    Rpc, RpcWithServer
-                };
-
-                #(
-                    #(#[doc = #docs])*
-                    ///
-                )*
-                /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
-                pub struct #service #generics #phantom_data;
+        // non-self-describing formats cannot read the tag of an adjacently tagged enum, so the
+        // enums are left externally tagged, which those formats write as the variant index
+        let (request_tagging, response_tagging) = if self.variant_index {
+            (None, None)
+        } else {
+            (
+                Some(quote!(#[serde(tag = "method", content = "args")])),
+                Some(quote!(#[serde(tag = "method", content = "result")])),
+            )
+        };
 
-                impl #generics Rpc for #service #generics #(where #(#maybe_generics: Send + 'static),*)* {
-                    type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> = #async_client<_Client #(,#gen_params)*>;
-                    type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> = #blocking_client<_Client #(,#gen_params)*>;
-                    type Request = Request #generics;
-                    type Response = Response #generics;
-                    fn async_client<_Client: AsyncClient<Request #generics, Response #generics>>(transport: _Client) -> #async_client<_Client #(,#gen_params)*> {
-                        #async_client(transport, #phantom_data_new)
+        // a service which borrows arguments is read by the server as a separate request type,
+        // which is converted to the owned request when it cannot be borrowed
+        let (borrowed_request_type, borrowed_request, handle_borrowed) = if self.borrows() {
+            let borrowed_request = self.borrowed_request(request_tagging.as_ref(), &request_streaming, &request_to_name);
+            let handle_borrowed_arms = self.methods.iter().map(|method| {
+                let name = &method.name;
+                let variant = ident_ccase!(pascal, method.name);
+                let args = method.args_from_borrowed();
+                match &method.ret {
+                    ReturnType::Nested { .. } => {
+                        let fields = method.request_fields(self.named_args, method.params(), Some(quote!(request)));
+                        quote! {
+                            BorrowedRequest::#variant #fields => {
+                                let response = self.0.#name(#(#args),*).await.into_handler().handle_borrowed(request).await;
+                                Response::#variant(response)
+                            },
+                        }
                     }
-                    fn blocking_client<_Client: BlockingClient<Request #generics, Response #generics>>(transport: _Client) -> #blocking_client<_Client #(,#gen_params)*> {
-                        #blocking_client(transport, #phantom_data_new)
+                    ReturnType::Simple(_) => {
+                        let fields = method.request_fields(self.named_args, method.params(), None);
+                        quote! {
+                            BorrowedRequest::#variant #fields => Response::#variant(self.0.#name(#(#args),*).await),
+                        }
                     }
-                }
-
-                impl<Server: #server #generics #(, #gen_params: Send + 'static)*> RpcWithServer<Server> for #service #generics {
-                    type Handler = #handler<Server #(, #gen_params)*>;
-                    fn handler(server: Server) -> Self::Handler {
-                        #handler(server, #phantom_data_new)
+                    ReturnType::Streaming(_) => quote! {},
+                    ReturnType::Inherited { service } => {
+                        let fields = method.request_fields(self.named_args, vec![], Some(quote!(request)));
+                        quote! {
+                            BorrowedRequest::#variant #fields => {
+                                let response = <#service as RpcWithServer<&_Server>>::handler(&self.0).handle_borrowed(request).await;
+                                Response::#variant(response)
+                            },
+                        }
                     }
                 }
+            });
+            (
+                quote!(BorrowedRequest<'req #(, #gen_params)*>),
+                borrowed_request,
+                quote! {
+                    async fn handle_borrowed(&self, request: <Self::Rpc as Rpc>::BorrowedRequest<'_>) -> Response #generics {
+                        match request {
+                            #(#handle_borrowed_arms)*
+                            _ => panic!("This is a streaming method, must call handle_streaming")
+                        }
+                    }
+                },
+            )
+        } else {
+            (quote!(Request #generics), TokenStream::new(), TokenStream::new())
+        };
 
+        // the client sends a request which borrows its args when a method takes a reference, it is
+        // also sent by a nested service, whose args are borrowed by the nested request
+        let (request_ref_type, request_ref) = if self.has_request_ref() {
+            (
+                quote!(RequestRef<'req #(, #gen_params)*>),
+                self.request_ref(request_tagging.as_ref(), &request_streaming, &request_to_name),
+            )
+        } else {
+            (quote!(Self), TokenStream::new())
+        };
 
-                #[derive(Debug, Serialize, Deserialize)]
-                #[serde(crate = "::trait_rpc::serde")]
-                #[serde(tag = "method", content = "args")]
-                pub enum Request #generics {
-                    #(#request_variants,)*
-                }
+        let derives = &self.derives;
 
-                impl #generics ::trait_rpc::Request for Request #generics {
-                    fn is_streaming_response(&self) -> bool {
-                        match self {
-                            #(#request_streaming),*
-                        }
-                    }
+        // without the generated clients, the client of the service is its transport, which sends
+        // the requests and returns the responses as they are
+        let rpc_clients = if self.client {
+            quote! {
+                type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> = #async_client<_Client #(,#gen_params)*>;
+                type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> = #blocking_client<_Client #(,#gen_params)*>;
+            }
+        } else {
+            quote! {
+                type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> = _Client;
+                type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> = _Client;
+            }
+        };
+        let rpc_client_fns = if self.client {
+            quote! {
+                fn async_client<_Client: AsyncClient<Request #generics, Response #generics>>(transport: _Client) -> #async_client<_Client #(,#gen_params)*> {
+                    #async_client(transport, #phantom_data_new)
+                }
+                fn blocking_client<_Client: BlockingClient<Request #generics, Response #generics>>(transport: _Client) -> #blocking_client<_Client #(,#gen_params)*> {
+                    #blocking_client(transport, #phantom_data_new)
                 }
+            }
+        } else {
+            quote! {
+                fn async_client<_Client: AsyncClient<Request #generics, Response #generics>>(transport: _Client) -> _Client {
+                    transport
+                }
+                fn blocking_client<_Client: BlockingClient<Request #generics, Response #generics>>(transport: _Client) -> _Client {
+                    transport
+                }
+            }
+        };
 
-                #[derive(Debug, Serialize, Deserialize)]
-                #[serde(crate = "::trait_rpc::serde")]
-                #[serde(tag = "method", content = "result")]
-                pub enum Response #generics {
-                    #(#response_variants,)*
+        let rpc_with_server = self.server.then(|| quote! {
+            impl<Server: #server #generics #(, #gen_params: Send + 'static)*> RpcWithServer<Server> for #service #generics {
+                type Handler = #handler<Server #(, #gen_params)*>;
+                fn handler(server: Server) -> Self::Handler {
+                    #handler(server, #phantom_data_new)
                 }
+            }
+        });
 
-                impl #generics Response #generics {
-                    fn fn_name(&self) -> &'static str {
-                        match self {
-                            #(#response_to_name),*
-                        }
+        let response_fn_name = self.client.then(|| quote! {
+            impl #generics Response #generics {
+                fn fn_name(&self) -> &'static str {
+                    match self {
+                        #(#variant_to_name),*
                     }
                 }
+            }
+        });
 
+        let server_items = self.server.then(|| {
+            let supertraits = self.methods.iter().filter_map(|method| match &method.ret {
+                ReturnType::Inherited { service } => {
+                    let mut server = service.clone();
+                    if let Some(last) = server.segments.last_mut() {
+                        last.ident = format_ident!("{}Server", last.ident);
+                    }
+                    Some(server)
+                }
+                _ => None,
+            });
+            // a service which inherits this service serves it with a handler which borrows its server
+            let forward_fns = self.methods.iter().map(|method| method.forward_fn(&server, generics));
+            quote! {
                 #(
                     #(#[doc = #docs])*
                     ///
                 )*
                 /// This is the trait which is used by the server side in order to serve the client
-                pub trait #server #generics: Send + Sync {
+                pub trait #server #generics: Send + Sync #(+ #supertraits)* {
                     #(#server_fns)*
                 }
 
+                impl<_Server: #server #generics + ?Sized #(, #gen_params)*> #server #generics for &_Server {
+                    #(#forward_fns)*
+                }
+
                 /// A [Handler](Handler) which handles requests/responses for a given service
                 #[derive(Debug, Clone)]
                 pub struct #handler<_Server #(,#gen_params)*>(_Server, #phantom_data);
@@ -279,6 +440,7 @@ impl ToTokens for Rpc {
                             _ => panic!("This is a streaming method, must call handle_streaming")
                         }
                     }
+                    #handle_borrowed
                     async fn handle_stream_response<S: Sink<Response #generics, Error = Infallible> + Send + 'static>(
                         &self,
                         request: Request #generics,
@@ -290,14 +452,22 @@ impl ToTokens for Rpc {
                         }
                     }
                 }
+            }
+        });
 
+        let client_items = self.client.then(|| {
+            let async_client_fns = self.client_fns(true, generics);
+            let blocking_client_fns = self.client_fns(false, generics);
+            let async_transport = format!(" [transport]({}) before returning the response", self.krate_path("client::AsyncClient"));
+            let blocking_transport = format!(" [transport]({}) before returning the response", self.krate_path("client::BlockingClient"));
+            quote! {
                 #(
                     #(#[doc = #docs])*
                     ///
                 )*
                 /// This is the async client for the service, it produces requests from method calls
                 /// (including chained method calls) and sends the requests with the given
-                /// [transport](::trait_rpc::AsyncClient) before returning the response
+                #[doc = #async_transport]
                 ///
                 /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
                 #[derive(Debug, Copy, Clone)]
@@ -313,7 +483,7 @@ impl ToTokens for Rpc {
                 )*
                 /// This is the blocking client for the service, it produces requests from method calls
                 /// (including chained method calls) and sends the requests with the given
-                /// [transport](::trait_rpc::AsyncClient) before returning the response
+                #[doc = #blocking_transport]
                 ///
                 /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
                 #[derive(Debug, Copy, Clone)]
@@ -322,6 +492,116 @@ impl ToTokens for Rpc {
                     #(#blocking_client_fns)*
                 }
             }
+        });
+
+        // the derived implementations are only called once the method of a request has been
+        // checked, so that an unknown method can be told apart from any other invalid request
+        let serialize_bounds = self.serde_bounds(&quote!(Serialize));
+        let deserialize_bounds = self.serde_bounds(&quote!(Deserialize<'de>));
+        let request_serde = quote! {
+            impl #generics Serialize for Request #generics #serialize_bounds {
+                fn serialize<S: #krate::serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    Self::serialize(self, serializer)
+                }
+            }
+
+            impl<'de #(, #gen_params)*> Deserialize<'de> for Request #generics #deserialize_bounds {
+                fn deserialize<D: #krate::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::deserialize(#krate::de::RequestDeserializer::new(deserializer, <Self as #krate::Request>::METHODS))
+                }
+            }
+        };
+
+        let rpc_doc = format!(" This is the [Rpc]({}) definition for this service", self.krate_path("Rpc"));
+        quote! {
+            #[allow(unused_imports, reason = "These might not always be used, but they should be available in this module anyway")]
+            #imports
+
+            #[allow(unused_imports, reason = "These might not always be used, but it's easier to include always")]
+            mod #module {
+                use super::*;
+                use std::convert::Infallible;
+                use std::marker::PhantomData;
+                use #krate::{
+                    client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+                    futures::sink::{Sink, SinkExt},
+                    futures::stream::{Stream, StreamExt},
+                    serde::{Deserialize, Serialize},
+                    server::{Handler, IntoHandler},
+                    Rpc, RpcWithServer
+                };
+
+                #(
+                    #(#[doc = #docs])*
+                    ///
+                )*
+                #[doc = #rpc_doc]
+                pub struct #service #generics #phantom_data;
+
+                impl #generics Rpc for #service #generics #(where #(#maybe_generics: Send + 'static),*)* {
+                    #rpc_clients
+                    type Request = Request #generics;
+                    type BorrowedRequest<'req> = #borrowed_request_type;
+                    type Response = Response #generics;
+                    #rpc_client_fns
+                }
+
+                #rpc_with_server
+
+                #[derive(Debug, Serialize, Deserialize #(, #derives)*)]
+                #[serde(crate = #serde_crate)]
+                #[serde(remote = "Self")]
+                #request_tagging
+                pub enum Request #generics {
+                    #(#request_variants,)*
+                }
+
+                #request_serde
+
+                impl #generics #krate::Request for Request #generics {
+                    const METHODS: &'static [#krate::MethodInfo] = &[#(#method_info),*];
+                    type Ref<'req> = #request_ref_type;
+
+                    fn is_streaming_response(&self) -> bool {
+                        match self {
+                            #(#request_streaming),*
+                        }
+                    }
+
+                    fn method_name(&self) -> &'static str {
+                        match self {
+                            #(#request_to_name),*
+                        }
+                    }
+                }
+
+                #(#default_fns)*
+
+                #request_ref
+
+                #borrowed_request
+
+                #[derive(Debug, Serialize, Deserialize #(, #derives)*)]
+                #[serde(crate = #serde_crate)]
+                #response_tagging
+                pub enum Response #generics {
+                    #(#response_variants,)*
+                }
+
+                impl #generics #krate::Response for Response #generics {
+                    fn is_error(&self) -> bool {
+                        match self {
+                            #(#response_is_error),*
+                        }
+                    }
+                }
+
+                #response_fn_name
+
+                #server_items
+
+                #client_items
+            }
         }
     }
 
@@ -330,7 +610,610 @@ impl ToTokens for Rpc {
     }
 }
 
+impl Method {
+    /// The pattern of each argument, which binds the argument by its name
+    fn params(&self) -> Vec<TokenStream> {
+        self.args.iter().map(|pat| pat.pat.to_token_stream()).collect()
+    }
+
+    /// The name of each argument, this is only used with `named_args`, which requires that every
+    /// argument is named
+    fn arg_names(&self) -> impl Iterator<Item = Ident> {
+        self.args.iter().filter_map(|pat| match &*pat.pat {
+            Pat::Ident(pat) => Some(pat.ident.clone()),
+            _ => None,
+        })
+    }
+
+    /// The names of the fields in this method's request variant, the nested request is named `request`
+    fn field_names(&self, nested: bool) -> impl Iterator<Item = Ident> {
+        self.arg_names().chain(nested.then(|| format_ident!("request")))
+    }
+
+    /// Declare the fields of this method's request variant, from the attributes and type of each
+    /// argument and of the nested request
+    ///
+    /// These are a tuple, or a struct keyed by the argument names when `named` is set
+    fn declare_fields(&self, named: bool, args: Vec<(TokenStream, TokenStream)>, nested: Option<(TokenStream, TokenStream)>) -> TokenStream {
+        if named {
+            let field_names = self.field_names(nested.is_some());
+            let fields = field_names.zip(args.into_iter().chain(nested)).map(|(name, (attrs, ty))| quote!(#attrs #name: #ty));
+            quote!({ #(#fields),* })
+        } else {
+            let fields = args.into_iter().chain(nested).map(|(attrs, ty)| quote!(#attrs #ty));
+            quote!((#(#fields),*))
+        }
+    }
+
+    /// The fields of this method's request variant, either to construct it or to match it, from the
+    /// value or pattern of each argument and of the nested request
+    fn request_fields(&self, named: bool, args: Vec<TokenStream>, nested: Option<TokenStream>) -> TokenStream {
+        if named {
+            let field_names = self.field_names(nested.is_some());
+            let fields = field_names.zip(args.into_iter().chain(nested)).map(|(name, value)| {
+                if name == value.to_string() {
+                    quote!(#name)
+                } else {
+                    quote!(#name: #value)
+                }
+            });
+            quote!({ #(#fields),* })
+        } else {
+            let fields = args.into_iter().chain(nested);
+            quote!((#(#fields),*))
+        }
+    }
+
+    /// The name of the function which returns the default value of an argument, as it is read by
+    /// either the owned or the borrowed request
+    fn default_fn(&self, index: usize, borrowed: bool) -> Ident {
+        let arg = match &*self.args[index].pat {
+            Pat::Ident(pat) => pat.ident.to_string(),
+            _ => index.to_string(),
+        };
+        let suffix = if borrowed { "_borrowed" } else { "" };
+        format_ident!("default_{}_{}{}", self.name, arg, suffix)
+    }
+
+    /// The serde attributes which set the default of each argument, for either the owned or the
+    /// borrowed request
+    fn default_attrs(&self, borrowed: bool) -> Vec<TokenStream> {
+        self.defaults.iter().zip(&self.borrows).enumerate().map(|(index, (default, borrow))| match default {
+            Some(ArgDefault::Default) => quote!(#[serde(default)]),
+            Some(ArgDefault::Value(_)) => {
+                let path = self.default_fn(index, borrowed && borrow.is_some()).to_string();
+                quote!(#[serde(default = #path)])
+            }
+            None => TokenStream::new(),
+        }).collect()
+    }
+
+    /// The functions which return the default of each argument that declares a default value, the
+    /// value is converted to the type owned by the request, and wrapped in a [Cow](std::borrow::Cow)
+    /// for a borrowed request
+    fn default_fns(&self, borrowed_request: bool) -> TokenStream {
+        let types = self.owned_types();
+        let fns = self.defaults.iter().zip(&self.borrows).zip(types).enumerate().filter_map(|(index, ((default, borrowed), ty))| {
+            let Some(ArgDefault::Value(value)) = default else {
+                return None;
+            };
+            let name = self.default_fn(index, false);
+            let owned = self.to_owned(index, value);
+            let borrowed_fn = borrowed.as_ref().filter(|_| borrowed_request).map(|borrowed| {
+                let borrowed_name = self.default_fn(index, true);
+                let borrowed = borrowed.ty.to_type();
+                quote! {
+                    fn #borrowed_name() -> ::std::borrow::Cow<'static, #borrowed> {
+                        ::std::borrow::Cow::Owned(#name())
+                    }
+                }
+            });
+            Some(quote! {
+                fn #name() -> #ty {
+                    #owned
+                }
+
+                #borrowed_fn
+            })
+        });
+        quote!(#(#fns)*)
+    }
+
+    /// The type of each argument in the request, arguments which are borrowed by the server are
+    /// owned by the request
+    fn owned_types(&self) -> Vec<Type> {
+        self.args.iter().zip(&self.borrows).map(|(pat, borrow)| match borrow {
+            Some(Borrow { ty: Borrowed::Str, .. }) => parse_quote!(String),
+            Some(Borrow { ty: Borrowed::Bytes, .. }) => parse_quote!(Vec<u8>),
+            Some(Borrow { ty: Borrowed::Other(ty), .. }) => parse_quote!(<#ty as ::std::borrow::ToOwned>::Owned),
+            None => *pat.ty.clone(),
+        }).collect()
+    }
+
+    /// Convert each argument, as declared in the trait, to the value owned by the request
+    fn to_owned_args(&self) -> Vec<TokenStream> {
+        self.args.iter().enumerate().map(|(index, pat)| self.to_owned(index, &pat.pat)).collect()
+    }
+
+    /// Convert a value of an argument, as declared in the trait, to the value owned by the request
+    fn to_owned(&self, index: usize, value: &impl ToTokens) -> TokenStream {
+        match (&self.borrows[index], &self.refs[index]) {
+            (Some(Borrow { cow: true, .. }), _) => quote!(::std::borrow::Cow::into_owned(#value)),
+            (Some(Borrow { cow: false, .. }), _) | (None, Some(_)) => quote!(::std::borrow::ToOwned::to_owned(#value)),
+            (None, None) => quote!(#value),
+        }
+    }
+
+    /// Returns true if the client takes any of the arguments by reference, and so sends the request
+    /// which borrows them
+    fn client_borrows(&self) -> bool {
+        self.refs.iter().any(Option::is_some)
+    }
+
+    /// The parameters of the client method, which take each reference as it is declared
+    fn client_params(&self, lifetime: Option<&Lifetime>) -> Vec<TokenStream> {
+        self.args.iter().zip(&self.refs).map(|(pat, reference)| match reference {
+            Some(Type::Reference(reference)) if lifetime.is_some() => {
+                let pat = &pat.pat;
+                let mut reference = reference.clone();
+                reference.lifetime = lifetime.cloned();
+                quote!(#pat: #reference)
+            }
+            Some(ty) => {
+                let pat = &pat.pat;
+                quote!(#pat: #ty)
+            }
+            None => pat.to_token_stream(),
+        }).collect()
+    }
+
+    /// The generics, receiver and params of a client method which streams its responses, the stream
+    /// borrows both the client and the args which it sends by reference, so these share a lifetime
+    fn client_stream_signature(&self) -> (Option<TokenStream>, TokenStream, Vec<TokenStream>) {
+        let lifetime = self.client_borrows().then(|| Lifetime::new("'req", Span::call_site()));
+        let params = self.client_params(lifetime.as_ref());
+        match lifetime {
+            Some(lifetime) => (Some(quote!(<#lifetime>)), quote!(&#lifetime self), params),
+            None => (None, quote!(&self), params),
+        }
+    }
+
+    /// A client method for a method which streams its responses
+    fn client_stream_fn(&self, ret: &Type, generics: &Generics, named: bool) -> TokenStream {
+        let name = &self.name;
+        let name_str = &self.wire_name;
+        let (generic, receiver, params) = self.client_stream_signature();
+        let request = self.client_request(named);
+        let variant = ident_ccase!(pascal, name);
+        let docs = &self.docs;
+        let (send, client) = self.client_send_streaming(generics);
+        quote! {
+            #(#[doc = #docs])*
+            pub async fn #name #generic(#receiver #(, #params)*) -> Result<impl Stream<Item = Result<#ret, _Client::Error>>, _Client::Error> where #client {
+                let stream = self.0.#send(#request).await?;
+                Ok(
+                     stream
+                         .map(|value| {
+                             match value {
+                                 Ok(Response::#variant(value)) => Ok(value),
+                                 Ok(other) => {
+                                     Err(WrongResponseType::new(#name_str, other.fn_name()).into())
+                                 }
+                                 Err(error) => Err(error.into()),
+                             }
+                         }),
+
+                )
+            }
+        }
+    }
+
+    /// The request which the client sends for this method, the request borrows the arguments which
+    /// the client takes by reference
+    fn client_request(&self, named: bool) -> TokenStream {
+        let variant = ident_ccase!(pascal, self.name);
+        if self.client_borrows() {
+            let values = self.args.iter().zip(&self.refs).zip(self.to_owned_args()).map(|((pat, reference), owned)| {
+                if reference.is_some() {
+                    pat.pat.to_token_stream()
+                } else {
+                    owned
+                }
+            }).collect();
+            let fields = self.request_fields(named, values, None);
+            quote!(RequestRef::#variant #fields)
+        } else {
+            let fields = self.request_fields(named, self.to_owned_args(), None);
+            quote!(Request::#variant #fields)
+        }
+    }
+
+    /// Convert each value owned by the request to the argument as declared in the trait
+    fn args_from_owned(&self) -> Vec<TokenStream> {
+        self.args.iter().zip(&self.borrows).map(|(pat, borrow)| {
+            let pat = &pat.pat;
+            match borrow {
+                Some(Borrow { cow: false, .. }) => quote!(&#pat),
+                Some(Borrow { cow: true, .. }) => quote!(::std::borrow::Cow::Owned(#pat)),
+                None => quote!(#pat),
+            }
+        }).collect()
+    }
+
+    /// Borrow each argument which the server receives as an owned value in place of a reference, so
+    /// that a default body sees the arguments as they are declared in the trait
+    fn default_body_refs(&self) -> Vec<TokenStream> {
+        self.args.iter().zip(&self.refs).zip(&self.borrows).filter_map(|((arg, reference), borrow)| {
+            let Pat::Ident(pat) = &*arg.pat else {
+                return None;
+            };
+            let ident = &pat.ident;
+            let reference = reference.as_ref().filter(|_| borrow.is_none())?;
+            Some(quote!(let #pat: #reference = ::std::borrow::Borrow::borrow(&#ident);))
+        }).collect()
+    }
+
+    /// Convert each value in the borrowed request to the argument as declared in the trait, every
+    /// borrowed value is read as a [Cow](std::borrow::Cow), since it may not always be possible to
+    /// borrow it
+    fn args_from_borrowed(&self) -> Vec<TokenStream> {
+        self.args.iter().zip(&self.borrows).map(|(pat, borrow)| {
+            let pat = &pat.pat;
+            if matches!(borrow, Some(Borrow { cow: false, .. })) {
+                quote!(&#pat)
+            } else {
+                quote!(#pat)
+            }
+        }).collect()
+    }
+
+    /// The server function for this method on a reference to the server, which calls the server
+    ///
+    /// The arguments are named, since they may be declared with any pattern
+    fn forward_fn(&self, server: &Ident, generics: &Generics) -> TokenStream {
+        let name = &self.name;
+        let (args, params): (Vec<_>, Vec<_>) = self.args.iter().enumerate().map(|(index, pat)| {
+            let arg = match &*pat.pat {
+                Pat::Ident(pat) => pat.ident.clone(),
+                _ => format_ident!("arg{index}"),
+            };
+            let ty = &pat.ty;
+            (arg.clone(), quote!(#arg: #ty))
+        }).unzip();
+        let call = quote!(<_Server as #server #generics>::#name);
+        match &self.ret {
+            ReturnType::Simple(ret) => {
+                let ret = self.with_error(ret);
+                quote! {
+                    fn #name(&self #(, #params)*) -> impl Future<Output=#ret> + Send {
+                        #call(*self #(, #args)*)
+                    }
+                }
+            }
+            ReturnType::Nested { service: path } => {
+                quote! {
+                    fn #name(&self #(, #params)*) -> impl Future<Output = impl IntoHandler<#path>> + Send {
+                        #call(*self #(, #args)*)
+                    }
+                }
+            }
+            ReturnType::Streaming(ret) => {
+                let ret = self.with_error(ret);
+                quote! {
+                    fn #name(&self, sink: impl Sink<#ret, Error = Infallible> + Send + 'static #(, #params)*) -> impl Future<Output=()> + Send {
+                        #call(*self, sink #(, #args)*)
+                    }
+                }
+            }
+            ReturnType::Inherited { .. } => TokenStream::new(),
+        }
+    }
+
+    /// The type which is returned by the server for this method, including the application error
+    fn with_error(&self, ty: &Type) -> Type {
+        self.error
+            .as_ref()
+            .map_or_else(|| ty.clone(), |error| parse_quote!(Result<#ty, #error>))
+    }
+
+    /// A client method for a method which declares an application error, the client error is
+    /// extended with the application error using [`WithApplicationError`]
+    fn client_fn_with_error(&self, ret: &Type, error: &Type, is_async: bool, generics: &Generics, named: bool) -> TokenStream {
+        let name = &self.name;
+        let name_str = &self.wire_name;
+        let params = self.client_params(None);
+        let request = self.client_request(named);
+        let variant = ident_ccase!(pascal, name);
+        let docs = &self.docs;
+        let with_error = quote!(<_Client::Error as WithApplicationError<#error>>);
+        match &self.ret {
+            ReturnType::Simple(_) => {
+                let (async_, await_) = if is_async {
+                    (quote!(async), quote!(.await))
+                } else {
+                    (TokenStream::new(), TokenStream::new())
+                };
+                let (send, client) = self.client_send(is_async, generics);
+                quote! {
+                    #(#[doc = #docs])*
+                    pub #async_ fn #name(&self #(, #params)*) -> Result<#ret, #with_error::Output>
+                    where
+                        #client
+                        _Client::Error: WithApplicationError<#error>,
+                    {
+                        match self.0.#send(#request)#await_.map_err(#with_error::from_client)? {
+                            Response::#variant(Ok(value)) => Ok(value),
+                            Response::#variant(Err(error)) => Err(#with_error::application(error)),
+                            other => Err(#with_error::from_client(WrongResponseType::new(#name_str, other.fn_name()).into())),
+                        }
+                    }
+                }
+            }
+            ReturnType::Streaming(_) if is_async => {
+                let (send, client) = self.client_send_streaming(generics);
+                let (generic, receiver, params) = self.client_stream_signature();
+                quote! {
+                    #(#[doc = #docs])*
+                    pub async fn #name #generic(#receiver #(, #params)*) -> Result<impl Stream<Item = Result<#ret, #with_error::Output>>, #with_error::Output>
+                    where
+                        #client
+                        _Client::Error: WithApplicationError<#error>,
+                    {
+                        let stream = self.0.#send(#request).await.map_err(#with_error::from_client)?;
+                        Ok(
+                             stream
+                                 .map(|value| {
+                                     match value {
+                                         Ok(Response::#variant(Ok(value))) => Ok(value),
+                                         Ok(Response::#variant(Err(error))) => Err(#with_error::application(error)),
+                                         Ok(other) => {
+                                             Err(#with_error::from_client(WrongResponseType::new(#name_str, other.fn_name()).into()))
+                                         }
+                                         Err(error) => Err(#with_error::from_client(error)),
+                                     }
+                                 }),
+                        )
+                    }
+                }
+            }
+            ReturnType::Streaming(_) | ReturnType::Nested { .. } | ReturnType::Inherited { .. } => TokenStream::new(),
+        }
+    }
+
+    /// The function which the client sends this method's request with, and the bound which the
+    /// client must satisfy in order to send a request which borrows its arguments
+    fn client_send(&self, is_async: bool, generics: &Generics) -> (Ident, Option<TokenStream>) {
+        if !self.client_borrows() {
+            return (format_ident!("send"), None);
+        }
+        let client = if is_async {
+            quote!(AsyncRefClient)
+        } else {
+            quote!(BlockingRefClient)
+        };
+        (format_ident!("send_ref"), Some(quote!(_Client: #client<Request #generics, Response #generics>,)))
+    }
+
+    /// The function which the client sends this method's request with, and the bound which the
+    /// client must satisfy in order to stream the responses
+    fn client_send_streaming(&self, generics: &Generics) -> (Ident, TokenStream) {
+        if self.client_borrows() {
+            (format_ident!("send_ref_streaming_response"), quote!(_Client: StreamRefClient<Request #generics, Response #generics>,))
+        } else {
+            (format_ident!("send_streaming_response"), quote!(_Client: StreamClient<Request #generics, Response #generics>,))
+        }
+    }
+}
+
+impl Borrowed {
+    /// The type which is borrowed
+    fn to_type(&self) -> Type {
+        match self {
+            Self::Str => parse_quote!(str),
+            Self::Bytes => parse_quote!([u8]),
+            Self::Other(ty) => (**ty).clone(),
+        }
+    }
+}
+
 impl Rpc {
+    /// The path to an item in the `trait_rpc` crate, as a string for serde attributes
+    /// The bounds on the generic types of the request, for an implementation of `bound`
+    fn serde_bounds(&self, bound: &TokenStream) -> TokenStream {
+        let params: Vec<_> = self.generics.type_params().map(|param| &param.ident).collect();
+        if params.is_empty() {
+            TokenStream::new()
+        } else {
+            quote!(where #(#params: #bound),*)
+        }
+    }
+
+    fn krate_path(&self, item: &str) -> String {
+        let krate = self.krate.to_token_stream().to_string().replace(' ', "");
+        format!("{krate}::{item}")
+    }
+
+    /// Returns true if the server reads a separate request type which borrows from the request
+    /// body, a nested service or a supertrait may borrow even if this service does not
+    fn borrows(&self) -> bool {
+        self.server && self.borrow && self.methods.iter().any(|method| {
+            method.borrows.iter().any(Option::is_some) || matches!(method.ret, ReturnType::Nested { .. } | ReturnType::Inherited { .. })
+        })
+    }
+
+    /// Returns true if the client sends a separate request type which borrows its arguments, this
+    /// is required by a nested service or a supertrait, which may borrow even if this service does not
+    fn has_request_ref(&self) -> bool {
+        self.methods.iter().any(|method| {
+            method.client_borrows() || matches!(method.ret, ReturnType::Nested { .. } | ReturnType::Inherited { .. })
+        })
+    }
+
+    /// The request type which is sent by the client when it borrows the arguments, this is encoded
+    /// the same as the owned request
+    fn request_ref(&self, tagging: Option<&TokenStream>, streaming: &[TokenStream], request_to_name: &[TokenStream]) -> TokenStream {
+        let krate = &self.krate;
+        let serde_crate = self.krate_path("serde");
+        let bytes = self.krate_path("borrow::bytes");
+        let generics = &self.generics;
+        let gen_params: Vec<_> = generics.params.iter().collect();
+        let variants = self.methods.iter().map(|method| {
+            let wire_name = &method.wire_name;
+            let variant = ident_ccase!(pascal, method.name);
+            let nested = match &method.ret {
+                ReturnType::Nested { service } | ReturnType::Inherited { service } => {
+                    Some((TokenStream::new(), quote!(<<#service as Rpc>::Request as #krate::Request>::Ref<'req>)))
+                }
+                ReturnType::Simple(_) | ReturnType::Streaming(_) => None,
+            };
+            let fields = method.owned_types().into_iter().zip(&method.refs).zip(&method.borrows).map(|((owned, reference), borrow)| {
+                let attrs = match borrow {
+                    Some(Borrow { ty: Borrowed::Bytes, .. }) => quote!(#[serde(with = #bytes)]),
+                    _ => TokenStream::new(),
+                };
+                // the args of a nested service are owned by its client, so they are borrowed
+                let ty = match reference {
+                    _ if nested.is_some() => quote!(&'req #owned),
+                    Some(Type::Reference(reference)) => {
+                        let elem = &reference.elem;
+                        quote!(&'req #elem)
+                    }
+                    _ => owned.to_token_stream(),
+                };
+                (attrs, ty)
+            }).collect();
+            let fields = method.declare_fields(self.named_args, fields, nested);
+            quote!(
+                #[serde(rename = #wire_name)]
+                #variant #fields
+            )
+        });
+        quote! {
+            #[derive(Debug, Serialize)]
+            #[serde(crate = #serde_crate)]
+            #tagging
+            pub enum RequestRef<'req #(, #gen_params)*> {
+                #(#variants,)*
+            }
+
+            impl<'req #(, #gen_params)*> #krate::Request for RequestRef<'req #(, #gen_params)*> {
+                const METHODS: &'static [#krate::MethodInfo] = <Request #generics as #krate::Request>::METHODS;
+                type Ref<'a> = Self;
+
+                fn is_streaming_response(&self) -> bool {
+                    match self {
+                        #(#streaming),*
+                    }
+                }
+
+                fn method_name(&self) -> &'static str {
+                    match self {
+                        #(#request_to_name),*
+                    }
+                }
+            }
+        }
+    }
+
+    /// The request type which is read by the server, and its conversion to the owned request
+    fn borrowed_request(&self, tagging: Option<&TokenStream>, streaming: &[TokenStream], request_to_name: &[TokenStream]) -> TokenStream {
+        let krate = &self.krate;
+        let serde_crate = self.krate_path("serde");
+        let bytes = self.krate_path("borrow::bytes");
+        let generics = &self.generics;
+        let gen_params: Vec<_> = generics.params.iter().collect();
+        let (variants, into_owned): (Vec<_>, Vec<_>) = self.methods.iter().map(|method| {
+            let wire_name = &method.wire_name;
+            let aliases = &method.aliases;
+            let variant = ident_ccase!(pascal, method.name);
+            let params = method.params();
+            let args = method.args.iter().zip(&method.borrows).map(|(pat, borrow)| {
+                let pat = &pat.pat;
+                if borrow.is_some() {
+                    quote!(::std::borrow::Cow::into_owned(#pat))
+                } else {
+                    quote!(#pat)
+                }
+            }).collect::<Vec<_>>();
+            let fields = method.args.iter().zip(&method.borrows).zip(method.default_attrs(true)).map(|((pat, borrow), default)| match borrow {
+                Some(Borrow { ty: Borrowed::Bytes, .. }) => (
+                    quote!(#[serde(borrow, with = #bytes)] #default),
+                    quote!(::std::borrow::Cow<'req, [u8]>),
+                ),
+                Some(Borrow { ty, .. }) => {
+                    let ty = ty.to_type();
+                    (quote!(#[serde(borrow)] #default), quote!(::std::borrow::Cow<'req, #ty>))
+                }
+                None => (default, pat.ty.to_token_stream()),
+            }).collect();
+            let (fields, into_owned) = if let ReturnType::Nested { service } | ReturnType::Inherited { service } = &method.ret {
+                let nested = (quote!(#[serde(borrow)]), quote!(<#service as Rpc>::BorrowedRequest<'req>));
+                let borrowed = method.request_fields(self.named_args, params, Some(quote!(request)));
+                let owned = method.request_fields(self.named_args, args, Some(quote!(request.into())));
+                (
+                    method.declare_fields(self.named_args, fields, Some(nested)),
+                    quote!(BorrowedRequest::#variant #borrowed => Request::#variant #owned),
+                )
+            } else {
+                let borrowed = method.request_fields(self.named_args, params, None);
+                let owned = method.request_fields(self.named_args, args, None);
+                (
+                    method.declare_fields(self.named_args, fields, None),
+                    quote!(BorrowedRequest::#variant #borrowed => Request::#variant #owned),
+                )
+            };
+            (
+                quote!(
+                    #[serde(rename = #wire_name)]
+                    #(#[serde(alias = #aliases)])*
+                    #variant #fields
+                ),
+                into_owned,
+            )
+        }).unzip();
+        let deserialize_bounds = self.serde_bounds(&quote!(Deserialize<'de>));
+        quote! {
+            #[derive(Debug, Deserialize)]
+            #[serde(crate = #serde_crate)]
+            #[serde(remote = "Self")]
+            #tagging
+            pub enum BorrowedRequest<'req #(, #gen_params)*> {
+                #(#variants,)*
+            }
+
+            impl<'de: 'req, 'req #(, #gen_params)*> Deserialize<'de> for BorrowedRequest<'req #(, #gen_params)*> #deserialize_bounds {
+                fn deserialize<D: #krate::serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    Self::deserialize(#krate::de::RequestDeserializer::new(deserializer, <Self as #krate::Request>::METHODS))
+                }
+            }
+
+            impl<'req #(, #gen_params)*> #krate::Request for BorrowedRequest<'req #(, #gen_params)*> {
+                const METHODS: &'static [#krate::MethodInfo] = <Request #generics as #krate::Request>::METHODS;
+                type Ref<'a> = Self;
+
+                fn is_streaming_response(&self) -> bool {
+                    match self {
+                        #(#streaming),*
+                    }
+                }
+
+                fn method_name(&self) -> &'static str {
+                    match self {
+                        #(#request_to_name),*
+                    }
+                }
+            }
+
+            impl<'req #(, #gen_params)*> From<BorrowedRequest<'req #(, #gen_params)*>> for Request #generics {
+                fn from(request: BorrowedRequest<'req #(, #gen_params)*>) -> Self {
+                    match request {
+                        #(#into_owned),*
+                    }
+                }
+            }
+        }
+    }
+
     fn client_fns(&self, is_async: bool, generics: &Generics) -> impl Iterator<Item=TokenStream> {
         let await_ = if is_async {
             vec![quote!(.await)]
@@ -344,9 +1227,10 @@ impl Rpc {
         };
         self.methods.iter().map(move |method| {
             let name = &method.name;
-            let name_str = name.to_string();
-            let params = &method.args;
-            let args = method.args.iter().map(|pat| &pat.pat);
+            let name_str = &method.wire_name;
+            let params = method.client_params(None);
+            let args = method.to_owned_args();
+            let request = method.client_request(self.named_args);
             let variant = ident_ccase!(pascal, name);
             let docs = &method.docs;
             let docs = quote! {
@@ -359,27 +1243,42 @@ impl Rpc {
             };
             let new_client = ident_ccase!(snake, client);
             match &method.ret {
+                ReturnType::Simple(ret) | ReturnType::Streaming(ret) if let Some(error) = &method.error => {
+                    method.client_fn_with_error(ret, error, is_async, generics, self.named_args)
+                }
                 ReturnType::Simple(ret) => {
+                    let (send, client) = method.client_send(is_async, generics);
+                    let client = client.map(|client| quote!(where #client));
                     quote! {
                         #docs
-                        pub #(#async_)* fn #name(&self #(, #params)*) -> Result<#ret, _Client::Error> {
-                            match self.0.send(Request::#variant(#(#args),*))#(#await_)*? {
+                        pub #(#async_)* fn #name(&self #(, #params)*) -> Result<#ret, _Client::Error> #client {
+                            match self.0.#send(#request)#(#await_)*? {
                                 Response::#variant(value) => Ok(value),
                                 other => Err(WrongResponseType::new(#name_str, other.fn_name()).into()),
                             }
                         }
                     }
                 }
-                ReturnType::Nested { service: nested } => { // TODO account for sub-service error
+                ReturnType::Nested { service: nested } | ReturnType::Inherited { service: nested } => { // TODO account for sub-service error
                     let to_inner = format_ident!("{name}_to_inner");
                     let to_outer = format_ident!("{name}_to_outer");
+                    let to_outer_ref = format_ident!("{name}_to_outer_ref");
                     let variant = ident_ccase!(pascal, name);
-                    let args = method.args.iter().map(|pat| &pat.pat).collect::<Vec<_>>();
-                    let types = method.args.iter().map(|pat| &pat.ty).collect::<Vec<_>>();
+                    let pats = method.params();
+                    let outer = method.request_fields(self.named_args, pats.clone(), Some(quote!(inner)));
+                    let types = method.owned_types();
+                    // the borrowed request borrows the args which are owned by the client
+                    let borrowed_args = if types.is_empty() { quote!(_) } else { quote!(args) };
+                    let borrowed = (0..types.len()).map(|index| {
+                        let index = syn::Index::from(index);
+                        quote!(&args.#index)
+                    }).collect();
+                    let outer_ref = method.request_fields(self.named_args, borrowed, Some(quote!(inner)));
+                    let krate = &self.krate;
                     quote! {
                         #docs
                         pub fn #name(&self #(, #params)*) -> <#nested as Rpc>::#client<MappedClient<_Client, <#nested as Rpc>::Request, Request, <#nested as Rpc>::Response, Response, (#(#types,)*)>> {
-                            #nested::#new_client(MappedClient::new(self.0.clone(), (#(#args,)*), Self::#to_inner, Self::#to_outer))
+                            #nested::#new_client(MappedClient::<_, <#nested as Rpc>::Request, Request, <#nested as Rpc>::Response, Response, _>::new(self.0.clone(), (#(#args,)*), Self::#to_inner, Self::#to_outer, Self::#to_outer_ref))
                         }
 
                         fn #to_inner(outer: Result<Response, WrongResponseType>) -> Result<<#nested as Rpc>::Response, WrongResponseType> {
@@ -390,36 +1289,17 @@ impl Rpc {
                             }
                         }
 
-                        fn #to_outer((#(#args,)*): (#(#types,)*), inner: <#nested as Rpc>::Request) -> Request {
-                            Request::#variant(#(#args,)*inner)
+                        fn #to_outer((#(#pats,)*): (#(#types,)*), inner: <#nested as Rpc>::Request) -> Request {
+                            Request::#variant #outer
                         }
-                    }
-                }
-                ReturnType::Streaming(ret) => {
-                    if is_async {
-                        quote! {
-                            #docs
-                            pub async fn #name(&self #(, #params)*) -> Result<impl Stream<Item = Result<#ret, _Client::Error>>, _Client::Error> where _Client: StreamClient<Request #generics, Response #generics> {
-                                let stream = self.0.send_streaming_response(Request::#variant(#(#args),*)).await?;
-                                Ok(
-                                     stream
-                                         .map(|value| {
-                                             match value {
-                                                 Ok(Response::#variant(value)) => Ok(value),
-                                                 Ok(other) => {
-                                                     Err(WrongResponseType::new(#name_str, other.fn_name()).into())
-                                                 }
-                                                 Err(error) => Err(error.into()),
-                                             }
-                                         }),
-
-                                )
-                            }
+
+                        fn #to_outer_ref<'req>(#borrowed_args: &'req (#(#types,)*), inner: <<#nested as Rpc>::Request as #krate::Request>::Ref<'req>) -> RequestRef<'req> {
+                            RequestRef::#variant #outer_ref
                         }
-                    } else {
-                        quote! {}
                     }
                 }
+                ReturnType::Streaming(ret) if is_async => method.client_stream_fn(ret, generics, self.named_args),
+                ReturnType::Streaming(_) => quote! {},
             }
         })
     }
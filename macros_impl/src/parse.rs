@@ -1,28 +1,118 @@
-use crate::{Method, Rpc};
+use crate::{ArgDefault, Borrow, Borrowed, Method, Rpc};
+use convert_case::{Case, Casing};
+use proc_macro2::{Ident, TokenStream, TokenTree};
+use quote::ToTokens;
+use std::iter;
+use syn::meta::ParseNestedMeta;
+use syn::parse::Parser as _;
 use syn::{
-    Attribute, Expr, FnArg, GenericArgument, ItemTrait, Meta, MetaNameValue, PathArguments,
-    PathSegment, ReturnType, TraitItem, TraitItemFn, Type, TypeParamBound, TypePath, parse_quote,
+    Attribute, Expr, FnArg, GenericArgument, ItemTrait, LitBool, LitStr, Meta, MetaNameValue, Pat, PatType,
+    Path, PathArguments, PathSegment, Receiver, ReturnType, Token, TraitBoundModifier, TraitItem, TraitItemFn, Type, TypeParamBound, TypePath, parse_quote,
 };
 
+/// Traits from the standard library which may be bounds of an ordinary trait, but which are not rpc
+/// services, so cannot be supertraits of one
+const MARKER_TRAITS: &[&str] = &["Send", "Sync", "Sized", "Unpin", "Copy", "UnwindSafe", "RefUnwindSafe"];
+
 /// This contains any args in the attribute macro invocation that may affect parsing
-// There are no such args for now, but we will keep this just in case tha changes
-pub struct Parser;
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools, reason = "Each of these is an independent option of the macro")]
+pub struct Parser {
+    /// The application error type for every method which does not declare its own
+    error: Option<Type>,
+    /// Identify methods by their variant index in non-self-describing formats
+    variant_index: bool,
+    /// Let the server borrow arguments from the request body
+    borrow: bool,
+    /// Encode the arguments of each method as a struct, keyed by their names
+    named_args: bool,
+    /// The path to the `trait_rpc` crate, for when it is re-exported by another crate
+    krate: Option<Path>,
+    /// Do not generate the clients
+    skip_client: bool,
+    /// Do not generate the server trait and its handler
+    skip_server: bool,
+    /// Extra traits to derive for the request and response
+    derives: Vec<Path>,
+    /// The case of the method names, as they are encoded
+    rename_all: Option<Case<'static>>,
+}
+
+/// The options of a method, as declared with `#[rpc(...)]` on the method
+#[derive(Default)]
+struct MethodOptions {
+    /// The application error type of the method
+    error: Option<Type>,
+    /// The name of the method as it is encoded
+    rename: Option<String>,
+    /// The other names which the method is decoded from
+    aliases: Vec<String>,
+}
 
 #[allow(clippy::unused_self)]
 impl Parser {
+    pub fn new(args: TokenStream) -> syn::Result<Self> {
+        let mut parser = Self::default();
+        let mut options = Vec::new();
+        syn::meta::parser(|meta| {
+            let option = meta.path.to_token_stream().to_string();
+            if options.contains(&option) {
+                return Err(meta.error("duplicate rpc option"));
+            }
+            options.push(option);
+            if meta.path.is_ident("error") {
+                parser.error = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("variant_index") {
+                parser.variant_index = true;
+                Ok(())
+            } else if meta.path.is_ident("borrow") {
+                parser.borrow = true;
+                Ok(())
+            } else if meta.path.is_ident("named_args") {
+                parser.named_args = true;
+                Ok(())
+            } else if meta.path.is_ident("crate") {
+                parser.krate = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("client") {
+                parser.skip_client = !meta.value()?.parse::<LitBool>()?.value;
+                Ok(())
+            } else if meta.path.is_ident("server") {
+                parser.skip_server = !meta.value()?.parse::<LitBool>()?.value;
+                Ok(())
+            } else if meta.path.is_ident("derive") {
+                meta.parse_nested_meta(|derive| {
+                    if let Some(derived) = derive.path.segments.last()
+                        && ["Debug", "Serialize", "Deserialize"].iter().any(|always| derived.ident == always)
+                    {
+                        return Err(derive.error(format!("`{}` is always derived", derived.ident)));
+                    }
+                    parser.derives.push(derive.path);
+                    Ok(())
+                })
+            } else if meta.path.is_ident("rename_all") {
+                parser.rename_all = Some(case(&meta.value()?.parse()?)?);
+                Ok(())
+            } else {
+                Err(unsupported_option(&meta))
+            }
+        })
+        .parse2(args)?;
+        Ok(parser)
+    }
+
     pub fn rpc(&self, input: ItemTrait) -> syn::Result<Rpc> {
         let mut methods = vec![];
+        for bound in &input.supertraits {
+            methods.push(self.supertrait(bound)?);
+        }
         for item in input.items {
             if let TraitItem::Fn(item) = item {
                 methods.push(self.method(item)?);
             }
         }
-        if !input.supertraits.is_empty() {
-            return Err(syn::Error::new_spanned(
-                input.supertraits,
-                "supertraits are not supported",
-            ));
-        }
+        check_wire_names(&methods)?;
         let docs = input.attrs.iter().filter_map(docs).collect();
         Ok(Rpc {
             docs,
@@ -30,16 +120,75 @@ impl Parser {
             generics: input.generics,
             name: input.ident,
             methods,
+            variant_index: self.variant_index,
+            borrow: self.borrow,
+            named_args: self.named_args,
+            krate: self.krate.clone().unwrap_or_else(|| parse_quote!(::trait_rpc)),
+            client: !self.skip_client,
+            server: !self.skip_server,
+            derives: self.derives.clone(),
         })
     }
 
-    fn method(&self, item: TraitItemFn) -> syn::Result<Method> {
-        if let Some(default) = item.default {
+    /// The name of a method as it is encoded, unless it is renamed
+    fn wire_name(&self, name: &Ident) -> String {
+        self.rename_all.map_or_else(|| name.to_string(), |case| name.to_string().to_case(case))
+    }
+
+    /// A supertrait is another service, its requests are wrapped in a variant named after it
+    fn supertrait(&self, bound: &TypeParamBound) -> syn::Result<Method> {
+        let bound = match bound {
+            TypeParamBound::Trait(bound) => bound,
+            TypeParamBound::Lifetime(lifetime) => {
+                return Err(syn::Error::new_spanned(
+                    lifetime,
+                    "lifetime bounds are not supported, supertraits must be rpc services",
+                ));
+            }
+            bound => return Err(syn::Error::new_spanned(bound, "supertraits must be rpc services")),
+        };
+        if bound.lifetimes.is_some() {
             return Err(syn::Error::new_spanned(
-                default,
-                "default fn is not supported",
+                &bound.lifetimes,
+                "lifetimes not supported here",
             ));
         }
+        if !matches!(bound.modifier, TraitBoundModifier::None) || bound.paren_token.is_some() {
+            return Err(syn::Error::new_spanned(bound, "supertraits must be rpc services"));
+        }
+        let Some(service) = bound.path.segments.last() else {
+            return Err(syn::Error::new_spanned(bound, "supertraits must be rpc services"));
+        };
+        if MARKER_TRAITS.iter().any(|marker| service.ident == marker) {
+            return Err(syn::Error::new_spanned(
+                bound,
+                format!("`{}` is a marker trait, supertraits must be rpc services", service.ident),
+            ));
+        }
+        let name = Ident::new(&service.ident.to_string().to_case(Case::Snake), service.ident.span());
+        let wire_name = self.wire_name(&name);
+        let docs = [
+            format!(" The methods inherited from [`{}`], these are called through this method", service.ident),
+            format!(" (eg: `client.{name}().method()`) and are sent as the nested `{wire_name}` method"),
+        ];
+        Ok(Method {
+            docs: docs.iter().map(|docs| parse_quote!(#docs)).collect(),
+            name,
+            wire_name,
+            aliases: vec![],
+            args: vec![],
+            borrows: vec![],
+            refs: vec![],
+            defaults: vec![],
+            ret: super::ReturnType::Inherited {
+                service: bound.path.clone(),
+            },
+            error: None,
+            body: None,
+        })
+    }
+
+    fn method(&self, item: TraitItemFn) -> syn::Result<Method> {
         if let Some(con) = item.sig.constness {
             return Err(syn::Error::new_spanned(con, "const fn is not supported"));
         }
@@ -50,7 +199,8 @@ impl Parser {
             ));
         }
         let name = item.sig.ident.clone();
-        let mut args = Vec::with_capacity(item.sig.inputs.len() - 1);
+        let mut args = Vec::with_capacity(item.sig.inputs.len().saturating_sub(1));
+        let mut defaults = Vec::with_capacity(item.sig.inputs.len().saturating_sub(1));
         let mut has_self = false;
         for arg in &item.sig.inputs {
             match arg {
@@ -58,46 +208,84 @@ impl Parser {
                     if has_self {
                         return Err(syn::Error::new_spanned(s, "cannot have multiple receivers"));
                     }
-                    if s.reference.is_none() {
-                        return Err(syn::Error::new_spanned(s, "cannot take owned self value"));
-                    }
-                    if s.mutability.is_some() {
-                        return Err(syn::Error::new_spanned(
-                            s,
-                            "cannot take a mutable self reference",
-                        ));
-                    }
-                    if let Type::Reference(ty) = &*s.ty
-                        && ty.mutability.is_none()
-                        && let Type::Path(ty) = &*ty.elem
-                        && ty.path.segments.len() == 1
-                        && ty.path.segments[0].ident == "Self"
-                    {
-                    } else {
-                        return Err(syn::Error::new_spanned(
-                            s,
-                            "cannot use a smart pointer for self type, must use &Self",
-                        ));
-                    }
+                    check_receiver(s)?;
                     has_self = true;
                 }
-                FnArg::Typed(arg) => args.push(arg.clone()),
+                FnArg::Typed(arg) => {
+                    let mut arg = arg.clone();
+                    defaults.push(arg_default(&mut arg.attrs)?);
+                    args.push(arg);
+                }
             }
         }
         if !has_self {
             return Err(syn::Error::new_spanned(item, "missing self"));
         }
+        let borrows: Vec<_> = args
+            .iter()
+            .map(|arg| if self.borrow { borrow(&arg.ty) } else { Ok(None) })
+            .collect::<syn::Result<_>>()?;
+        let refs = args
+            .iter_mut()
+            .zip(&borrows)
+            .map(|(arg, borrow)| client_ref(arg, borrow.as_ref()))
+            .collect::<syn::Result<_>>()?;
+        if let (Some(asyncness), ReturnType::Type(_, ty)) = (&item.sig.asyncness, &item.sig.output)
+            && future_output(ty)?.is_some()
+        {
+            return Err(syn::Error::new_spanned(
+                asyncness,
+                "an async fn cannot also return a future",
+            ));
+        }
         let ret = self.return_type(item.sig.output)?;
+        if let (Some(body), super::ReturnType::Streaming(_)) = (&item.default, &ret) {
+            return Err(syn::Error::new_spanned(
+                body,
+                "a streaming method cannot have a default body",
+            ));
+        }
+        if self.named_args {
+            check_named_args(&args, &ret)?;
+        }
         let docs = item.attrs.iter().filter_map(docs).collect();
+        let MethodOptions { error, rename, aliases } = method_options(&item.attrs)?;
+        let error = if let super::ReturnType::Nested { .. } = ret {
+            if let Some(error) = error {
+                return Err(syn::Error::new_spanned(
+                    error,
+                    "a nested service cannot have an error type, declare it on the nested service instead",
+                ));
+            }
+            None
+        } else {
+            error.or_else(|| self.error.clone())
+        };
+        let wire_name = rename.unwrap_or_else(|| self.wire_name(&name));
         Ok(Method {
             docs,
             name,
+            wire_name,
+            aliases,
             args,
+            borrows,
+            refs,
+            defaults,
             ret,
+            error,
+            body: item.default,
         })
     }
 
     fn return_type(&self, output: ReturnType) -> syn::Result<super::ReturnType> {
+        // `-> impl Future<Output = T>` is another spelling of `-> T`, as is `async fn`
+        let output = match output {
+            ReturnType::Type(arrow, ty) => {
+                let ty = future_output(&ty)?.map_or(ty, Box::new);
+                ReturnType::Type(arrow, ty)
+            }
+            ReturnType::Default => ReturnType::Default,
+        };
         match output {
             ReturnType::Default => Ok(super::ReturnType::Simple(parse_quote! {()})),
             ReturnType::Type(_, ty) => {
@@ -145,6 +333,257 @@ impl Parser {
     }
 }
 
+/// Find the output of a future, if the type is `impl Future<Output = T>`, which may also be `Send`
+fn future_output(ty: &Type) -> syn::Result<Option<Type>> {
+    let Type::ImplTrait(ty) = ty else {
+        return Ok(None);
+    };
+    let mut output = None;
+    for bound in &ty.bounds {
+        let TypeParamBound::Trait(bound) = bound else {
+            continue;
+        };
+        let Some(PathSegment { ident, arguments }) = bound.path.segments.last() else {
+            continue;
+        };
+        if ident == "Future"
+            && let PathArguments::AngleBracketed(args) = arguments
+            && let [GenericArgument::AssocType(assoc)] = args.args.iter().collect::<Vec<_>>()[..]
+            && assoc.ident == "Output"
+        {
+            output = Some(assoc.ty.clone());
+        }
+    }
+    if output.is_some()
+        && let Some(bound) = ty.bounds.iter().find(|bound| match bound {
+            TypeParamBound::Trait(bound) => bound.path.segments.last().is_none_or(|segment| segment.ident != "Future" && segment.ident != "Send"),
+            _ => false,
+        })
+    {
+        return Err(syn::Error::new_spanned(bound, "unsupported bound on a future"));
+    }
+    Ok(output)
+}
+
+/// Find the application error of a method, as declared with `#[rpc(error = ...)]`
+fn method_options(attrs: &[Attribute]) -> syn::Result<MethodOptions> {
+    let mut options = MethodOptions::default();
+    for attr in attrs {
+        if attr.path().is_ident("rpc") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("error") {
+                    if options.error.is_some() {
+                        return Err(meta.error("duplicate rpc option"));
+                    }
+                    options.error = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("rename") {
+                    if options.rename.is_some() {
+                        return Err(meta.error("duplicate rpc option"));
+                    }
+                    options.rename = Some(wire_name(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else if meta.path.is_ident("alias") {
+                    options.aliases.push(wire_name(&meta.value()?.parse()?)?);
+                    Ok(())
+                } else {
+                    Err(unsupported_option(&meta))
+                }
+            })?;
+        }
+    }
+    Ok(options)
+}
+
+/// A method name as it is declared with `#[rpc(rename = ...)]` or `#[rpc(alias = ...)]`
+fn wire_name(name: &LitStr) -> syn::Result<String> {
+    let value = name.value();
+    if value.is_empty() {
+        return Err(syn::Error::new_spanned(name, "a method name cannot be empty"));
+    }
+    // JSON-RPC joins the names of nested methods with dots
+    if value.contains('.') {
+        return Err(syn::Error::new_spanned(name, "a method name cannot contain `.`"));
+    }
+    Ok(value)
+}
+
+/// Check that no two methods are encoded with the same name, including their aliases
+fn check_wire_names(methods: &[Method]) -> syn::Result<()> {
+    let mut names = Vec::new();
+    for method in methods {
+        for name in iter::once(&method.wire_name).chain(&method.aliases) {
+            if names.contains(&name) {
+                return Err(syn::Error::new_spanned(
+                    &method.name,
+                    format!("the method name `{name}` is used by more than one method"),
+                ));
+            }
+            names.push(name);
+        }
+    }
+    Ok(())
+}
+
+/// Check that the receiver of a method is `&self`
+fn check_receiver(s: &Receiver) -> syn::Result<()> {
+    if s.reference.is_none() {
+        return Err(syn::Error::new_spanned(s, "cannot take owned self value"));
+    }
+    if s.mutability.is_some() {
+        return Err(syn::Error::new_spanned(
+            s,
+            "cannot take a mutable self reference",
+        ));
+    }
+    if let Type::Reference(ty) = &*s.ty
+        && ty.mutability.is_none()
+        && let Type::Path(ty) = &*ty.elem
+        && ty.path.segments.len() == 1
+        && ty.path.segments[0].ident == "Self"
+    {
+        Ok(())
+    } else {
+        Err(syn::Error::new_spanned(
+            s,
+            "cannot use a smart pointer for self type, must use &Self",
+        ))
+    }
+}
+
+/// Find the default of an argument, as declared with `#[rpc(default)]` or `#[rpc(default = ...)]`,
+/// the rpc attributes are removed from the argument
+fn arg_default(attrs: &mut Vec<Attribute>) -> syn::Result<Option<ArgDefault>> {
+    let mut default = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("rpc")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("default") {
+                default = Some(if meta.input.peek(Token![=]) {
+                    ArgDefault::Value(Box::new(meta.value()?.parse()?))
+                } else {
+                    ArgDefault::Default
+                });
+                Ok(())
+            } else {
+                Err(unsupported_option(&meta))
+            }
+        })?;
+    }
+    attrs.retain(|attr| !attr.path().is_ident("rpc"));
+    Ok(default)
+}
+
+/// Check that every argument can name a field of the request, this is required by `named_args`
+fn check_named_args(args: &[PatType], ret: &super::ReturnType) -> syn::Result<()> {
+    for arg in args {
+        let Pat::Ident(pat) = &*arg.pat else {
+            return Err(syn::Error::new_spanned(&arg.pat, "named_args requires every argument to be named"));
+        };
+        if pat.ident == "request" && matches!(ret, super::ReturnType::Nested { .. }) {
+            return Err(syn::Error::new_spanned(
+                &pat.ident,
+                "`request` is the name of the nested request, it cannot be used for an argument with named_args",
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Find how an argument borrows from the request body, only `&str`, `&[u8]` and `Cow<'_, T>` can
+/// be borrowed
+fn borrow(ty: &Type) -> syn::Result<Option<Borrow>> {
+    if let Type::Reference(reference) = ty
+        && reference.mutability.is_none()
+    {
+        let Some(ty) = borrowed(&reference.elem) else {
+            return Err(syn::Error::new_spanned(
+                ty,
+                "only `&str` and `&[u8]` can be borrowed from the request, use `Cow<'_, T>` for other types",
+            ));
+        };
+        return Ok(Some(Borrow { cow: false, ty }));
+    }
+    if let Type::Path(TypePath { qself: None, path }) = ty
+        && let Some(PathSegment { ident, arguments: PathArguments::AngleBracketed(args) }) = path.segments.last()
+        && ident == "Cow"
+        && let Some(GenericArgument::Type(inner)) = args.args.last()
+    {
+        let ty = borrowed(inner).unwrap_or_else(|| Borrowed::Other(Box::new(inner.clone())));
+        return Ok(Some(Borrow { cow: true, ty }));
+    }
+    if has_lifetime(ty.to_token_stream()) {
+        return Err(syn::Error::new_spanned(
+            ty,
+            "only `&str`, `&[u8]` and `Cow<'_, T>` can be borrowed from the request",
+        ));
+    }
+    Ok(None)
+}
+
+/// Find the reference which the client takes for an argument, this is either borrowed by the server
+/// or, for services which do not borrow, the argument is replaced with the owned type which the
+/// server receives
+fn client_ref(arg: &mut PatType, borrow: Option<&Borrow>) -> syn::Result<Option<Type>> {
+    if let Some(borrow) = borrow {
+        return Ok((!borrow.cow).then(|| (*arg.ty).clone()));
+    }
+    let Type::Reference(reference) = &*arg.ty else {
+        return Ok(None);
+    };
+    if reference.mutability.is_some() {
+        return Err(syn::Error::new_spanned(reference, "cannot take a mutable reference"));
+    }
+    let owned = match &*reference.elem {
+        Type::Path(path) if path.path.is_ident("str") => parse_quote!(String),
+        Type::Slice(slice) => {
+            let elem = &slice.elem;
+            parse_quote!(Vec<#elem>)
+        }
+        ty => parse_quote!(<#ty as ::std::borrow::ToOwned>::Owned),
+    };
+    Ok(Some(std::mem::replace(&mut *arg.ty, owned)))
+}
+
+/// Check if the type is `str` or `[u8]`
+fn borrowed(ty: &Type) -> Option<Borrowed> {
+    match ty {
+        Type::Path(path) if path.path.is_ident("str") => Some(Borrowed::Str),
+        Type::Slice(slice) if matches!(&*slice.elem, Type::Path(path) if path.path.is_ident("u8")) => Some(Borrowed::Bytes),
+        _ => None,
+    }
+}
+
+fn has_lifetime(tokens: TokenStream) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Punct(punct) => matches!(punct.as_char(), '\'' | '&'),
+        TokenTree::Group(group) => has_lifetime(group.stream()),
+        TokenTree::Ident(_) | TokenTree::Literal(_) => false,
+    })
+}
+
+/// Find the case for `rename_all`, this accepts the same cases as serde
+fn case(case: &LitStr) -> syn::Result<Case<'static>> {
+    match case.value().as_str() {
+        "lowercase" => Ok(Case::Flat),
+        "UPPERCASE" => Ok(Case::UpperFlat),
+        "PascalCase" => Ok(Case::Pascal),
+        "camelCase" => Ok(Case::Camel),
+        "snake_case" => Ok(Case::Snake),
+        "SCREAMING_SNAKE_CASE" => Ok(Case::Constant),
+        "kebab-case" => Ok(Case::Kebab),
+        "SCREAMING-KEBAB-CASE" => Ok(Case::Cobol),
+        _ => Err(syn::Error::new_spanned(
+            case,
+            "unknown case, expected one of: lowercase, UPPERCASE, PascalCase, camelCase, snake_case, \
+            SCREAMING_SNAKE_CASE, kebab-case, SCREAMING-KEBAB-CASE",
+        )),
+    }
+}
+
+fn unsupported_option(meta: &ParseNestedMeta) -> syn::Error {
+    meta.error("unsupported rpc option")
+}
+
 fn docs(attr: &Attribute) -> Option<Expr> {
     if let Meta::NameValue(MetaNameValue { path, value, .. }) = &attr.meta {
         if path.is_ident("doc") {
@@ -160,10 +599,12 @@ fn docs(attr: &Attribute) -> Option<Expr> {
 #[cfg(test)]
 mod test {
     use crate::parse::Parser;
+    use proc_macro2::TokenStream;
+    use quote::quote;
     use syn::parse_quote;
     use syn::punctuated::Punctuated;
     use syn::token::Paren;
-    use syn::{ReturnType, Type, TypeTuple};
+    use syn::{ReturnType, TraitItemFn, Type, TypeParamBound, TypeTuple};
 
     macro_rules! return_type_tests {
         ($($name:ident: $output:expr => {$($input:tt)*}),*) => {
@@ -179,13 +620,116 @@ mod test {
     return_type_tests![
         unit: crate::ReturnType::Simple(Type::Tuple(TypeTuple { paren_token: Paren::default(),elems: Punctuated::default(),})) => {},
         simple: crate::ReturnType::Simple(Type::Path(parse_quote!(String))) => {-> String},
-        service: crate::ReturnType::Nested {  service: parse_quote!(SubService) } => { -> impl SubService }
+        service: crate::ReturnType::Nested {  service: parse_quote!(SubService) } => { -> impl SubService },
+        future: crate::ReturnType::Simple(Type::Path(parse_quote!(String))) => {-> impl Future<Output = String> + Send},
+        future_service: crate::ReturnType::Nested {  service: parse_quote!(SubService) } => { -> impl Future<Output = impl SubService> }
     ];
 
     #[allow(clippy::needless_pass_by_value)]
     fn test_return_type(input: ReturnType, expected: crate::ReturnType) {
-        let parser = Parser;
+        let parser = Parser::default();
         let output = parser.return_type(input).expect("failed to parse input");
         assert_eq!(output, expected);
     }
+
+    macro_rules! option_error_tests {
+        ($($name:ident: $error:literal => {$($args:tt)*}),*) => {
+            $(
+            #[test]
+            fn $name() {
+                test_option_error(quote!($($args)*), $error);
+            }
+            )*
+        };
+    }
+
+    option_error_tests![
+        duplicate_option: "duplicate rpc option" => { borrow, borrow },
+        unknown_case: "unknown case" => { rename_all = "Title Case" },
+        always_derived: "`Debug` is always derived" => { derive(Clone, Debug) },
+        client_not_bool: "expected boolean literal" => { client = "no" }
+    ];
+
+    macro_rules! method_error_tests {
+        ($($name:ident: $error:literal => {$($method:tt)*}),*) => {
+            $(
+            #[test]
+            fn $name() {
+                test_method_error(parse_quote!($($method)*), $error);
+            }
+            )*
+        };
+    }
+
+    method_error_tests![
+        missing_self: "missing self" => { fn count() -> u32; },
+        missing_self_with_args: "missing self" => { fn add(a: u32, b: u32) -> u32; },
+        streaming_default: "a streaming method cannot have a default body" => { fn count(&self) -> Stream<u32> {} },
+        mutable_reference: "cannot take a mutable reference" => { fn push(&self, items: &mut Vec<u32>); },
+        async_future: "an async fn cannot also return a future" => { async fn get(&self) -> impl Future<Output = u32>; },
+        empty_rename: "a method name cannot be empty" => { #[rpc(rename = "")] fn get(&self) -> u32; },
+        dotted_alias: "a method name cannot contain `.`" => { #[rpc(alias = "old.get")] fn get(&self) -> u32; },
+        duplicate_rename: "duplicate rpc option" => { #[rpc(rename = "fetch", rename = "load")] fn get(&self) -> u32; },
+        duplicate_error: "duplicate rpc option" => { #[rpc(error = String)] #[rpc(error = u32)] fn get(&self) -> u32; }
+    ];
+
+    #[test]
+    fn duplicate_wire_name() {
+        let input = parse_quote! {
+            trait Service {
+                #[rpc(alias = "fetch")]
+                fn get(&self) -> u32;
+                #[rpc(rename = "fetch")]
+                fn get_all(&self) -> Vec<u32>;
+            }
+        };
+        let Err(error) = Parser::default().rpc(input) else {
+            panic!("expected the service to be rejected");
+        };
+        assert_eq!(error.to_string(), "the method name `fetch` is used by more than one method");
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn test_method_error(method: TraitItemFn, expected: &str) {
+        let Err(error) = Parser::default().method(method) else {
+            panic!("expected the method to be rejected");
+        };
+        assert!(error.to_string().starts_with(expected), "unexpected error: {error}");
+    }
+
+    macro_rules! supertrait_error_tests {
+        ($($name:ident: $error:literal => {$($bound:tt)*}),*) => {
+            $(
+            #[test]
+            fn $name() {
+                test_supertrait_error(parse_quote!($($bound)*), $error);
+            }
+            )*
+        };
+    }
+
+    supertrait_error_tests![
+        lifetime_supertrait: "lifetime bounds are not supported, supertraits must be rpc services" => { 'static },
+        higher_ranked_supertrait: "lifetimes not supported here" => { for<'a> Service<'a> },
+        send_supertrait: "`Send` is a marker trait, supertraits must be rpc services" => { Send },
+        sync_supertrait: "`Sync` is a marker trait, supertraits must be rpc services" => { Sync },
+        marker_path_supertrait: "`Send` is a marker trait, supertraits must be rpc services" => { std::marker::Send },
+        maybe_sized_supertrait: "supertraits must be rpc services" => { ?Sized }
+    ];
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn test_supertrait_error(bound: TypeParamBound, expected: &str) {
+        let Err(error) = Parser::default().supertrait(&bound) else {
+            panic!("expected the supertrait to be rejected");
+        };
+        assert!(error.to_string().starts_with(expected), "unexpected error: {error}");
+    }
+
+    #[allow(clippy::needless_pass_by_value)]
+    fn test_option_error(args: TokenStream, expected: &str) {
+        let Err(error) = Parser::new(args) else {
+            panic!("expected the options to be rejected");
+        };
+        assert!(error.to_string().starts_with(expected), "unexpected error: {error}");
+    }
 }
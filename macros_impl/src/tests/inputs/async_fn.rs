@@ -0,0 +1,10 @@
+#[rpc]
+/// A service for managing to-do items
+pub trait TodoService {
+    /// Get a list of to-do items
+    async fn get_todos(&self) -> Vec<Todo>;
+    /// Get a to-do item by name, returns None if no to-do item with the given name exists
+    fn get_todo(&self, name: String) -> impl Future<Output = Option<Todo>> + Send;
+    /// Create a new to-do item
+    async fn new_todo(&self, todo: Todo);
+}
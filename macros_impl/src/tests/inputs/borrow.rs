@@ -0,0 +1,12 @@
+#[rpc(borrow)]
+/// A store which keeps blobs of data by key
+pub trait BlobStore {
+    /// Store some data under the given key, returning the number of bytes stored
+    fn put(&self, key: &str, data: &[u8]) -> usize;
+    /// Write a line to the log of the store
+    fn log(&self, line: Cow<'_, str>);
+    /// Count the number of keys in the store
+    fn count(&self) -> u64;
+    /// Watch for changes to keys with the given prefix
+    fn watch(&self, prefix: &str) -> Stream<String>;
+}
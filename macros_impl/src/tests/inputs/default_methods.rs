@@ -0,0 +1,16 @@
+#[rpc(error = String)]
+pub trait VersionService {
+    fn name(&self) -> String;
+    /// The version of the service, which servers rarely need to override
+    fn version(&self) -> String {
+        Ok(String::from("1.0"))
+    }
+    fn describe(&self, verbose: bool) -> String {
+        let name = self.name().await?;
+        if verbose {
+            Ok(format!("{name} {}", self.version().await?))
+        } else {
+            Ok(name)
+        }
+    }
+}
@@ -0,0 +1,8 @@
+#[rpc(named_args, borrow)]
+/// A search index, which has gained options since it was first deployed
+pub trait SearchIndex {
+    /// Search for items which match the query
+    fn search(&self, query: &str, #[rpc(default)] exact: bool, #[rpc(default = 20)] limit: u32) -> Vec<Item>;
+    /// Suggest completions of a prefix
+    fn suggest(&self, prefix: String, #[rpc(default = "en")] language: &str) -> Vec<String>;
+}
@@ -0,0 +1,8 @@
+#[rpc(error = StoreError)]
+pub trait Store {
+    fn subscribe(&self) -> Stream<Item>;
+    fn get(&self, id: u64) -> Item;
+    #[rpc(error = NotFound)]
+    fn delete(&self, id: u64);
+    fn users(&self) -> impl UsersService;
+}
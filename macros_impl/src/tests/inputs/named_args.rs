@@ -0,0 +1,12 @@
+#[rpc(named_args)]
+/// A todo list, where each argument is sent by its name
+pub trait TodoList {
+    /// Add a new todo item
+    fn add(&self, title: String, done: bool) -> u64;
+    /// List every todo item
+    fn list(&self) -> Vec<Todo>;
+    /// Watch the todo items for changes
+    fn watch(&self, since: u64) -> Stream<Todo>;
+    /// The tags of a given todo item
+    fn tags(&self, id: u64) -> impl TagService;
+}
@@ -0,0 +1,15 @@
+#[rpc(crate = ::my_crate::rpc, server = false, derive(Clone, PartialEq), rename_all = "camelCase")]
+/// A clock which is served by another application
+pub trait Clock {
+    /// The current time, in seconds since the epoch
+    fn current_time(&self) -> u64;
+    /// Set an alarm for the given time
+    fn set_alarm(&self, at: u64);
+}
+
+#[rpc(client = false, rename_all = "kebab-case")]
+/// An alarm which is only called by clients in other languages
+pub trait Alarm {
+    /// Ring the alarm now
+    fn ring_now(&self, volume: u8);
+}
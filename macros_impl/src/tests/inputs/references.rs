@@ -0,0 +1,17 @@
+#[rpc]
+/// A phone book, which looks up entries without copying the caller's strings
+pub trait PhoneBook {
+    /// Look up the number of a name
+    fn lookup(&self, name: &str) -> Option<String>;
+    /// Add the given numbers to a name
+    #[rpc(error = String)]
+    fn add(&self, name: &str, numbers: &[u64]) -> usize;
+    /// Watch for changes to the entries with the given prefix
+    fn watch(&self, prefix: &str) -> Stream<String>;
+    /// The entries of a group
+    fn group(&self, group: &str) -> impl Entries;
+    /// Format a name as it is written in the book, the default body sees the name as `&str`
+    fn display_name(&self, name: &str) -> String {
+        str::to_uppercase(name)
+    }
+}
@@ -0,0 +1,13 @@
+#[rpc(rename_all = "camelCase")]
+/// A to-do list, whose methods have been renamed since it was first deployed
+pub trait TodoList {
+    /// Find a to-do by its name, this was `get_todo`
+    #[rpc(rename = "get_todo")]
+    fn find_todo(&self, name: String) -> Option<String>;
+    /// Remove a to-do, which is still accepted by its old names
+    #[rpc(error = String, alias = "delete", alias = "deleteTodo")]
+    fn remove_todo(&self, name: String);
+    /// The lists which are shared with this one
+    #[rpc(rename = "shared", alias = "friends")]
+    fn shared_lists(&self) -> impl SharedLists;
+}
@@ -0,0 +1,16 @@
+#[rpc]
+pub trait HealthService {
+    fn ping(&self) -> String;
+    fn status(&self) -> Stream<Status>;
+}
+
+#[rpc]
+pub trait AdminService {
+    fn shutdown(&self, force: bool);
+}
+
+#[rpc]
+/// The public api, which also serves the shared services
+pub trait ApiService: HealthService + admin::AdminService {
+    fn version(&self) -> String;
+}
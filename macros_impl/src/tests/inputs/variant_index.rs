@@ -0,0 +1,8 @@
+#[rpc(variant_index)]
+/// A sensor which is read by clients over a constrained link
+pub trait Sensor {
+    /// Read the latest temperature
+    fn temperature(&self) -> f32;
+    /// Set the interval between readings, in seconds
+    fn set_interval(&self, seconds: u16);
+}
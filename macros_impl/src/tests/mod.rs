@@ -61,4 +61,12 @@ fn test_case(input: &'static str, expected: &'static str) {
     difference::assert_diff!(&actual, &expected, "\n", 0);
 }
 
-tests!(simple, resource, nested);
+tests!(simple, resource, nested, errors, variant_index, borrow, named_args, defaults, options, supertraits, default_methods, references, rename);
+
+/// `async fn` and `-> impl Future` are other spellings of the same methods, so the output is the same
+#[test]
+fn async_fn() {
+    let input = include_str!("inputs/async_fn.rs");
+    let expected = include_str!("outputs/simple.rs");
+    test_case(input, expected);
+}
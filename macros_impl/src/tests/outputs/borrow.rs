@@ -0,0 +1,475 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use blob_store::{BlobStore, BlobStoreAsyncClient, BlobStoreBlockingClient, BlobStoreServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod blob_store {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A store which keeps blobs of data by key
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct BlobStore;
+
+    impl Rpc for BlobStore {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            BlobStoreAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            BlobStoreBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = BorrowedRequest<'req>;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> BlobStoreAsyncClient<_Client> {
+            BlobStoreAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> BlobStoreBlockingClient<_Client> {
+            BlobStoreBlockingClient(transport)
+        }
+    }
+
+    impl<Server: BlobStoreServer> RpcWithServer<Server> for BlobStore {
+        type Handler = BlobStoreHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            BlobStoreHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "put")]
+        Put(
+            String,
+            #[serde(with = "::trait_rpc::borrow::bytes")] Vec<u8>,
+        ),
+        #[serde(rename = "log")]
+        Log(String),
+        #[serde(rename = "count")]
+        Count(),
+        #[serde(rename = "watch")]
+        Watch(String),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "put",
+                aliases: &[],
+                args: &["key", "data"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "log",
+                aliases: &[],
+                args: &["line"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "count",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "watch",
+                aliases: &[],
+                args: &["prefix"],
+                named: false,
+                streaming: true,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Put(..) => false,
+                Self::Log(..) => false,
+                Self::Count(..) => false,
+                Self::Watch(..) => true,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Put(..) => "put",
+                Self::Log(..) => "log",
+                Self::Count(..) => "count",
+                Self::Watch(..) => "watch",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "put")]
+        Put(
+            &'req str,
+            #[serde(with = "::trait_rpc::borrow::bytes")] &'req [u8],
+        ),
+        #[serde(rename = "log")]
+        Log(String),
+        #[serde(rename = "count")]
+        Count(),
+        #[serde(rename = "watch")]
+        Watch(&'req str),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Put(..) => false,
+                Self::Log(..) => false,
+                Self::Count(..) => false,
+                Self::Watch(..) => true,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Put(..) => "put",
+                Self::Log(..) => "log",
+                Self::Count(..) => "count",
+                Self::Watch(..) => "watch",
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum BorrowedRequest<'req> {
+        #[serde(rename = "put")]
+        Put(
+            #[serde(borrow)] ::std::borrow::Cow<'req, str>,
+            #[serde(borrow, with = "::trait_rpc::borrow::bytes")] ::std::borrow::Cow<'req, [u8]>,
+        ),
+        #[serde(rename = "log")]
+        Log(#[serde(borrow)] ::std::borrow::Cow<'req, str>),
+        #[serde(rename = "count")]
+        Count(),
+        #[serde(rename = "watch")]
+        Watch(#[serde(borrow)] ::std::borrow::Cow<'req, str>),
+    }
+
+    impl<'de: 'req, 'req> Deserialize<'de> for BorrowedRequest<'req> {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl<'req> ::trait_rpc::Request for BorrowedRequest<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Put(..) => false,
+                Self::Log(..) => false,
+                Self::Count(..) => false,
+                Self::Watch(..) => true,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Put(..) => "put",
+                Self::Log(..) => "log",
+                Self::Count(..) => "count",
+                Self::Watch(..) => "watch",
+            }
+        }
+    }
+
+    impl<'req> From<BorrowedRequest<'req>> for Request {
+        fn from(request: BorrowedRequest<'req>) -> Self {
+            match request {
+                BorrowedRequest::Put(key, data) => Request::Put(
+                    ::std::borrow::Cow::into_owned(key),
+                    ::std::borrow::Cow::into_owned(data),
+                ),
+                BorrowedRequest::Log(line) => Request::Log(::std::borrow::Cow::into_owned(line)),
+                BorrowedRequest::Count() => Request::Count(),
+                BorrowedRequest::Watch(prefix) => {
+                    Request::Watch(::std::borrow::Cow::into_owned(prefix))
+                }
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "put")]
+        Put(usize),
+        #[serde(rename = "log")]
+        Log(()),
+        #[serde(rename = "count")]
+        Count(u64),
+        #[serde(rename = "watch")]
+        Watch(String),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Put(..) => false,
+                Self::Log(..) => false,
+                Self::Count(..) => false,
+                Self::Watch(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Put(..) => "put",
+                Self::Log(..) => "log",
+                Self::Count(..) => "count",
+                Self::Watch(..) => "watch",
+            }
+        }
+    }
+
+    /// A store which keeps blobs of data by key
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait BlobStoreServer: Send + Sync {
+        /// Store some data under the given key, returning the number of bytes stored
+        fn put(&self, key: &str, data: &[u8]) -> impl Future<Output = usize> + Send;
+        /// Write a line to the log of the store
+        fn log(&self, line: Cow<'_, str>) -> impl Future<Output = ()> + Send;
+        /// Count the number of keys in the store
+        fn count(&self) -> impl Future<Output = u64> + Send;
+        /// Watch for changes to keys with the given prefix
+        fn watch(
+            &self,
+            sink: impl Sink<String, Error = Infallible> + Send + 'static,
+            prefix: &str,
+        ) -> impl Future<Output = ()> + Send;
+    }
+
+    impl<_Server: BlobStoreServer + ?Sized> BlobStoreServer for &_Server {
+        fn put(&self, key: &str, data: &[u8]) -> impl Future<Output = usize> + Send {
+            <_Server as BlobStoreServer>::put(*self, key, data)
+        }
+        fn log(&self, line: Cow<'_, str>) -> impl Future<Output = ()> + Send {
+            <_Server as BlobStoreServer>::log(*self, line)
+        }
+        fn count(&self) -> impl Future<Output = u64> + Send {
+            <_Server as BlobStoreServer>::count(*self)
+        }
+        fn watch(
+            &self,
+            sink: impl Sink<String, Error = Infallible> + Send + 'static,
+            prefix: &str,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as BlobStoreServer>::watch(*self, sink, prefix)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct BlobStoreHandler<_Server>(_Server);
+
+    impl<_Server: BlobStoreServer> Handler for BlobStoreHandler<_Server> {
+        type Rpc = BlobStore;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Put(key, data) => Response::Put(self.0.put(&key, &data).await),
+                Request::Log(line) => {
+                    Response::Log(self.0.log(::std::borrow::Cow::Owned(line)).await)
+                }
+                Request::Count() => Response::Count(self.0.count().await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_borrowed(&self, request: <Self::Rpc as Rpc>::BorrowedRequest<'_>) -> Response {
+            match request {
+                BorrowedRequest::Put(key, data) => Response::Put(self.0.put(&key, &data).await),
+                BorrowedRequest::Log(line) => Response::Log(self.0.log(line).await),
+                BorrowedRequest::Count() => Response::Count(self.0.count().await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::Watch(prefix) => {
+                    let sink =
+                        sink.with(async |value| Result::<_, S::Error>::Ok(Response::Watch(value)));
+                    self.0.watch(sink, &prefix).await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A store which keeps blobs of data by key
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct BlobStoreAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> BlobStoreAsyncClient<_Client> {
+        /// Store some data under the given key, returning the number of bytes stored
+        pub async fn put(&self, key: &str, data: &[u8]) -> Result<usize, _Client::Error>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Put(key, data)).await? {
+                Response::Put(value) => Ok(value),
+                other => Err(WrongResponseType::new("put", other.fn_name()).into()),
+            }
+        }
+        /// Write a line to the log of the store
+        pub async fn log(&self, line: Cow<'_, str>) -> Result<(), _Client::Error> {
+            match self
+                .0
+                .send(Request::Log(::std::borrow::Cow::into_owned(line)))
+                .await?
+            {
+                Response::Log(value) => Ok(value),
+                other => Err(WrongResponseType::new("log", other.fn_name()).into()),
+            }
+        }
+        /// Count the number of keys in the store
+        pub async fn count(&self) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::Count()).await? {
+                Response::Count(value) => Ok(value),
+                other => Err(WrongResponseType::new("count", other.fn_name()).into()),
+            }
+        }
+        /// Watch for changes to keys with the given prefix
+        pub async fn watch<'req>(
+            &'req self,
+            prefix: &'req str,
+        ) -> Result<impl Stream<Item = Result<String, _Client::Error>>, _Client::Error>
+        where
+            _Client: StreamRefClient<Request, Response>,
+        {
+            let stream = self
+                .0
+                .send_ref_streaming_response(RequestRef::Watch(prefix))
+                .await?;
+            Ok(stream.map(|value| {
+                match value {
+                    Ok(Response::Watch(value)) => Ok(value),
+                    Ok(other) => Err(WrongResponseType::new("watch", other.fn_name()).into()),
+                    Err(error) => Err(error.into()),
+                }
+            }))
+        }
+    }
+
+    /// A store which keeps blobs of data by key
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct BlobStoreBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> BlobStoreBlockingClient<_Client> {
+        /// Store some data under the given key, returning the number of bytes stored
+        pub fn put(&self, key: &str, data: &[u8]) -> Result<usize, _Client::Error>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Put(key, data))? {
+                Response::Put(value) => Ok(value),
+                other => Err(WrongResponseType::new("put", other.fn_name()).into()),
+            }
+        }
+        /// Write a line to the log of the store
+        pub fn log(&self, line: Cow<'_, str>) -> Result<(), _Client::Error> {
+            match self
+                .0
+                .send(Request::Log(::std::borrow::Cow::into_owned(line)))?
+            {
+                Response::Log(value) => Ok(value),
+                other => Err(WrongResponseType::new("log", other.fn_name()).into()),
+            }
+        }
+        /// Count the number of keys in the store
+        pub fn count(&self) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::Count())? {
+                Response::Count(value) => Ok(value),
+                other => Err(WrongResponseType::new("count", other.fn_name()).into()),
+            }
+        }
+    }
+}
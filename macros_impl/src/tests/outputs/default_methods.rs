@@ -0,0 +1,385 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use version_service::{
+    VersionService, VersionServiceAsyncClient, VersionServiceBlockingClient, VersionServiceServer,
+};
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod version_service {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct VersionService;
+
+    impl Rpc for VersionService {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            VersionServiceAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            VersionServiceBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> VersionServiceAsyncClient<_Client> {
+            VersionServiceAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> VersionServiceBlockingClient<_Client> {
+            VersionServiceBlockingClient(transport)
+        }
+    }
+
+    impl<Server: VersionServiceServer> RpcWithServer<Server> for VersionService {
+        type Handler = VersionServiceHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            VersionServiceHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "name")]
+        Name(),
+        #[serde(rename = "version")]
+        Version(),
+        #[serde(rename = "describe")]
+        Describe(bool),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "name",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "version",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "describe",
+                aliases: &[],
+                args: &["verbose"],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Name(..) => false,
+                Self::Version(..) => false,
+                Self::Describe(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Name(..) => "name",
+                Self::Version(..) => "version",
+                Self::Describe(..) => "describe",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "name")]
+        Name(Result<String, String>),
+        #[serde(rename = "version")]
+        Version(Result<String, String>),
+        #[serde(rename = "describe")]
+        Describe(Result<String, String>),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Name(response) => response.is_err(),
+                Self::Version(response) => response.is_err(),
+                Self::Describe(response) => response.is_err(),
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Name(..) => "name",
+                Self::Version(..) => "version",
+                Self::Describe(..) => "describe",
+            }
+        }
+    }
+
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait VersionServiceServer: Send + Sync {
+        fn name(&self) -> impl Future<Output = Result<String, String>> + Send;
+        /// The version of the service, which servers rarely need to override
+        fn version(&self) -> impl Future<Output = Result<String, String>> + Send {
+            async move { Ok(String::from("1.0")) }
+        }
+        fn describe(&self, verbose: bool) -> impl Future<Output = Result<String, String>> + Send {
+            async move {
+                let name = self.name().await?;
+                if verbose {
+                    Ok(format!("{name} {}", self.version().await?))
+                } else {
+                    Ok(name)
+                }
+            }
+        }
+    }
+
+    impl<_Server: VersionServiceServer + ?Sized> VersionServiceServer for &_Server {
+        fn name(&self) -> impl Future<Output = Result<String, String>> + Send {
+            <_Server as VersionServiceServer>::name(*self)
+        }
+        fn version(&self) -> impl Future<Output = Result<String, String>> + Send {
+            <_Server as VersionServiceServer>::version(*self)
+        }
+        fn describe(&self, verbose: bool) -> impl Future<Output = Result<String, String>> + Send {
+            <_Server as VersionServiceServer>::describe(*self, verbose)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct VersionServiceHandler<_Server>(_Server);
+
+    impl<_Server: VersionServiceServer> Handler for VersionServiceHandler<_Server> {
+        type Rpc = VersionService;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Name() => Response::Name(self.0.name().await),
+                Request::Version() => Response::Version(self.0.version().await),
+                Request::Describe(verbose) => Response::Describe(self.0.describe(verbose).await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct VersionServiceAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> VersionServiceAsyncClient<_Client> {
+        pub async fn name(
+            &self,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Name())
+                .await
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Name(Ok(value)) => Ok(value),
+                Response::Name(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("name", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// The version of the service, which servers rarely need to override
+        pub async fn version(
+            &self,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Version())
+                .await
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Version(Ok(value)) => Ok(value),
+                Response::Version(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("version", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub async fn describe(
+            &self,
+            verbose: bool,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Describe(verbose))
+                .await
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Describe(Ok(value)) => Ok(value),
+                Response::Describe(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("describe", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+    }
+
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct VersionServiceBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> VersionServiceBlockingClient<_Client> {
+        pub fn name(
+            &self,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Name())
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Name(Ok(value)) => Ok(value),
+                Response::Name(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("name", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// The version of the service, which servers rarely need to override
+        pub fn version(
+            &self,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Version())
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Version(Ok(value)) => Ok(value),
+                Response::Version(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("version", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub fn describe(
+            &self,
+            verbose: bool,
+        ) -> Result<String, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::Describe(verbose))
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Describe(Ok(value)) => Ok(value),
+                Response::Describe(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("describe", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+    }
+}
@@ -0,0 +1,460 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use search_index::{
+    SearchIndex, SearchIndexAsyncClient, SearchIndexBlockingClient, SearchIndexServer,
+};
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod search_index {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A search index, which has gained options since it was first deployed
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct SearchIndex;
+
+    impl Rpc for SearchIndex {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            SearchIndexAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            SearchIndexBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = BorrowedRequest<'req>;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> SearchIndexAsyncClient<_Client> {
+            SearchIndexAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> SearchIndexBlockingClient<_Client> {
+            SearchIndexBlockingClient(transport)
+        }
+    }
+
+    impl<Server: SearchIndexServer> RpcWithServer<Server> for SearchIndex {
+        type Handler = SearchIndexHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            SearchIndexHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "search")]
+        Search {
+            query: String,
+            #[serde(default)]
+            exact: bool,
+            #[serde(default = "default_search_limit")]
+            limit: u32,
+        },
+        #[serde(rename = "suggest")]
+        Suggest {
+            prefix: String,
+            #[serde(default = "default_suggest_language")]
+            language: String,
+        },
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "search",
+                aliases: &[],
+                args: &["query", "exact", "limit"],
+                named: true,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "suggest",
+                aliases: &[],
+                args: &["prefix", "language"],
+                named: true,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Search { .. } => false,
+                Self::Suggest { .. } => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Search { .. } => "search",
+                Self::Suggest { .. } => "suggest",
+            }
+        }
+    }
+
+    fn default_search_limit() -> u32 {
+        20
+    }
+
+    fn default_suggest_language() -> String {
+        ::std::borrow::ToOwned::to_owned("en")
+    }
+
+    fn default_suggest_language_borrowed() -> ::std::borrow::Cow<'static, str> {
+        ::std::borrow::Cow::Owned(default_suggest_language())
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "search")]
+        Search {
+            query: &'req str,
+            exact: bool,
+            limit: u32,
+        },
+        #[serde(rename = "suggest")]
+        Suggest { prefix: String, language: &'req str },
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Search { .. } => false,
+                Self::Suggest { .. } => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Search { .. } => "search",
+                Self::Suggest { .. } => "suggest",
+            }
+        }
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum BorrowedRequest<'req> {
+        #[serde(rename = "search")]
+        Search {
+            #[serde(borrow)]
+            query: ::std::borrow::Cow<'req, str>,
+            #[serde(default)]
+            exact: bool,
+            #[serde(default = "default_search_limit")]
+            limit: u32,
+        },
+        #[serde(rename = "suggest")]
+        Suggest {
+            prefix: String,
+            #[serde(borrow)]
+            #[serde(default = "default_suggest_language_borrowed")]
+            language: ::std::borrow::Cow<'req, str>,
+        },
+    }
+
+    impl<'de: 'req, 'req> Deserialize<'de> for BorrowedRequest<'req> {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl<'req> ::trait_rpc::Request for BorrowedRequest<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Search { .. } => false,
+                Self::Suggest { .. } => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Search { .. } => "search",
+                Self::Suggest { .. } => "suggest",
+            }
+        }
+    }
+
+    impl<'req> From<BorrowedRequest<'req>> for Request {
+        fn from(request: BorrowedRequest<'req>) -> Self {
+            match request {
+                BorrowedRequest::Search {
+                    query,
+                    exact,
+                    limit,
+                } => Request::Search {
+                    query: ::std::borrow::Cow::into_owned(query),
+                    exact,
+                    limit,
+                },
+                BorrowedRequest::Suggest { prefix, language } => Request::Suggest {
+                    prefix,
+                    language: ::std::borrow::Cow::into_owned(language),
+                },
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "search")]
+        Search(Vec<Item>),
+        #[serde(rename = "suggest")]
+        Suggest(Vec<String>),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Search(..) => false,
+                Self::Suggest(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Search(..) => "search",
+                Self::Suggest(..) => "suggest",
+            }
+        }
+    }
+
+    /// A search index, which has gained options since it was first deployed
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait SearchIndexServer: Send + Sync {
+        /// Search for items which match the query
+        fn search(
+            &self,
+            query: &str,
+            exact: bool,
+            limit: u32,
+        ) -> impl Future<Output = Vec<Item>> + Send;
+        /// Suggest completions of a prefix
+        fn suggest(
+            &self,
+            prefix: String,
+            language: &str,
+        ) -> impl Future<Output = Vec<String>> + Send;
+    }
+
+    impl<_Server: SearchIndexServer + ?Sized> SearchIndexServer for &_Server {
+        fn search(
+            &self,
+            query: &str,
+            exact: bool,
+            limit: u32,
+        ) -> impl Future<Output = Vec<Item>> + Send {
+            <_Server as SearchIndexServer>::search(*self, query, exact, limit)
+        }
+        fn suggest(
+            &self,
+            prefix: String,
+            language: &str,
+        ) -> impl Future<Output = Vec<String>> + Send {
+            <_Server as SearchIndexServer>::suggest(*self, prefix, language)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct SearchIndexHandler<_Server>(_Server);
+
+    impl<_Server: SearchIndexServer> Handler for SearchIndexHandler<_Server> {
+        type Rpc = SearchIndex;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Search {
+                    query,
+                    exact,
+                    limit,
+                } => Response::Search(self.0.search(&query, exact, limit).await),
+                Request::Suggest { prefix, language } => {
+                    Response::Suggest(self.0.suggest(prefix, &language).await)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_borrowed(
+            &self,
+            request: <Self::Rpc as Rpc>::BorrowedRequest<'_>,
+        ) -> Response {
+            match request {
+                BorrowedRequest::Search {
+                    query,
+                    exact,
+                    limit,
+                } => Response::Search(self.0.search(&query, exact, limit).await),
+                BorrowedRequest::Suggest { prefix, language } => {
+                    Response::Suggest(self.0.suggest(prefix, &language).await)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A search index, which has gained options since it was first deployed
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct SearchIndexAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> SearchIndexAsyncClient<_Client> {
+        /// Search for items which match the query
+        pub async fn search(
+            &self,
+            query: &str,
+            exact: bool,
+            limit: u32,
+        ) -> Result<Vec<Item>, _Client::Error>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+        {
+            match self
+                .0
+                .send_ref(RequestRef::Search {
+                    query,
+                    exact,
+                    limit,
+                })
+                .await?
+            {
+                Response::Search(value) => Ok(value),
+                other => Err(WrongResponseType::new("search", other.fn_name()).into()),
+            }
+        }
+        /// Suggest completions of a prefix
+        pub async fn suggest(
+            &self,
+            prefix: String,
+            language: &str,
+        ) -> Result<Vec<String>, _Client::Error>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+        {
+            match self
+                .0
+                .send_ref(RequestRef::Suggest { prefix, language })
+                .await?
+            {
+                Response::Suggest(value) => Ok(value),
+                other => Err(WrongResponseType::new("suggest", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// A search index, which has gained options since it was first deployed
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct SearchIndexBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> SearchIndexBlockingClient<_Client> {
+        /// Search for items which match the query
+        pub fn search(
+            &self,
+            query: &str,
+            exact: bool,
+            limit: u32,
+        ) -> Result<Vec<Item>, _Client::Error>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Search {
+                query,
+                exact,
+                limit,
+            })? {
+                Response::Search(value) => Ok(value),
+                other => Err(WrongResponseType::new("search", other.fn_name()).into()),
+            }
+        }
+        /// Suggest completions of a prefix
+        pub fn suggest(&self, prefix: String, language: &str) -> Result<Vec<String>, _Client::Error>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Suggest { prefix, language })? {
+                Response::Suggest(value) => Ok(value),
+                other => Err(WrongResponseType::new("suggest", other.fn_name()).into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,520 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use store::{Store, StoreAsyncClient, StoreBlockingClient, StoreServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod store {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct Store;
+
+    impl Rpc for Store {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            StoreAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            StoreBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> StoreAsyncClient<_Client> {
+            StoreAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> StoreBlockingClient<_Client> {
+            StoreBlockingClient(transport)
+        }
+    }
+
+    impl<Server: StoreServer> RpcWithServer<Server> for Store {
+        type Handler = StoreHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            StoreHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "subscribe")]
+        Subscribe(),
+        #[serde(rename = "get")]
+        Get(u64),
+        #[serde(rename = "delete")]
+        Delete(u64),
+        #[serde(rename = "users")]
+        Users(<UsersService as Rpc>::Request),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "subscribe",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: true,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "get",
+                aliases: &[],
+                args: &["id"],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "delete",
+                aliases: &[],
+                args: &["id"],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "users",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<UsersService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Subscribe(..) => true,
+                Self::Get(..) => false,
+                Self::Delete(..) => false,
+                Self::Users(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Subscribe(..) => "subscribe",
+                Self::Get(..) => "get",
+                Self::Delete(..) => "delete",
+                Self::Users(..) => "users",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "subscribe")]
+        Subscribe(),
+        #[serde(rename = "get")]
+        Get(u64),
+        #[serde(rename = "delete")]
+        Delete(u64),
+        #[serde(rename = "users")]
+        Users(<<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Subscribe(..) => true,
+                Self::Get(..) => false,
+                Self::Delete(..) => false,
+                Self::Users(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Subscribe(..) => "subscribe",
+                Self::Get(..) => "get",
+                Self::Delete(..) => "delete",
+                Self::Users(..) => "users",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "subscribe")]
+        Subscribe(Result<Item, StoreError>),
+        #[serde(rename = "get")]
+        Get(Result<Item, StoreError>),
+        #[serde(rename = "delete")]
+        Delete(Result<(), NotFound>),
+        #[serde(rename = "users")]
+        Users(<UsersService as Rpc>::Response),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Subscribe(response) => response.is_err(),
+                Self::Get(response) => response.is_err(),
+                Self::Delete(response) => response.is_err(),
+                Self::Users(response) => ::trait_rpc::Response::is_error(response),
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Subscribe(..) => "subscribe",
+                Self::Get(..) => "get",
+                Self::Delete(..) => "delete",
+                Self::Users(..) => "users",
+            }
+        }
+    }
+
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait StoreServer: Send + Sync {
+        fn subscribe(
+            &self,
+            sink: impl Sink<Result<Item, StoreError>, Error = Infallible> + Send + 'static,
+        ) -> impl Future<Output = ()> + Send;
+        fn get(&self, id: u64) -> impl Future<Output = Result<Item, StoreError>> + Send;
+        fn delete(&self, id: u64) -> impl Future<Output = Result<(), NotFound>> + Send;
+        fn users(&self) -> impl Future<Output = impl IntoHandler<UsersService>> + Send;
+    }
+
+    impl<_Server: StoreServer + ?Sized> StoreServer for &_Server {
+        fn subscribe(
+            &self,
+            sink: impl Sink<Result<Item, StoreError>, Error = Infallible> + Send + 'static,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as StoreServer>::subscribe(*self, sink)
+        }
+        fn get(&self, id: u64) -> impl Future<Output = Result<Item, StoreError>> + Send {
+            <_Server as StoreServer>::get(*self, id)
+        }
+        fn delete(&self, id: u64) -> impl Future<Output = Result<(), NotFound>> + Send {
+            <_Server as StoreServer>::delete(*self, id)
+        }
+        fn users(&self) -> impl Future<Output = impl IntoHandler<UsersService>> + Send {
+            <_Server as StoreServer>::users(*self)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct StoreHandler<_Server>(_Server);
+    impl<_Server: StoreServer> Handler for StoreHandler<_Server> {
+        type Rpc = Store;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Get(id) => Response::Get(self.0.get(id).await),
+                Request::Delete(id) => Response::Delete(self.0.delete(id).await),
+                Request::Users(request) => {
+                    let response = self.0.users().await.into_handler().handle(request).await;
+                    Response::Users(response)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::Subscribe() => {
+                    let sink = sink
+                        .with(async |value| Result::<_, S::Error>::Ok(Response::Subscribe(value)));
+                    self.0.subscribe(sink).await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct StoreAsyncClient<_Client>(_Client);
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> StoreAsyncClient<_Client> {
+        pub async fn subscribe(
+            &self,
+        ) -> Result<
+            impl Stream<
+                Item = Result<Item, <_Client::Error as WithApplicationError<StoreError>>::Output>,
+            >,
+            <_Client::Error as WithApplicationError<StoreError>>::Output,
+        >
+        where
+            _Client: StreamClient<Request, Response>,
+            _Client::Error: WithApplicationError<StoreError>,
+        {
+            let stream = self
+                .0
+                .send_streaming_response(Request::Subscribe())
+                .await
+                .map_err(<_Client::Error as WithApplicationError<StoreError>>::from_client)?;
+            Ok(stream.map(|value| {
+                match value {
+                    Ok(Response::Subscribe(Ok(value))) => Ok(value),
+                    Ok(Response::Subscribe(Err(error))) => {
+                        Err(<_Client::Error as WithApplicationError<StoreError>>::application(error))
+                    }
+                    Ok(other) => Err(
+                        <_Client::Error as WithApplicationError<StoreError>>::from_client(
+                            WrongResponseType::new("subscribe", other.fn_name()).into(),
+                        ),
+                    ),
+                    Err(error) => {
+                        Err(<_Client::Error as WithApplicationError<StoreError>>::from_client(error))
+                    }
+                }
+            }))
+        }
+        pub async fn get(
+            &self,
+            id: u64,
+        ) -> Result<Item, <_Client::Error as WithApplicationError<StoreError>>::Output>
+        where
+            _Client::Error: WithApplicationError<StoreError>,
+        {
+            match self
+                .0
+                .send(Request::Get(id))
+                .await
+                .map_err(<_Client::Error as WithApplicationError<StoreError>>::from_client)?
+            {
+                Response::Get(Ok(value)) => Ok(value),
+                Response::Get(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<StoreError>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<StoreError>>::from_client(
+                        WrongResponseType::new("get", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub async fn delete(
+            &self,
+            id: u64,
+        ) -> Result<(), <_Client::Error as WithApplicationError<NotFound>>::Output>
+        where
+            _Client::Error: WithApplicationError<NotFound>,
+        {
+            match self
+                .0
+                .send(Request::Delete(id))
+                .await
+                .map_err(<_Client::Error as WithApplicationError<NotFound>>::from_client)?
+            {
+                Response::Delete(Ok(value)) => Ok(value),
+                Response::Delete(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<NotFound>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<NotFound>>::from_client(
+                        WrongResponseType::new("delete", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub fn users(
+            &self,
+        ) -> <UsersService as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            UsersService::async_client(MappedClient::<
+                _,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::users_to_inner,
+                Self::users_to_outer,
+                Self::users_to_outer_ref,
+            ))
+        }
+        fn users_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<UsersService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Users(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("users", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("users")),
+            }
+        }
+        fn users_to_outer((): (), inner: <UsersService as Rpc>::Request) -> Request {
+            Request::Users(inner)
+        }
+        fn users_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Users(inner)
+        }
+    }
+
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct StoreBlockingClient<_Client>(_Client);
+    impl<_Client: BlockingClient<Request, Response>> StoreBlockingClient<_Client> {
+        pub fn get(
+            &self,
+            id: u64,
+        ) -> Result<Item, <_Client::Error as WithApplicationError<StoreError>>::Output>
+        where
+            _Client::Error: WithApplicationError<StoreError>,
+        {
+            match self
+                .0
+                .send(Request::Get(id))
+                .map_err(<_Client::Error as WithApplicationError<StoreError>>::from_client)?
+            {
+                Response::Get(Ok(value)) => Ok(value),
+                Response::Get(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<StoreError>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<StoreError>>::from_client(
+                        WrongResponseType::new("get", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub fn delete(
+            &self,
+            id: u64,
+        ) -> Result<(), <_Client::Error as WithApplicationError<NotFound>>::Output>
+        where
+            _Client::Error: WithApplicationError<NotFound>,
+        {
+            match self
+                .0
+                .send(Request::Delete(id))
+                .map_err(<_Client::Error as WithApplicationError<NotFound>>::from_client)?
+            {
+                Response::Delete(Ok(value)) => Ok(value),
+                Response::Delete(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<NotFound>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<NotFound>>::from_client(
+                        WrongResponseType::new("delete", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        pub fn users(
+            &self,
+        ) -> <UsersService as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            UsersService::blocking_client(MappedClient::<
+                _,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::users_to_inner,
+                Self::users_to_outer,
+                Self::users_to_outer_ref,
+            ))
+        }
+        fn users_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<UsersService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Users(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("users", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("users")),
+            }
+        }
+        fn users_to_outer((): (), inner: <UsersService as Rpc>::Request) -> Request {
+            Request::Users(inner)
+        }
+        fn users_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Users(inner)
+        }
+    }
+}
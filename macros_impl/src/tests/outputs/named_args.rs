@@ -0,0 +1,473 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use todo_list::{TodoList, TodoListAsyncClient, TodoListBlockingClient, TodoListServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod todo_list {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A todo list, where each argument is sent by its name
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct TodoList;
+
+    impl Rpc for TodoList {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            TodoListAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            TodoListBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> TodoListAsyncClient<_Client> {
+            TodoListAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> TodoListBlockingClient<_Client> {
+            TodoListBlockingClient(transport)
+        }
+    }
+
+    impl<Server: TodoListServer> RpcWithServer<Server> for TodoList {
+        type Handler = TodoListHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            TodoListHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "add")]
+        Add { title: String, done: bool },
+        #[serde(rename = "list")]
+        List {},
+        #[serde(rename = "watch")]
+        Watch { since: u64 },
+        #[serde(rename = "tags")]
+        Tags {
+            id: u64,
+            request: <TagService as Rpc>::Request,
+        },
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "add",
+                aliases: &[],
+                args: &["title", "done"],
+                named: true,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "list",
+                aliases: &[],
+                args: &[],
+                named: true,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "watch",
+                aliases: &[],
+                args: &["since"],
+                named: true,
+                streaming: true,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "tags",
+                aliases: &[],
+                args: &["id"],
+                named: true,
+                streaming: false,
+                error: false,
+                nested: Some(<<TagService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Add { .. } => false,
+                Self::List { .. } => false,
+                Self::Watch { .. } => true,
+                Self::Tags { .. } => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Add { .. } => "add",
+                Self::List { .. } => "list",
+                Self::Watch { .. } => "watch",
+                Self::Tags { .. } => "tags",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "add")]
+        Add { title: String, done: bool },
+        #[serde(rename = "list")]
+        List {},
+        #[serde(rename = "watch")]
+        Watch { since: u64 },
+        #[serde(rename = "tags")]
+        Tags {
+            id: &'req u64,
+            request: <<TagService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        },
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Add { .. } => false,
+                Self::List { .. } => false,
+                Self::Watch { .. } => true,
+                Self::Tags { .. } => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Add { .. } => "add",
+                Self::List { .. } => "list",
+                Self::Watch { .. } => "watch",
+                Self::Tags { .. } => "tags",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "add")]
+        Add(u64),
+        #[serde(rename = "list")]
+        List(Vec<Todo>),
+        #[serde(rename = "watch")]
+        Watch(Todo),
+        #[serde(rename = "tags")]
+        Tags(<TagService as Rpc>::Response),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Add(..) => false,
+                Self::List(..) => false,
+                Self::Watch(..) => false,
+                Self::Tags(response) => ::trait_rpc::Response::is_error(response),
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Add(..) => "add",
+                Self::List(..) => "list",
+                Self::Watch(..) => "watch",
+                Self::Tags(..) => "tags",
+            }
+        }
+    }
+
+    /// A todo list, where each argument is sent by its name
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait TodoListServer: Send + Sync {
+        /// Add a new todo item
+        fn add(&self, title: String, done: bool) -> impl Future<Output = u64> + Send;
+        /// List every todo item
+        fn list(&self) -> impl Future<Output = Vec<Todo>> + Send;
+        /// Watch the todo items for changes
+        fn watch(
+            &self,
+            sink: impl Sink<Todo, Error = Infallible> + Send + 'static,
+            since: u64,
+        ) -> impl Future<Output = ()> + Send;
+        /// The tags of a given todo item
+        fn tags(&self, id: u64) -> impl Future<Output = impl IntoHandler<TagService>> + Send;
+    }
+
+    impl<_Server: TodoListServer + ?Sized> TodoListServer for &_Server {
+        fn add(&self, title: String, done: bool) -> impl Future<Output = u64> + Send {
+            <_Server as TodoListServer>::add(*self, title, done)
+        }
+        fn list(&self) -> impl Future<Output = Vec<Todo>> + Send {
+            <_Server as TodoListServer>::list(*self)
+        }
+        fn watch(
+            &self,
+            sink: impl Sink<Todo, Error = Infallible> + Send + 'static,
+            since: u64,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as TodoListServer>::watch(*self, sink, since)
+        }
+        fn tags(&self, id: u64) -> impl Future<Output = impl IntoHandler<TagService>> + Send {
+            <_Server as TodoListServer>::tags(*self, id)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct TodoListHandler<_Server>(_Server);
+
+    impl<_Server: TodoListServer> Handler for TodoListHandler<_Server> {
+        type Rpc = TodoList;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Add { title, done } => Response::Add(self.0.add(title, done).await),
+                Request::List {} => Response::List(self.0.list().await),
+                Request::Tags { id, request } => {
+                    let response = self.0.tags(id).await.into_handler().handle(request).await;
+                    Response::Tags(response)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::Watch { since } => {
+                    let sink =
+                        sink.with(async |value| Result::<_, S::Error>::Ok(Response::Watch(value)));
+                    self.0.watch(sink, since).await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A todo list, where each argument is sent by its name
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct TodoListAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> TodoListAsyncClient<_Client> {
+        /// Add a new todo item
+        pub async fn add(&self, title: String, done: bool) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::Add { title, done }).await? {
+                Response::Add(value) => Ok(value),
+                other => Err(WrongResponseType::new("add", other.fn_name()).into()),
+            }
+        }
+        /// List every todo item
+        pub async fn list(&self) -> Result<Vec<Todo>, _Client::Error> {
+            match self.0.send(Request::List {}).await? {
+                Response::List(value) => Ok(value),
+                other => Err(WrongResponseType::new("list", other.fn_name()).into()),
+            }
+        }
+        /// Watch the todo items for changes
+        pub async fn watch(
+            &self,
+            since: u64,
+        ) -> Result<impl Stream<Item = Result<Todo, _Client::Error>>, _Client::Error>
+        where
+            _Client: StreamClient<Request, Response>,
+        {
+            let stream = self
+                .0
+                .send_streaming_response(Request::Watch { since })
+                .await?;
+            Ok(stream.map(|value| {
+                match value {
+                    Ok(Response::Watch(value)) => Ok(value),
+                    Ok(other) => Err(WrongResponseType::new("watch", other.fn_name()).into()),
+                    Err(error) => Err(error.into()),
+                }
+            }))
+        }
+        /// The tags of a given todo item
+        pub fn tags(
+            &self,
+            id: u64,
+        ) -> <TagService as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <TagService as Rpc>::Request,
+                Request,
+                <TagService as Rpc>::Response,
+                Response,
+                (u64,),
+            >,
+        > {
+            TagService::async_client(MappedClient::<
+                _,
+                <TagService as Rpc>::Request,
+                Request,
+                <TagService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (id,),
+                Self::tags_to_inner,
+                Self::tags_to_outer,
+                Self::tags_to_outer_ref,
+            ))
+        }
+        fn tags_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<TagService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Tags(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("tags", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("tags")),
+            }
+        }
+        fn tags_to_outer((id,): (u64,), inner: <TagService as Rpc>::Request) -> Request {
+            Request::Tags { id, request: inner }
+        }
+        fn tags_to_outer_ref<'req>(
+            args: &'req (u64,),
+            inner: <<TagService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Tags {
+                id: &args.0,
+                request: inner,
+            }
+        }
+    }
+
+    /// A todo list, where each argument is sent by its name
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct TodoListBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> TodoListBlockingClient<_Client> {
+        /// Add a new todo item
+        pub fn add(&self, title: String, done: bool) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::Add { title, done })? {
+                Response::Add(value) => Ok(value),
+                other => Err(WrongResponseType::new("add", other.fn_name()).into()),
+            }
+        }
+        /// List every todo item
+        pub fn list(&self) -> Result<Vec<Todo>, _Client::Error> {
+            match self.0.send(Request::List {})? {
+                Response::List(value) => Ok(value),
+                other => Err(WrongResponseType::new("list", other.fn_name()).into()),
+            }
+        }
+        /// The tags of a given todo item
+        pub fn tags(
+            &self,
+            id: u64,
+        ) -> <TagService as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <TagService as Rpc>::Request,
+                Request,
+                <TagService as Rpc>::Response,
+                Response,
+                (u64,),
+            >,
+        > {
+            TagService::blocking_client(MappedClient::<
+                _,
+                <TagService as Rpc>::Request,
+                Request,
+                <TagService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (id,),
+                Self::tags_to_inner,
+                Self::tags_to_outer,
+                Self::tags_to_outer_ref,
+            ))
+        }
+        fn tags_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<TagService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Tags(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("tags", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("tags")),
+            }
+        }
+        fn tags_to_outer((id,): (u64,), inner: <TagService as Rpc>::Request) -> Request {
+            Request::Tags { id, request: inner }
+        }
+        fn tags_to_outer_ref<'req>(
+            args: &'req (u64,),
+            inner: <<TagService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Tags {
+                id: &args.0,
+                request: inner,
+            }
+        }
+    }
+}
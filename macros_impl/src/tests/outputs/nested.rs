@@ -9,7 +9,7 @@ mod api_service {
     use std::convert::Infallible;
     use std::marker::PhantomData;
     use ::trait_rpc::{
-        client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
         futures::sink::{Sink, SinkExt},
         futures::stream::{Stream, StreamExt},
         serde::{Deserialize, Serialize},
@@ -24,6 +24,7 @@ mod api_service {
         type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
             ApiServiceBlockingClient<_Client>;
         type Request = Request;
+        type BorrowedRequest<'req> = Request;
         type Response = Response;
         fn async_client<_Client: AsyncClient<Request, Response>>(
             transport: _Client,
@@ -46,6 +47,7 @@ mod api_service {
 
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
     #[serde(tag = "method", content = "args")]
     pub enum Request {
         #[serde(rename = "users")]
@@ -53,14 +55,93 @@ mod api_service {
         #[serde(rename = "login")]
         Login(String, String),
     }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
     impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "users",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<UsersService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+            ::trait_rpc::MethodInfo {
+                name: "login",
+                aliases: &[],
+                args: &["username", "password"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Users(..) => false,
+                Self::Login(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Users(..) => "users",
+                Self::Login(..) => "login",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "users")]
+        Users(<<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>),
+        #[serde(rename = "login")]
+        Login(String, String),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
         fn is_streaming_response(&self) -> bool {
             match self {
                 Self::Users(..) => false,
                 Self::Login(..) => false,
             }
         }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Users(..) => "users",
+                Self::Login(..) => "login",
+            }
+        }
     }
+
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
     #[serde(tag = "method", content = "result")]
@@ -70,6 +151,14 @@ mod api_service {
         #[serde(rename = "login")]
         Login(Option<LoginToken>),
     }
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Users(response) => ::trait_rpc::Response::is_error(response),
+                Self::Login(..) => false,
+            }
+        }
+    }
     impl Response {
         fn fn_name(&self) -> &'static str {
             match self {
@@ -88,6 +177,19 @@ mod api_service {
             password: String,
         ) -> impl Future<Output = Option<LoginToken>> + Send;
     }
+
+    impl<_Server: ApiServiceServer + ?Sized> ApiServiceServer for &_Server {
+        fn users(&self) -> impl Future<Output = impl IntoHandler<UsersService>> + Send {
+            <_Server as ApiServiceServer>::users(*self)
+        }
+        fn login(
+            &self,
+            username: String,
+            password: String,
+        ) -> impl Future<Output = Option<LoginToken>> + Send {
+            <_Server as ApiServiceServer>::login(*self, username, password)
+        }
+    }
     /// A [Handler](Handler) which handles requests/responses for a given service
     #[derive(Debug, Clone)]
     pub struct ApiServiceHandler<_Server>(_Server);
@@ -120,7 +222,7 @@ mod api_service {
 
     /// This is the async client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -139,11 +241,19 @@ mod api_service {
                 (),
             >,
         > {
-            UsersService::async_client(MappedClient::new(
+            UsersService::async_client(MappedClient::<
+                _,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (),
                 Self::users_to_inner,
                 Self::users_to_outer,
+                Self::users_to_outer_ref,
             ))
         }
         fn users_to_inner(
@@ -158,6 +268,12 @@ mod api_service {
         fn users_to_outer((): (), inner: <UsersService as Rpc>::Request) -> Request {
             Request::Users(inner)
         }
+        fn users_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Users(inner)
+        }
         pub async fn login(
             &self,
             username: String,
@@ -171,7 +287,7 @@ mod api_service {
     }
     /// This is the blocking client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -189,11 +305,19 @@ mod api_service {
                 (),
             >,
         > {
-            UsersService::blocking_client(MappedClient::new(
+            UsersService::blocking_client(MappedClient::<
+                _,
+                <UsersService as Rpc>::Request,
+                Request,
+                <UsersService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (),
                 Self::users_to_inner,
                 Self::users_to_outer,
+                Self::users_to_outer_ref,
             ))
         }
         fn users_to_inner(
@@ -208,6 +332,12 @@ mod api_service {
         fn users_to_outer((): (), inner: <UsersService as Rpc>::Request) -> Request {
             Request::Users(inner)
         }
+        fn users_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<UsersService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Users(inner)
+        }
         pub fn login(
             &self,
             username: String,
@@ -230,7 +360,7 @@ mod users_service {
     use std::convert::Infallible;
     use std::marker::PhantomData;
     use ::trait_rpc::{
-        client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
         futures::sink::{Sink, SinkExt},
         futures::stream::{Stream, StreamExt},
         serde::{Deserialize, Serialize},
@@ -245,6 +375,7 @@ mod users_service {
         type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
             UsersServiceBlockingClient<_Client>;
         type Request = Request;
+        type BorrowedRequest<'req> = Request;
         type Response = Response;
         fn async_client<_Client: AsyncClient<Request, Response>>(
             transport: _Client,
@@ -267,6 +398,7 @@ mod users_service {
 
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
     #[serde(tag = "method", content = "args")]
     pub enum Request {
         #[serde(rename = "new")]
@@ -278,7 +410,68 @@ mod users_service {
         #[serde(rename = "current")]
         Current(LoginToken, <UserService as Rpc>::Request),
     }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
     impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "new",
+                aliases: &[],
+                args: &["user"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "list",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "by_id",
+                aliases: &[],
+                args: &["id"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<UserService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+            ::trait_rpc::MethodInfo {
+                name: "current",
+                aliases: &[],
+                args: &["token"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<UserService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
         fn is_streaming_response(&self) -> bool {
             match self {
                 Self::New(..) => false,
@@ -287,7 +480,59 @@ mod users_service {
                 Self::Current(..) => false,
             }
         }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::New(..) => "new",
+                Self::List(..) => "list",
+                Self::ById(..) => "by_id",
+                Self::Current(..) => "current",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "new")]
+        New(NewUser),
+        #[serde(rename = "list")]
+        List(),
+        #[serde(rename = "by_id")]
+        ById(
+            &'req u64,
+            <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ),
+        #[serde(rename = "current")]
+        Current(
+            &'req LoginToken,
+            <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ),
     }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::New(..) => false,
+                Self::List(..) => false,
+                Self::ById(..) => false,
+                Self::Current(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::New(..) => "new",
+                Self::List(..) => "list",
+                Self::ById(..) => "by_id",
+                Self::Current(..) => "current",
+            }
+        }
+    }
+
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
     #[serde(tag = "method", content = "result")]
@@ -301,6 +546,16 @@ mod users_service {
         #[serde(rename = "current")]
         Current(<UserService as Rpc>::Response),
     }
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::New(..) => false,
+                Self::List(..) => false,
+                Self::ById(response) => ::trait_rpc::Response::is_error(response),
+                Self::Current(response) => ::trait_rpc::Response::is_error(response),
+            }
+        }
+    }
     impl Response {
         fn fn_name(&self) -> &'static str {
             match self {
@@ -322,6 +577,24 @@ mod users_service {
             token: LoginToken,
         ) -> impl Future<Output = impl IntoHandler<UserService>> + Send;
     }
+
+    impl<_Server: UsersServiceServer + ?Sized> UsersServiceServer for &_Server {
+        fn new(&self, user: NewUser) -> impl Future<Output = User> + Send {
+            <_Server as UsersServiceServer>::new(*self, user)
+        }
+        fn list(&self) -> impl Future<Output = Vec<User>> + Send {
+            <_Server as UsersServiceServer>::list(*self)
+        }
+        fn by_id(&self, id: u64) -> impl Future<Output = impl IntoHandler<UserService>> + Send {
+            <_Server as UsersServiceServer>::by_id(*self, id)
+        }
+        fn current(
+            &self,
+            token: LoginToken,
+        ) -> impl Future<Output = impl IntoHandler<UserService>> + Send {
+            <_Server as UsersServiceServer>::current(*self, token)
+        }
+    }
     /// A [Handler](Handler) which handles requests/responses for a given service
     #[derive(Debug, Clone)]
     pub struct UsersServiceHandler<_Server>(_Server);
@@ -347,7 +620,7 @@ mod users_service {
                         .handle(request)
                         .await;
                     Response::Current(response)
-                },
+                }
                 _ => panic!("This is a streaming method, must call handle_streaming"),
             }
         }
@@ -366,7 +639,7 @@ mod users_service {
 
     /// This is the async client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -398,11 +671,19 @@ mod users_service {
                 (u64,),
             >,
         > {
-            UserService::async_client(MappedClient::new(
+            UserService::async_client(MappedClient::<
+                _,
+                <UserService as Rpc>::Request,
+                Request,
+                <UserService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (id,),
                 Self::by_id_to_inner,
                 Self::by_id_to_outer,
+                Self::by_id_to_outer_ref,
             ))
         }
         fn by_id_to_inner(
@@ -417,6 +698,12 @@ mod users_service {
         fn by_id_to_outer((id,): (u64,), inner: <UserService as Rpc>::Request) -> Request {
             Request::ById(id, inner)
         }
+        fn by_id_to_outer_ref<'req>(
+            args: &'req (u64,),
+            inner: <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::ById(&args.0, inner)
+        }
         pub fn current(
             &self,
             token: LoginToken,
@@ -430,11 +717,19 @@ mod users_service {
                 (LoginToken,),
             >,
         > {
-            UserService::async_client(MappedClient::new(
+            UserService::async_client(MappedClient::<
+                _,
+                <UserService as Rpc>::Request,
+                Request,
+                <UserService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (token,),
                 Self::current_to_inner,
                 Self::current_to_outer,
+                Self::current_to_outer_ref,
             ))
         }
         fn current_to_inner(
@@ -452,10 +747,16 @@ mod users_service {
         ) -> Request {
             Request::Current(token, inner)
         }
+        fn current_to_outer_ref<'req>(
+            args: &'req (LoginToken,),
+            inner: <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Current(&args.0, inner)
+        }
     }
     /// This is the blocking client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -486,11 +787,19 @@ mod users_service {
                 (u64,),
             >,
         > {
-            UserService::blocking_client(MappedClient::new(
+            UserService::blocking_client(MappedClient::<
+                _,
+                <UserService as Rpc>::Request,
+                Request,
+                <UserService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (id,),
                 Self::by_id_to_inner,
                 Self::by_id_to_outer,
+                Self::by_id_to_outer_ref,
             ))
         }
         fn by_id_to_inner(
@@ -505,6 +814,12 @@ mod users_service {
         fn by_id_to_outer((id,): (u64,), inner: <UserService as Rpc>::Request) -> Request {
             Request::ById(id, inner)
         }
+        fn by_id_to_outer_ref<'req>(
+            args: &'req (u64,),
+            inner: <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::ById(&args.0, inner)
+        }
         pub fn current(
             &self,
             token: LoginToken,
@@ -518,11 +833,19 @@ mod users_service {
                 (LoginToken,),
             >,
         > {
-            UserService::blocking_client(MappedClient::new(
+            UserService::blocking_client(MappedClient::<
+                _,
+                <UserService as Rpc>::Request,
+                Request,
+                <UserService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
                 self.0.clone(),
                 (token,),
                 Self::current_to_inner,
                 Self::current_to_outer,
+                Self::current_to_outer_ref,
             ))
         }
         fn current_to_inner(
@@ -540,6 +863,12 @@ mod users_service {
         ) -> Request {
             Request::Current(token, inner)
         }
+        fn current_to_outer_ref<'req>(
+            args: &'req (LoginToken,),
+            inner: <<UserService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Current(&args.0, inner)
+        }
     }
 }
 #[allow(unused_imports, reason = "These might not always be used, but they should be available in this module anyway")]
@@ -552,7 +881,7 @@ mod user_service {
     use std::convert::Infallible;
     use std::marker::PhantomData;
     use ::trait_rpc::{
-        client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
         futures::sink::{Sink, SinkExt},
         futures::stream::{Stream, StreamExt},
         serde::{Deserialize, Serialize},
@@ -567,6 +896,7 @@ mod user_service {
         type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
             UserServiceBlockingClient<_Client>;
         type Request = Request;
+        type BorrowedRequest<'req> = Request;
         type Response = Response;
         fn async_client<_Client: AsyncClient<Request, Response>>(
             transport: _Client,
@@ -589,6 +919,7 @@ mod user_service {
 
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
     #[serde(tag = "method", content = "args")]
     pub enum Request {
         #[serde(rename = "get")]
@@ -598,7 +929,59 @@ mod user_service {
         #[serde(rename = "delete")]
         Delete(),
     }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
     impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "get",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "update",
+                aliases: &[],
+                args: &["user"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "delete",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
         fn is_streaming_response(&self) -> bool {
             match self {
                 Self::Get(..) => false,
@@ -606,6 +989,13 @@ mod user_service {
                 Self::Delete(..) => false,
             }
         }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Get(..) => "get",
+                Self::Update(..) => "update",
+                Self::Delete(..) => "delete",
+            }
+        }
     }
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
@@ -618,6 +1008,15 @@ mod user_service {
         #[serde(rename = "delete")]
         Delete(Result<User, UserNotFound>),
     }
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Get(..) => false,
+                Self::Update(..) => false,
+                Self::Delete(..) => false,
+            }
+        }
+    }
     impl Response {
         fn fn_name(&self) -> &'static str {
             match self {
@@ -636,6 +1035,21 @@ mod user_service {
         ) -> impl Future<Output = Result<User, UserNotFound>> + Send;
         fn delete(&self) -> impl Future<Output = Result<User, UserNotFound>> + Send;
     }
+
+    impl<_Server: UserServiceServer + ?Sized> UserServiceServer for &_Server {
+        fn get(&self) -> impl Future<Output = Result<User, UserNotFound>> + Send {
+            <_Server as UserServiceServer>::get(*self)
+        }
+        fn update(
+            &self,
+            user: UserUpdate,
+        ) -> impl Future<Output = Result<User, UserNotFound>> + Send {
+            <_Server as UserServiceServer>::update(*self, user)
+        }
+        fn delete(&self) -> impl Future<Output = Result<User, UserNotFound>> + Send {
+            <_Server as UserServiceServer>::delete(*self)
+        }
+    }
     /// A [Handler](Handler) which handles requests/responses for a given service
     #[derive(Debug, Clone)]
     pub struct UserServiceHandler<_Server>(_Server);
@@ -664,7 +1078,7 @@ mod user_service {
 
     /// This is the async client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -695,7 +1109,7 @@ mod user_service {
     }
     /// This is the blocking client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
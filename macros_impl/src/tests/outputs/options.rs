@@ -0,0 +1,357 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use clock::{Clock, ClockAsyncClient, ClockBlockingClient};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod clock {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::my_crate::rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A clock which is served by another application
+    ///
+    /// This is the [Rpc](::my_crate::rpc::Rpc) definition for this service
+    pub struct Clock;
+
+    impl Rpc for Clock {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            ClockAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            ClockBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> ClockAsyncClient<_Client> {
+            ClockAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> ClockBlockingClient<_Client> {
+            ClockBlockingClient(transport)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+    #[serde(crate = "::my_crate::rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "currentTime")]
+        CurrentTime(),
+        #[serde(rename = "setAlarm")]
+        SetAlarm(u64),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::my_crate::rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::my_crate::rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::my_crate::rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::my_crate::rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::my_crate::rpc::Request for Request {
+        const METHODS: &'static [::my_crate::rpc::MethodInfo] = &[
+            ::my_crate::rpc::MethodInfo {
+                name: "currentTime",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::my_crate::rpc::MethodInfo {
+                name: "setAlarm",
+                aliases: &[],
+                args: &["at"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::CurrentTime(..) => false,
+                Self::SetAlarm(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::CurrentTime(..) => "currentTime",
+                Self::SetAlarm(..) => "setAlarm",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+    #[serde(crate = "::my_crate::rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "currentTime")]
+        CurrentTime(u64),
+        #[serde(rename = "setAlarm")]
+        SetAlarm(()),
+    }
+
+    impl ::my_crate::rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::CurrentTime(..) => false,
+                Self::SetAlarm(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::CurrentTime(..) => "currentTime",
+                Self::SetAlarm(..) => "setAlarm",
+            }
+        }
+    }
+
+    /// A clock which is served by another application
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::my_crate::rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct ClockAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> ClockAsyncClient<_Client> {
+        /// The current time, in seconds since the epoch
+        pub async fn current_time(&self) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::CurrentTime()).await? {
+                Response::CurrentTime(value) => Ok(value),
+                other => Err(WrongResponseType::new("currentTime", other.fn_name()).into()),
+            }
+        }
+        /// Set an alarm for the given time
+        pub async fn set_alarm(&self, at: u64) -> Result<(), _Client::Error> {
+            match self.0.send(Request::SetAlarm(at)).await? {
+                Response::SetAlarm(value) => Ok(value),
+                other => Err(WrongResponseType::new("setAlarm", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// A clock which is served by another application
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::my_crate::rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct ClockBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> ClockBlockingClient<_Client> {
+        /// The current time, in seconds since the epoch
+        pub fn current_time(&self) -> Result<u64, _Client::Error> {
+            match self.0.send(Request::CurrentTime())? {
+                Response::CurrentTime(value) => Ok(value),
+                other => Err(WrongResponseType::new("currentTime", other.fn_name()).into()),
+            }
+        }
+        /// Set an alarm for the given time
+        pub fn set_alarm(&self, at: u64) -> Result<(), _Client::Error> {
+            match self.0.send(Request::SetAlarm(at))? {
+                Response::SetAlarm(value) => Ok(value),
+                other => Err(WrongResponseType::new("setAlarm", other.fn_name()).into()),
+            }
+        }
+    }
+}
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use alarm::{Alarm, AlarmServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod alarm {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// An alarm which is only called by clients in other languages
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct Alarm;
+
+    impl Rpc for Alarm {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> = _Client;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> = _Client;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(transport: _Client) -> _Client {
+            transport
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> _Client {
+            transport
+        }
+    }
+
+    impl<Server: AlarmServer> RpcWithServer<Server> for Alarm {
+        type Handler = AlarmHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            AlarmHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "ring-now")]
+        RingNow(u8),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[::trait_rpc::MethodInfo {
+            name: "ring-now",
+            aliases: &[],
+            args: &["volume"],
+            named: false,
+            streaming: false,
+            error: false,
+            nested: None,
+        }];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::RingNow(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::RingNow(..) => "ring-now",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "ring-now")]
+        RingNow(()),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::RingNow(..) => false,
+            }
+        }
+    }
+
+    /// An alarm which is only called by clients in other languages
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait AlarmServer: Send + Sync {
+        /// Ring the alarm now
+        fn ring_now(&self, volume: u8) -> impl Future<Output = ()> + Send;
+    }
+
+    impl<_Server: AlarmServer + ?Sized> AlarmServer for &_Server {
+        fn ring_now(&self, volume: u8) -> impl Future<Output = ()> + Send {
+            <_Server as AlarmServer>::ring_now(*self, volume)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct AlarmHandler<_Server>(_Server);
+
+    impl<_Server: AlarmServer> Handler for AlarmHandler<_Server> {
+        type Rpc = Alarm;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::RingNow(volume) => Response::RingNow(self.0.ring_now(volume).await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+}
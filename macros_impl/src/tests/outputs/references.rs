@@ -0,0 +1,575 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use phone_book::{PhoneBook, PhoneBookAsyncClient, PhoneBookBlockingClient, PhoneBookServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod phone_book {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A phone book, which looks up entries without copying the caller's strings
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct PhoneBook;
+
+    impl Rpc for PhoneBook {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            PhoneBookAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            PhoneBookBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> PhoneBookAsyncClient<_Client> {
+            PhoneBookAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> PhoneBookBlockingClient<_Client> {
+            PhoneBookBlockingClient(transport)
+        }
+    }
+
+    impl<Server: PhoneBookServer> RpcWithServer<Server> for PhoneBook {
+        type Handler = PhoneBookHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            PhoneBookHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "lookup")]
+        Lookup(String),
+        #[serde(rename = "add")]
+        Add(String, Vec<u64>),
+        #[serde(rename = "watch")]
+        Watch(String),
+        #[serde(rename = "group")]
+        Group(String, <Entries as Rpc>::Request),
+        #[serde(rename = "display_name")]
+        DisplayName(String),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "lookup",
+                aliases: &[],
+                args: &["name"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "add",
+                aliases: &[],
+                args: &["name", "numbers"],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "watch",
+                aliases: &[],
+                args: &["prefix"],
+                named: false,
+                streaming: true,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "group",
+                aliases: &[],
+                args: &["group"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<Entries as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+            ::trait_rpc::MethodInfo {
+                name: "display_name",
+                aliases: &[],
+                args: &["name"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Lookup(..) => false,
+                Self::Add(..) => false,
+                Self::Watch(..) => true,
+                Self::Group(..) => false,
+                Self::DisplayName(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Lookup(..) => "lookup",
+                Self::Add(..) => "add",
+                Self::Watch(..) => "watch",
+                Self::Group(..) => "group",
+                Self::DisplayName(..) => "display_name",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "lookup")]
+        Lookup(&'req str),
+        #[serde(rename = "add")]
+        Add(&'req str, &'req [u64]),
+        #[serde(rename = "watch")]
+        Watch(&'req str),
+        #[serde(rename = "group")]
+        Group(
+            &'req String,
+            <<Entries as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ),
+        #[serde(rename = "display_name")]
+        DisplayName(&'req str),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Lookup(..) => false,
+                Self::Add(..) => false,
+                Self::Watch(..) => true,
+                Self::Group(..) => false,
+                Self::DisplayName(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Lookup(..) => "lookup",
+                Self::Add(..) => "add",
+                Self::Watch(..) => "watch",
+                Self::Group(..) => "group",
+                Self::DisplayName(..) => "display_name",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "lookup")]
+        Lookup(Option<String>),
+        #[serde(rename = "add")]
+        Add(Result<usize, String>),
+        #[serde(rename = "watch")]
+        Watch(String),
+        #[serde(rename = "group")]
+        Group(<Entries as Rpc>::Response),
+        #[serde(rename = "display_name")]
+        DisplayName(String),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Lookup(..) => false,
+                Self::Add(response) => response.is_err(),
+                Self::Watch(..) => false,
+                Self::Group(response) => ::trait_rpc::Response::is_error(response),
+                Self::DisplayName(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Lookup(..) => "lookup",
+                Self::Add(..) => "add",
+                Self::Watch(..) => "watch",
+                Self::Group(..) => "group",
+                Self::DisplayName(..) => "display_name",
+            }
+        }
+    }
+
+    /// A phone book, which looks up entries without copying the caller's strings
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait PhoneBookServer: Send + Sync {
+        /// Look up the number of a name
+        fn lookup(&self, name: String) -> impl Future<Output = Option<String>> + Send;
+        /// Add the given numbers to a name
+        fn add(
+            &self,
+            name: String,
+            numbers: Vec<u64>,
+        ) -> impl Future<Output = Result<usize, String>> + Send;
+        /// Watch for changes to the entries with the given prefix
+        fn watch(
+            &self,
+            sink: impl Sink<String, Error = Infallible> + Send + 'static,
+            prefix: String,
+        ) -> impl Future<Output = ()> + Send;
+        /// The entries of a group
+        fn group(&self, group: String) -> impl Future<Output = impl IntoHandler<Entries>> + Send;
+        /// Format a name as it is written in the book, the default body sees the name as `&str`
+        fn display_name(&self, name: String) -> impl Future<Output = String> + Send {
+            async move {
+                let name: &str = ::std::borrow::Borrow::borrow(&name);
+                { str::to_uppercase(name) }
+            }
+        }
+    }
+
+    impl<_Server: PhoneBookServer + ?Sized> PhoneBookServer for &_Server {
+        fn lookup(&self, name: String) -> impl Future<Output = Option<String>> + Send {
+            <_Server as PhoneBookServer>::lookup(*self, name)
+        }
+        fn add(
+            &self,
+            name: String,
+            numbers: Vec<u64>,
+        ) -> impl Future<Output = Result<usize, String>> + Send {
+            <_Server as PhoneBookServer>::add(*self, name, numbers)
+        }
+        fn watch(
+            &self,
+            sink: impl Sink<String, Error = Infallible> + Send + 'static,
+            prefix: String,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as PhoneBookServer>::watch(*self, sink, prefix)
+        }
+        fn group(&self, group: String) -> impl Future<Output = impl IntoHandler<Entries>> + Send {
+            <_Server as PhoneBookServer>::group(*self, group)
+        }
+        fn display_name(&self, name: String) -> impl Future<Output = String> + Send {
+            <_Server as PhoneBookServer>::display_name(*self, name)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct PhoneBookHandler<_Server>(_Server);
+
+    impl<_Server: PhoneBookServer> Handler for PhoneBookHandler<_Server> {
+        type Rpc = PhoneBook;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Lookup(name) => Response::Lookup(self.0.lookup(name).await),
+                Request::Add(name, numbers) => Response::Add(self.0.add(name, numbers).await),
+                Request::Group(group, request) => {
+                    let response = self
+                        .0
+                        .group(group)
+                        .await
+                        .into_handler()
+                        .handle(request)
+                        .await;
+                    Response::Group(response)
+                }
+                Request::DisplayName(name) => {
+                    Response::DisplayName(self.0.display_name(name).await)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::Watch(prefix) => {
+                    let sink =
+                        sink.with(async |value| Result::<_, S::Error>::Ok(Response::Watch(value)));
+                    self.0.watch(sink, prefix).await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A phone book, which looks up entries without copying the caller's strings
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct PhoneBookAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> PhoneBookAsyncClient<_Client> {
+        /// Look up the number of a name
+        pub async fn lookup(&self, name: &str) -> Result<Option<String>, _Client::Error>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Lookup(name)).await? {
+                Response::Lookup(value) => Ok(value),
+                other => Err(WrongResponseType::new("lookup", other.fn_name()).into()),
+            }
+        }
+        /// Add the given numbers to a name
+        pub async fn add(
+            &self,
+            name: &str,
+            numbers: &[u64],
+        ) -> Result<usize, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send_ref(RequestRef::Add(name, numbers))
+                .await
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Add(Ok(value)) => Ok(value),
+                Response::Add(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("add", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// Watch for changes to the entries with the given prefix
+        pub async fn watch<'req>(
+            &'req self,
+            prefix: &'req str,
+        ) -> Result<impl Stream<Item = Result<String, _Client::Error>>, _Client::Error>
+        where
+            _Client: StreamRefClient<Request, Response>,
+        {
+            let stream = self
+                .0
+                .send_ref_streaming_response(RequestRef::Watch(prefix))
+                .await?;
+            Ok(stream.map(|value| {
+                match value {
+                    Ok(Response::Watch(value)) => Ok(value),
+                    Ok(other) => Err(WrongResponseType::new("watch", other.fn_name()).into()),
+                    Err(error) => Err(error.into()),
+                }
+            }))
+        }
+        /// The entries of a group
+        pub fn group(
+            &self,
+            group: &str,
+        ) -> <Entries as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <Entries as Rpc>::Request,
+                Request,
+                <Entries as Rpc>::Response,
+                Response,
+                (String,),
+            >,
+        > {
+            Entries::async_client(MappedClient::<
+                _,
+                <Entries as Rpc>::Request,
+                Request,
+                <Entries as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (::std::borrow::ToOwned::to_owned(group),),
+                Self::group_to_inner,
+                Self::group_to_outer,
+                Self::group_to_outer_ref,
+            ))
+        }
+        fn group_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<Entries as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Group(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("group", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("group")),
+            }
+        }
+        fn group_to_outer((group,): (String,), inner: <Entries as Rpc>::Request) -> Request {
+            Request::Group(group, inner)
+        }
+        fn group_to_outer_ref<'req>(
+            args: &'req (String,),
+            inner: <<Entries as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Group(&args.0, inner)
+        }
+        /// Format a name as it is written in the book, the default body sees the name as `&str`
+        pub async fn display_name(&self, name: &str) -> Result<String, _Client::Error>
+        where
+            _Client: AsyncRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::DisplayName(name)).await? {
+                Response::DisplayName(value) => Ok(value),
+                other => Err(WrongResponseType::new("display_name", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// A phone book, which looks up entries without copying the caller's strings
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct PhoneBookBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> PhoneBookBlockingClient<_Client> {
+        /// Look up the number of a name
+        pub fn lookup(&self, name: &str) -> Result<Option<String>, _Client::Error>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::Lookup(name))? {
+                Response::Lookup(value) => Ok(value),
+                other => Err(WrongResponseType::new("lookup", other.fn_name()).into()),
+            }
+        }
+        /// Add the given numbers to a name
+        pub fn add(
+            &self,
+            name: &str,
+            numbers: &[u64],
+        ) -> Result<usize, <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send_ref(RequestRef::Add(name, numbers))
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::Add(Ok(value)) => Ok(value),
+                Response::Add(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("add", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// The entries of a group
+        pub fn group(
+            &self,
+            group: &str,
+        ) -> <Entries as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <Entries as Rpc>::Request,
+                Request,
+                <Entries as Rpc>::Response,
+                Response,
+                (String,),
+            >,
+        > {
+            Entries::blocking_client(MappedClient::<
+                _,
+                <Entries as Rpc>::Request,
+                Request,
+                <Entries as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (::std::borrow::ToOwned::to_owned(group),),
+                Self::group_to_inner,
+                Self::group_to_outer,
+                Self::group_to_outer_ref,
+            ))
+        }
+        fn group_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<Entries as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::Group(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("group", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("group")),
+            }
+        }
+        fn group_to_outer((group,): (String,), inner: <Entries as Rpc>::Request) -> Request {
+            Request::Group(group, inner)
+        }
+        fn group_to_outer_ref<'req>(
+            args: &'req (String,),
+            inner: <<Entries as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::Group(&args.0, inner)
+        }
+        /// Format a name as it is written in the book, the default body sees the name as `&str`
+        pub fn display_name(&self, name: &str) -> Result<String, _Client::Error>
+        where
+            _Client: BlockingRefClient<Request, Response>,
+        {
+            match self.0.send_ref(RequestRef::DisplayName(name))? {
+                Response::DisplayName(value) => Ok(value),
+                other => Err(WrongResponseType::new("display_name", other.fn_name()).into()),
+            }
+        }
+    }
+}
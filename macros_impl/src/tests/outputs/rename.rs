@@ -0,0 +1,445 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use todo_list::{TodoList, TodoListAsyncClient, TodoListBlockingClient, TodoListServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod todo_list {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A to-do list, whose methods have been renamed since it was first deployed
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct TodoList;
+
+    impl Rpc for TodoList {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            TodoListAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            TodoListBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> TodoListAsyncClient<_Client> {
+            TodoListAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> TodoListBlockingClient<_Client> {
+            TodoListBlockingClient(transport)
+        }
+    }
+
+    impl<Server: TodoListServer> RpcWithServer<Server> for TodoList {
+        type Handler = TodoListHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            TodoListHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "get_todo")]
+        FindTodo(String),
+        #[serde(rename = "removeTodo")]
+        #[serde(alias = "delete")]
+        #[serde(alias = "deleteTodo")]
+        RemoveTodo(String),
+        #[serde(rename = "shared")]
+        #[serde(alias = "friends")]
+        SharedLists(<SharedLists as Rpc>::Request),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "get_todo",
+                aliases: &[],
+                args: &["name"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "removeTodo",
+                aliases: &["delete", "deleteTodo"],
+                args: &["name"],
+                named: false,
+                streaming: false,
+                error: true,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "shared",
+                aliases: &["friends"],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<SharedLists as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::FindTodo(..) => false,
+                Self::RemoveTodo(..) => false,
+                Self::SharedLists(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::FindTodo(..) => "get_todo",
+                Self::RemoveTodo(..) => "removeTodo",
+                Self::SharedLists(..) => "shared",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "get_todo")]
+        FindTodo(String),
+        #[serde(rename = "removeTodo")]
+        RemoveTodo(String),
+        #[serde(rename = "shared")]
+        SharedLists(<<SharedLists as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::FindTodo(..) => false,
+                Self::RemoveTodo(..) => false,
+                Self::SharedLists(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::FindTodo(..) => "get_todo",
+                Self::RemoveTodo(..) => "removeTodo",
+                Self::SharedLists(..) => "shared",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "get_todo")]
+        FindTodo(Option<String>),
+        #[serde(rename = "removeTodo")]
+        #[serde(alias = "delete")]
+        #[serde(alias = "deleteTodo")]
+        RemoveTodo(Result<(), String>),
+        #[serde(rename = "shared")]
+        #[serde(alias = "friends")]
+        SharedLists(<SharedLists as Rpc>::Response),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::FindTodo(..) => false,
+                Self::RemoveTodo(response) => response.is_err(),
+                Self::SharedLists(response) => ::trait_rpc::Response::is_error(response),
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::FindTodo(..) => "get_todo",
+                Self::RemoveTodo(..) => "removeTodo",
+                Self::SharedLists(..) => "shared",
+            }
+        }
+    }
+
+    /// A to-do list, whose methods have been renamed since it was first deployed
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait TodoListServer: Send + Sync {
+        /// Find a to-do by its name, this was `get_todo`
+        fn find_todo(&self, name: String) -> impl Future<Output = Option<String>> + Send;
+        /// Remove a to-do, which is still accepted by its old names
+        fn remove_todo(&self, name: String) -> impl Future<Output = Result<(), String>> + Send;
+        /// The lists which are shared with this one
+        fn shared_lists(&self) -> impl Future<Output = impl IntoHandler<SharedLists>> + Send;
+    }
+
+    impl<_Server: TodoListServer + ?Sized> TodoListServer for &_Server {
+        fn find_todo(&self, name: String) -> impl Future<Output = Option<String>> + Send {
+            <_Server as TodoListServer>::find_todo(*self, name)
+        }
+        fn remove_todo(&self, name: String) -> impl Future<Output = Result<(), String>> + Send {
+            <_Server as TodoListServer>::remove_todo(*self, name)
+        }
+        fn shared_lists(&self) -> impl Future<Output = impl IntoHandler<SharedLists>> + Send {
+            <_Server as TodoListServer>::shared_lists(*self)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct TodoListHandler<_Server>(_Server);
+
+    impl<_Server: TodoListServer> Handler for TodoListHandler<_Server> {
+        type Rpc = TodoList;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::FindTodo(name) => Response::FindTodo(self.0.find_todo(name).await),
+                Request::RemoveTodo(name) => Response::RemoveTodo(self.0.remove_todo(name).await),
+                Request::SharedLists(request) => {
+                    let response = self
+                        .0
+                        .shared_lists()
+                        .await
+                        .into_handler()
+                        .handle(request)
+                        .await;
+                    Response::SharedLists(response)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A to-do list, whose methods have been renamed since it was first deployed
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct TodoListAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> TodoListAsyncClient<_Client> {
+        /// Find a to-do by its name, this was `get_todo`
+        pub async fn find_todo(&self, name: String) -> Result<Option<String>, _Client::Error> {
+            match self.0.send(Request::FindTodo(name)).await? {
+                Response::FindTodo(value) => Ok(value),
+                other => Err(WrongResponseType::new("get_todo", other.fn_name()).into()),
+            }
+        }
+        /// Remove a to-do, which is still accepted by its old names
+        pub async fn remove_todo(
+            &self,
+            name: String,
+        ) -> Result<(), <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::RemoveTodo(name))
+                .await
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::RemoveTodo(Ok(value)) => Ok(value),
+                Response::RemoveTodo(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("removeTodo", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// The lists which are shared with this one
+        pub fn shared_lists(
+            &self,
+        ) -> <SharedLists as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <SharedLists as Rpc>::Request,
+                Request,
+                <SharedLists as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            SharedLists::async_client(MappedClient::<
+                _,
+                <SharedLists as Rpc>::Request,
+                Request,
+                <SharedLists as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::shared_lists_to_inner,
+                Self::shared_lists_to_outer,
+                Self::shared_lists_to_outer_ref,
+            ))
+        }
+        fn shared_lists_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<SharedLists as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::SharedLists(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("shared", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("shared")),
+            }
+        }
+        fn shared_lists_to_outer((): (), inner: <SharedLists as Rpc>::Request) -> Request {
+            Request::SharedLists(inner)
+        }
+        fn shared_lists_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<SharedLists as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::SharedLists(inner)
+        }
+    }
+
+    /// A to-do list, whose methods have been renamed since it was first deployed
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct TodoListBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> TodoListBlockingClient<_Client> {
+        /// Find a to-do by its name, this was `get_todo`
+        pub fn find_todo(&self, name: String) -> Result<Option<String>, _Client::Error> {
+            match self.0.send(Request::FindTodo(name))? {
+                Response::FindTodo(value) => Ok(value),
+                other => Err(WrongResponseType::new("get_todo", other.fn_name()).into()),
+            }
+        }
+        /// Remove a to-do, which is still accepted by its old names
+        pub fn remove_todo(
+            &self,
+            name: String,
+        ) -> Result<(), <_Client::Error as WithApplicationError<String>>::Output>
+        where
+            _Client::Error: WithApplicationError<String>,
+        {
+            match self
+                .0
+                .send(Request::RemoveTodo(name))
+                .map_err(<_Client::Error as WithApplicationError<String>>::from_client)?
+            {
+                Response::RemoveTodo(Ok(value)) => Ok(value),
+                Response::RemoveTodo(Err(error)) => {
+                    Err(<_Client::Error as WithApplicationError<String>>::application(error))
+                }
+                other => Err(
+                    <_Client::Error as WithApplicationError<String>>::from_client(
+                        WrongResponseType::new("removeTodo", other.fn_name()).into(),
+                    ),
+                ),
+            }
+        }
+        /// The lists which are shared with this one
+        pub fn shared_lists(
+            &self,
+        ) -> <SharedLists as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <SharedLists as Rpc>::Request,
+                Request,
+                <SharedLists as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            SharedLists::blocking_client(MappedClient::<
+                _,
+                <SharedLists as Rpc>::Request,
+                Request,
+                <SharedLists as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::shared_lists_to_inner,
+                Self::shared_lists_to_outer,
+                Self::shared_lists_to_outer_ref,
+            ))
+        }
+        fn shared_lists_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<SharedLists as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::SharedLists(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("shared", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("shared")),
+            }
+        }
+        fn shared_lists_to_outer((): (), inner: <SharedLists as Rpc>::Request) -> Request {
+            Request::SharedLists(inner)
+        }
+        fn shared_lists_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<SharedLists as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::SharedLists(inner)
+        }
+    }
+}
@@ -6,7 +6,7 @@ mod resources {
     use std::convert::Infallible;
     use std::marker::PhantomData;
     use ::trait_rpc::{
-        client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
         futures::sink::{Sink, SinkExt},
         futures::stream::{Stream, StreamExt},
         serde::{Deserialize, Serialize},
@@ -24,6 +24,7 @@ mod resources {
         type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
             ResourcesBlockingClient<_Client, T>;
         type Request = Request<T>;
+        type BorrowedRequest<'req> = Request<T>;
         type Response = Response<T>;
         fn async_client<_Client: AsyncClient<Request<T>, Response<T>>>(
             transport: _Client,
@@ -45,6 +46,7 @@ mod resources {
     }
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
     #[serde(tag = "method", content = "args")]
     pub enum Request<T> {
         #[serde(rename = "subscribe")]
@@ -56,7 +58,74 @@ mod resources {
         #[serde(rename = "new")]
         New(T),
     }
+
+    impl<T> Serialize for Request<T>
+    where
+        T: Serialize,
+    {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de, T> Deserialize<'de> for Request<T>
+    where
+        T: Deserialize<'de>,
+    {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
     impl<T> ::trait_rpc::Request for Request<T> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "subscribe",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: true,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "list",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "get",
+                aliases: &[],
+                args: &["id"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "new",
+                aliases: &[],
+                args: &["value"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
         fn is_streaming_response(&self) -> bool {
             match self {
                 Self::Subscribe(..) => true,
@@ -65,6 +134,14 @@ mod resources {
                 Self::New(..) => false,
             }
         }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Subscribe(..) => "subscribe",
+                Self::List(..) => "list",
+                Self::Get(..) => "get",
+                Self::New(..) => "new",
+            }
+        }
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -80,6 +157,16 @@ mod resources {
         #[serde(rename = "new")]
         New(()),
     }
+    impl<T> ::trait_rpc::Response for Response<T> {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Subscribe(..) => false,
+                Self::List(..) => false,
+                Self::Get(..) => false,
+                Self::New(..) => false,
+            }
+        }
+    }
     impl<T> Response<T> {
         fn fn_name(&self) -> &'static str {
             match self {
@@ -97,6 +184,24 @@ mod resources {
         fn get(&self, id: u64) -> impl Future<Output = Option<T>> + Send;
         fn new(&self, value: T) -> impl Future<Output = ()> + Send;
     }
+
+    impl<_Server: ResourcesServer<T> + ?Sized, T> ResourcesServer<T> for &_Server {
+        fn subscribe(
+            &self,
+            sink: impl Sink<T, Error = Infallible> + Send + 'static,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as ResourcesServer<T>>::subscribe(*self, sink)
+        }
+        fn list(&self) -> impl Future<Output = Vec<T>> + Send {
+            <_Server as ResourcesServer<T>>::list(*self)
+        }
+        fn get(&self, id: u64) -> impl Future<Output = Option<T>> + Send {
+            <_Server as ResourcesServer<T>>::get(*self, id)
+        }
+        fn new(&self, value: T) -> impl Future<Output = ()> + Send {
+            <_Server as ResourcesServer<T>>::new(*self, value)
+        }
+    }
     /// A [Handler](Handler) which handles requests/responses for a given service
     #[derive(Debug, Clone)]
     pub struct ResourcesHandler<_Server, T>(_Server, (PhantomData<fn() -> (T,)>));
@@ -115,7 +220,7 @@ mod resources {
                 Request::Subscribe() => {
                     let sink = sink.with(async |value| Result::<_, S::Error>::Ok(Response::Subscribe(value)));
                     self.0.subscribe(sink).await;
-                },
+                }
                 _ => panic!("This is not a streaming method, must call handle"),
             }
         }
@@ -123,20 +228,25 @@ mod resources {
 
     /// This is the async client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
     pub struct ResourcesAsyncClient<_Client, T>(_Client, (PhantomData<fn() -> (T,)>));
     #[allow(clippy::future_not_send)]
-         impl<_Client: AsyncClient<Request<T>, Response<T>>, T> ResourcesAsyncClient<_Client, T> {
-        pub async fn subscribe(&self) -> Result<impl Stream<Item = Result<T, _Client::Error>>, _Client::Error> where _Client: StreamClient<Request<T>, Response<T>> {
+    impl<_Client: AsyncClient<Request<T>, Response<T>>, T> ResourcesAsyncClient<_Client, T> {
+        pub async fn subscribe(
+            &self,
+        ) -> Result<impl Stream<Item = Result<T, _Client::Error>>, _Client::Error>
+        where
+            _Client: StreamClient<Request<T>, Response<T>>,
+        {
             let stream = self.0.send_streaming_response(Request::Subscribe()).await?;
             Ok(stream.map(|value| {
                 match value {
                     Ok(Response::Subscribe(value)) => Ok(value),
                     Ok(other) => Err(WrongResponseType::new("subscribe", other.fn_name()).into()),
-                    Err(error) => {Err(error.into())},
+                    Err(error) => Err(error.into()),
                 }
             }))
         }
@@ -161,7 +271,7 @@ mod resources {
     }
     /// This is the blocking client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -15,7 +15,7 @@ mod todo_service {
     use std::convert::Infallible;
     use std::marker::PhantomData;
     use ::trait_rpc::{
-        client::{AsyncClient, BlockingClient, MappedClient, StreamClient, WrongResponseType},
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
         futures::sink::{Sink, SinkExt},
         futures::stream::{Stream, StreamExt},
         serde::{Deserialize, Serialize},
@@ -34,6 +34,7 @@ mod todo_service {
         type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
             TodoServiceBlockingClient<_Client>;
         type Request = Request;
+        type BorrowedRequest<'req> = Request;
         type Response = Response;
         fn async_client<_Client: AsyncClient<Request, Response>>(
             transport: _Client,
@@ -56,6 +57,7 @@ mod todo_service {
 
     #[derive(Debug, Serialize, Deserialize)]
     #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
     #[serde(tag = "method", content = "args")]
     pub enum Request {
         #[serde(rename = "get_todos")]
@@ -65,7 +67,59 @@ mod todo_service {
         #[serde(rename = "new_todo")]
         NewTodo(Todo),
     }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
     impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "get_todos",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "get_todo",
+                aliases: &[],
+                args: &["name"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "new_todo",
+                aliases: &[],
+                args: &["todo"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
         fn is_streaming_response(&self) -> bool {
             match self {
                 Self::GetTodos(..) => false,
@@ -73,6 +127,13 @@ mod todo_service {
                 Self::NewTodo(..) => false,
             }
         }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::GetTodos(..) => "get_todos",
+                Self::GetTodo(..) => "get_todo",
+                Self::NewTodo(..) => "new_todo",
+            }
+        }
     }
 
     #[derive(Debug, Serialize, Deserialize)]
@@ -86,6 +147,15 @@ mod todo_service {
         #[serde(rename = "new_todo")]
         NewTodo(()),
     }
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::GetTodos(..) => false,
+                Self::GetTodo(..) => false,
+                Self::NewTodo(..) => false,
+            }
+        }
+    }
     impl Response {
         fn fn_name(&self) -> &'static str {
             match self {
@@ -108,6 +178,18 @@ mod todo_service {
         fn new_todo(&self, todo: Todo) -> impl Future<Output = ()> + Send;
     }
 
+    impl<_Server: TodoServiceServer + ?Sized> TodoServiceServer for &_Server {
+        fn get_todos(&self) -> impl Future<Output = Vec<Todo>> + Send {
+            <_Server as TodoServiceServer>::get_todos(*self)
+        }
+        fn get_todo(&self, name: String) -> impl Future<Output = Option<Todo>> + Send {
+            <_Server as TodoServiceServer>::get_todo(*self, name)
+        }
+        fn new_todo(&self, todo: Todo) -> impl Future<Output = ()> + Send {
+            <_Server as TodoServiceServer>::new_todo(*self, todo)
+        }
+    }
+
     /// A [Handler](Handler) which handles requests/responses for a given service
     #[derive(Debug, Clone)]
     pub struct TodoServiceHandler<_Server>(_Server);
@@ -137,7 +219,7 @@ mod todo_service {
     ///
     /// This is the async client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
@@ -173,7 +255,7 @@ mod todo_service {
     ///
     /// This is the blocking client for the service, it produces requests from method calls
     /// (including chained method calls) and sends the requests with the given
-    /// [transport](::trait_rpc::AsyncClient) before returning the response
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
     ///
     /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
     #[derive(Debug, Copy, Clone)]
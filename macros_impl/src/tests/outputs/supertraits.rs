@@ -0,0 +1,949 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use health_service::{
+    HealthService, HealthServiceAsyncClient, HealthServiceBlockingClient, HealthServiceServer,
+};
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod health_service {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct HealthService;
+
+    impl Rpc for HealthService {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            HealthServiceAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            HealthServiceBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> HealthServiceAsyncClient<_Client> {
+            HealthServiceAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> HealthServiceBlockingClient<_Client> {
+            HealthServiceBlockingClient(transport)
+        }
+    }
+
+    impl<Server: HealthServiceServer> RpcWithServer<Server> for HealthService {
+        type Handler = HealthServiceHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            HealthServiceHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "ping")]
+        Ping(),
+        #[serde(rename = "status")]
+        Status(),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "ping",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "status",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: true,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Ping(..) => false,
+                Self::Status(..) => true,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Ping(..) => "ping",
+                Self::Status(..) => "status",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "ping")]
+        Ping(String),
+        #[serde(rename = "status")]
+        Status(Status),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Ping(..) => false,
+                Self::Status(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Ping(..) => "ping",
+                Self::Status(..) => "status",
+            }
+        }
+    }
+
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait HealthServiceServer: Send + Sync {
+        fn ping(&self) -> impl Future<Output = String> + Send;
+        fn status(
+            &self,
+            sink: impl Sink<Status, Error = Infallible> + Send + 'static,
+        ) -> impl Future<Output = ()> + Send;
+    }
+
+    impl<_Server: HealthServiceServer + ?Sized> HealthServiceServer for &_Server {
+        fn ping(&self) -> impl Future<Output = String> + Send {
+            <_Server as HealthServiceServer>::ping(*self)
+        }
+        fn status(
+            &self,
+            sink: impl Sink<Status, Error = Infallible> + Send + 'static,
+        ) -> impl Future<Output = ()> + Send {
+            <_Server as HealthServiceServer>::status(*self, sink)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct HealthServiceHandler<_Server>(_Server);
+
+    impl<_Server: HealthServiceServer> Handler for HealthServiceHandler<_Server> {
+        type Rpc = HealthService;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Ping() => Response::Ping(self.0.ping().await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::Status() => {
+                    let sink =
+                        sink.with(async |value| Result::<_, S::Error>::Ok(Response::Status(value)));
+                    self.0.status(sink).await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct HealthServiceAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> HealthServiceAsyncClient<_Client> {
+        pub async fn ping(&self) -> Result<String, _Client::Error> {
+            match self.0.send(Request::Ping()).await? {
+                Response::Ping(value) => Ok(value),
+                other => Err(WrongResponseType::new("ping", other.fn_name()).into()),
+            }
+        }
+        pub async fn status(
+            &self,
+        ) -> Result<impl Stream<Item = Result<Status, _Client::Error>>, _Client::Error>
+        where
+            _Client: StreamClient<Request, Response>,
+        {
+            let stream = self.0.send_streaming_response(Request::Status()).await?;
+            Ok(stream.map(|value| {
+                match value {
+                    Ok(Response::Status(value)) => Ok(value),
+                    Ok(other) => Err(WrongResponseType::new("status", other.fn_name()).into()),
+                    Err(error) => Err(error.into()),
+                }
+            }))
+        }
+    }
+
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct HealthServiceBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> HealthServiceBlockingClient<_Client> {
+        pub fn ping(&self) -> Result<String, _Client::Error> {
+            match self.0.send(Request::Ping())? {
+                Response::Ping(value) => Ok(value),
+                other => Err(WrongResponseType::new("ping", other.fn_name()).into()),
+            }
+        }
+    }
+}
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use admin_service::{
+    AdminService, AdminServiceAsyncClient, AdminServiceBlockingClient, AdminServiceServer,
+};
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod admin_service {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct AdminService;
+
+    impl Rpc for AdminService {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            AdminServiceAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            AdminServiceBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> AdminServiceAsyncClient<_Client> {
+            AdminServiceAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> AdminServiceBlockingClient<_Client> {
+            AdminServiceBlockingClient(transport)
+        }
+    }
+
+    impl<Server: AdminServiceServer> RpcWithServer<Server> for AdminService {
+        type Handler = AdminServiceHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            AdminServiceHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "shutdown")]
+        Shutdown(bool),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[::trait_rpc::MethodInfo {
+            name: "shutdown",
+            aliases: &[],
+            args: &["force"],
+            named: false,
+            streaming: false,
+            error: false,
+            nested: None,
+        }];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Shutdown(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Shutdown(..) => "shutdown",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "shutdown")]
+        Shutdown(()),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Shutdown(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Shutdown(..) => "shutdown",
+            }
+        }
+    }
+
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait AdminServiceServer: Send + Sync {
+        fn shutdown(&self, force: bool) -> impl Future<Output = ()> + Send;
+    }
+
+    impl<_Server: AdminServiceServer + ?Sized> AdminServiceServer for &_Server {
+        fn shutdown(&self, force: bool) -> impl Future<Output = ()> + Send {
+            <_Server as AdminServiceServer>::shutdown(*self, force)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct AdminServiceHandler<_Server>(_Server);
+
+    impl<_Server: AdminServiceServer> Handler for AdminServiceHandler<_Server> {
+        type Rpc = AdminService;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Shutdown(force) => Response::Shutdown(self.0.shutdown(force).await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct AdminServiceAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> AdminServiceAsyncClient<_Client> {
+        pub async fn shutdown(&self, force: bool) -> Result<(), _Client::Error> {
+            match self.0.send(Request::Shutdown(force)).await? {
+                Response::Shutdown(value) => Ok(value),
+                other => Err(WrongResponseType::new("shutdown", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct AdminServiceBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> AdminServiceBlockingClient<_Client> {
+        pub fn shutdown(&self, force: bool) -> Result<(), _Client::Error> {
+            match self.0.send(Request::Shutdown(force))? {
+                Response::Shutdown(value) => Ok(value),
+                other => Err(WrongResponseType::new("shutdown", other.fn_name()).into()),
+            }
+        }
+    }
+}
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use api_service::{
+    ApiService, ApiServiceAsyncClient, ApiServiceBlockingClient, ApiServiceServer,
+};
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod api_service {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// The public api, which also serves the shared services
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct ApiService;
+
+    impl Rpc for ApiService {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            ApiServiceAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            ApiServiceBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> ApiServiceAsyncClient<_Client> {
+            ApiServiceAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> ApiServiceBlockingClient<_Client> {
+            ApiServiceBlockingClient(transport)
+        }
+    }
+
+    impl<Server: ApiServiceServer> RpcWithServer<Server> for ApiService {
+        type Handler = ApiServiceHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            ApiServiceHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    #[serde(tag = "method", content = "args")]
+    pub enum Request {
+        #[serde(rename = "health_service")]
+        HealthService(<HealthService as Rpc>::Request),
+        #[serde(rename = "admin_service")]
+        AdminService(<admin::AdminService as Rpc>::Request),
+        #[serde(rename = "version")]
+        Version(),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "health_service",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(<<HealthService as Rpc>::Request as ::trait_rpc::Request>::METHODS),
+            },
+            ::trait_rpc::MethodInfo {
+                name: "admin_service",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: Some(
+                    <<admin::AdminService as Rpc>::Request as ::trait_rpc::Request>::METHODS,
+                ),
+            },
+            ::trait_rpc::MethodInfo {
+                name: "version",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = RequestRef<'req>;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::HealthService(request) => {
+                    ::trait_rpc::Request::is_streaming_response(request)
+                }
+                Self::AdminService(request) => ::trait_rpc::Request::is_streaming_response(request),
+                Self::Version(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::HealthService(..) => "health_service",
+                Self::AdminService(..) => "admin_service",
+                Self::Version(..) => "version",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "args")]
+    pub enum RequestRef<'req> {
+        #[serde(rename = "health_service")]
+        HealthService(<<HealthService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>),
+        #[serde(rename = "admin_service")]
+        AdminService(<<admin::AdminService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>),
+        #[serde(rename = "version")]
+        Version(),
+    }
+
+    impl<'req> ::trait_rpc::Request for RequestRef<'req> {
+        const METHODS: &'static [::trait_rpc::MethodInfo] =
+            <Request as ::trait_rpc::Request>::METHODS;
+        type Ref<'a> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::HealthService(request) => {
+                    ::trait_rpc::Request::is_streaming_response(request)
+                }
+                Self::AdminService(request) => ::trait_rpc::Request::is_streaming_response(request),
+                Self::Version(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::HealthService(..) => "health_service",
+                Self::AdminService(..) => "admin_service",
+                Self::Version(..) => "version",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(tag = "method", content = "result")]
+    pub enum Response {
+        #[serde(rename = "health_service")]
+        HealthService(<HealthService as Rpc>::Response),
+        #[serde(rename = "admin_service")]
+        AdminService(<admin::AdminService as Rpc>::Response),
+        #[serde(rename = "version")]
+        Version(String),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::HealthService(response) => ::trait_rpc::Response::is_error(response),
+                Self::AdminService(response) => ::trait_rpc::Response::is_error(response),
+                Self::Version(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::HealthService(..) => "health_service",
+                Self::AdminService(..) => "admin_service",
+                Self::Version(..) => "version",
+            }
+        }
+    }
+
+    /// The public api, which also serves the shared services
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait ApiServiceServer:
+        Send + Sync + HealthServiceServer + admin::AdminServiceServer
+    {
+        fn version(&self) -> impl Future<Output = String> + Send;
+    }
+
+    impl<_Server: ApiServiceServer + ?Sized> ApiServiceServer for &_Server {
+        fn version(&self) -> impl Future<Output = String> + Send {
+            <_Server as ApiServiceServer>::version(*self)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct ApiServiceHandler<_Server>(_Server);
+
+    impl<_Server: ApiServiceServer> Handler for ApiServiceHandler<_Server> {
+        type Rpc = ApiService;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::HealthService(request) => {
+                    let response = <HealthService as RpcWithServer<&_Server>>::handler(&self.0)
+                        .handle(request)
+                        .await;
+                    Response::HealthService(response)
+                }
+                Request::AdminService(request) => {
+                    let response =
+                        <admin::AdminService as RpcWithServer<&_Server>>::handler(&self.0)
+                            .handle(request)
+                            .await;
+                    Response::AdminService(response)
+                }
+                Request::Version() => Response::Version(self.0.version().await),
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                Request::HealthService(request) => {
+                    let sink = sink.with(async |value| Result::<_, S::Error>::Ok(Response::HealthService(value)));
+                    <HealthService as RpcWithServer<&_Server>>::handler(&self.0)
+                        .handle_stream_response(request, sink)
+                        .await;
+                }
+                Request::AdminService(request) => {
+                    let sink = sink.with(async |value| Result::<_, S::Error>::Ok(Response::AdminService(value)));
+                    <admin::AdminService as RpcWithServer<&_Server>>::handler(&self.0)
+                        .handle_stream_response(request, sink)
+                        .await;
+                }
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// The public api, which also serves the shared services
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct ApiServiceAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> ApiServiceAsyncClient<_Client> {
+        /// The methods inherited from [`HealthService`], these are called through this method
+        /// (eg: `client.health_service().method()`) and are sent as the nested `health_service` method
+        pub fn health_service(
+            &self,
+        ) -> <HealthService as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <HealthService as Rpc>::Request,
+                Request,
+                <HealthService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            HealthService::async_client(MappedClient::<
+                _,
+                <HealthService as Rpc>::Request,
+                Request,
+                <HealthService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::health_service_to_inner,
+                Self::health_service_to_outer,
+                Self::health_service_to_outer_ref,
+            ))
+        }
+        fn health_service_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<HealthService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::HealthService(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("health_service", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("health_service")),
+            }
+        }
+        fn health_service_to_outer((): (), inner: <HealthService as Rpc>::Request) -> Request {
+            Request::HealthService(inner)
+        }
+        fn health_service_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<HealthService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::HealthService(inner)
+        }
+        /// The methods inherited from [`AdminService`], these are called through this method
+        /// (eg: `client.admin_service().method()`) and are sent as the nested `admin_service` method
+        pub fn admin_service(
+            &self,
+        ) -> <admin::AdminService as Rpc>::AsyncClient<
+            MappedClient<
+                _Client,
+                <admin::AdminService as Rpc>::Request,
+                Request,
+                <admin::AdminService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            admin::AdminService::async_client(MappedClient::<
+                _,
+                <admin::AdminService as Rpc>::Request,
+                Request,
+                <admin::AdminService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::admin_service_to_inner,
+                Self::admin_service_to_outer,
+                Self::admin_service_to_outer_ref,
+            ))
+        }
+        fn admin_service_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<admin::AdminService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::AdminService(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("admin_service", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("admin_service")),
+            }
+        }
+        fn admin_service_to_outer((): (), inner: <admin::AdminService as Rpc>::Request) -> Request {
+            Request::AdminService(inner)
+        }
+        fn admin_service_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<admin::AdminService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::AdminService(inner)
+        }
+        pub async fn version(&self) -> Result<String, _Client::Error> {
+            match self.0.send(Request::Version()).await? {
+                Response::Version(value) => Ok(value),
+                other => Err(WrongResponseType::new("version", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// The public api, which also serves the shared services
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct ApiServiceBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> ApiServiceBlockingClient<_Client> {
+        /// The methods inherited from [`HealthService`], these are called through this method
+        /// (eg: `client.health_service().method()`) and are sent as the nested `health_service` method
+        pub fn health_service(
+            &self,
+        ) -> <HealthService as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <HealthService as Rpc>::Request,
+                Request,
+                <HealthService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            HealthService::blocking_client(MappedClient::<
+                _,
+                <HealthService as Rpc>::Request,
+                Request,
+                <HealthService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::health_service_to_inner,
+                Self::health_service_to_outer,
+                Self::health_service_to_outer_ref,
+            ))
+        }
+        fn health_service_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<HealthService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::HealthService(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("health_service", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("health_service")),
+            }
+        }
+        fn health_service_to_outer((): (), inner: <HealthService as Rpc>::Request) -> Request {
+            Request::HealthService(inner)
+        }
+        fn health_service_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<HealthService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::HealthService(inner)
+        }
+        /// The methods inherited from [`AdminService`], these are called through this method
+        /// (eg: `client.admin_service().method()`) and are sent as the nested `admin_service` method
+        pub fn admin_service(
+            &self,
+        ) -> <admin::AdminService as Rpc>::BlockingClient<
+            MappedClient<
+                _Client,
+                <admin::AdminService as Rpc>::Request,
+                Request,
+                <admin::AdminService as Rpc>::Response,
+                Response,
+                (),
+            >,
+        > {
+            admin::AdminService::blocking_client(MappedClient::<
+                _,
+                <admin::AdminService as Rpc>::Request,
+                Request,
+                <admin::AdminService as Rpc>::Response,
+                Response,
+                _,
+            >::new(
+                self.0.clone(),
+                (),
+                Self::admin_service_to_inner,
+                Self::admin_service_to_outer,
+                Self::admin_service_to_outer_ref,
+            ))
+        }
+        fn admin_service_to_inner(
+            outer: Result<Response, WrongResponseType>,
+        ) -> Result<<admin::AdminService as Rpc>::Response, WrongResponseType> {
+            match outer {
+                Ok(Response::AdminService(inner)) => Ok(inner),
+                Ok(other) => Err(WrongResponseType::new("admin_service", other.fn_name()).into()),
+                Err(err) => Err(err.in_subservice("admin_service")),
+            }
+        }
+        fn admin_service_to_outer((): (), inner: <admin::AdminService as Rpc>::Request) -> Request {
+            Request::AdminService(inner)
+        }
+        fn admin_service_to_outer_ref<'req>(
+            _: &'req (),
+            inner: <<admin::AdminService as Rpc>::Request as ::trait_rpc::Request>::Ref<'req>,
+        ) -> RequestRef<'req> {
+            RequestRef::AdminService(inner)
+        }
+        pub fn version(&self) -> Result<String, _Client::Error> {
+            match self.0.send(Request::Version())? {
+                Response::Version(value) => Ok(value),
+                other => Err(WrongResponseType::new("version", other.fn_name()).into()),
+            }
+        }
+    }
+}
@@ -0,0 +1,249 @@
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but they should be available in this module anyway"
+)]
+pub use sensor::{Sensor, SensorAsyncClient, SensorBlockingClient, SensorServer};
+
+#[allow(
+    unused_imports,
+    reason = "These might not always be used, but it's easier to include always"
+)]
+mod sensor {
+    use super::*;
+    use std::convert::Infallible;
+    use std::marker::PhantomData;
+    use ::trait_rpc::{
+        client::{AsyncClient, AsyncRefClient, BlockingClient, BlockingRefClient, MappedClient, StreamClient, StreamRefClient, WithApplicationError, WrongResponseType},
+        futures::sink::{Sink, SinkExt},
+        futures::stream::{Stream, StreamExt},
+        serde::{Deserialize, Serialize},
+        server::{Handler, IntoHandler},
+        Rpc, RpcWithServer
+    };
+
+    /// A sensor which is read by clients over a constrained link
+    ///
+    /// This is the [Rpc](::trait_rpc::Rpc) definition for this service
+    pub struct Sensor;
+
+    impl Rpc for Sensor {
+        type AsyncClient<_Client: AsyncClient<Self::Request, Self::Response>> =
+            SensorAsyncClient<_Client>;
+        type BlockingClient<_Client: BlockingClient<Self::Request, Self::Response>> =
+            SensorBlockingClient<_Client>;
+        type Request = Request;
+        type BorrowedRequest<'req> = Request;
+        type Response = Response;
+        fn async_client<_Client: AsyncClient<Request, Response>>(
+            transport: _Client,
+        ) -> SensorAsyncClient<_Client> {
+            SensorAsyncClient(transport)
+        }
+        fn blocking_client<_Client: BlockingClient<Request, Response>>(
+            transport: _Client,
+        ) -> SensorBlockingClient<_Client> {
+            SensorBlockingClient(transport)
+        }
+    }
+
+    impl<Server: SensorServer> RpcWithServer<Server> for Sensor {
+        type Handler = SensorHandler<Server>;
+        fn handler(server: Server) -> Self::Handler {
+            SensorHandler(server)
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    #[serde(remote = "Self")]
+    pub enum Request {
+        #[serde(rename = "temperature")]
+        Temperature(),
+        #[serde(rename = "set_interval")]
+        SetInterval(u16),
+    }
+
+    impl Serialize for Request {
+        fn serialize<S: ::trait_rpc::serde::Serializer>(
+            &self,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            Self::serialize(self, serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Request {
+        fn deserialize<D: ::trait_rpc::serde::Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Self, D::Error> {
+            Self::deserialize(::trait_rpc::de::RequestDeserializer::new(
+                deserializer,
+                <Self as ::trait_rpc::Request>::METHODS,
+            ))
+        }
+    }
+
+    impl ::trait_rpc::Request for Request {
+        const METHODS: &'static [::trait_rpc::MethodInfo] = &[
+            ::trait_rpc::MethodInfo {
+                name: "temperature",
+                aliases: &[],
+                args: &[],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+            ::trait_rpc::MethodInfo {
+                name: "set_interval",
+                aliases: &[],
+                args: &["seconds"],
+                named: false,
+                streaming: false,
+                error: false,
+                nested: None,
+            },
+        ];
+        type Ref<'req> = Self;
+
+        fn is_streaming_response(&self) -> bool {
+            match self {
+                Self::Temperature(..) => false,
+                Self::SetInterval(..) => false,
+            }
+        }
+        fn method_name(&self) -> &'static str {
+            match self {
+                Self::Temperature(..) => "temperature",
+                Self::SetInterval(..) => "set_interval",
+            }
+        }
+    }
+
+    #[derive(Debug, Serialize, Deserialize)]
+    #[serde(crate = "::trait_rpc::serde")]
+    pub enum Response {
+        #[serde(rename = "temperature")]
+        Temperature(f32),
+        #[serde(rename = "set_interval")]
+        SetInterval(()),
+    }
+
+    impl ::trait_rpc::Response for Response {
+        fn is_error(&self) -> bool {
+            match self {
+                Self::Temperature(..) => false,
+                Self::SetInterval(..) => false,
+            }
+        }
+    }
+
+    impl Response {
+        fn fn_name(&self) -> &'static str {
+            match self {
+                Self::Temperature(..) => "temperature",
+                Self::SetInterval(..) => "set_interval",
+            }
+        }
+    }
+
+    /// A sensor which is read by clients over a constrained link
+    ///
+    /// This is the trait which is used by the server side in order to serve the client
+    pub trait SensorServer: Send + Sync {
+        /// Read the latest temperature
+        fn temperature(&self) -> impl Future<Output = f32> + Send;
+        /// Set the interval between readings, in seconds
+        fn set_interval(&self, seconds: u16) -> impl Future<Output = ()> + Send;
+    }
+
+    impl<_Server: SensorServer + ?Sized> SensorServer for &_Server {
+        fn temperature(&self) -> impl Future<Output = f32> + Send {
+            <_Server as SensorServer>::temperature(*self)
+        }
+        fn set_interval(&self, seconds: u16) -> impl Future<Output = ()> + Send {
+            <_Server as SensorServer>::set_interval(*self, seconds)
+        }
+    }
+
+    /// A [Handler](Handler) which handles requests/responses for a given service
+    #[derive(Debug, Clone)]
+    pub struct SensorHandler<_Server>(_Server);
+
+    impl<_Server: SensorServer> Handler for SensorHandler<_Server> {
+        type Rpc = Sensor;
+        async fn handle(&self, request: Request) -> Response {
+            match request {
+                Request::Temperature() => Response::Temperature(self.0.temperature().await),
+                Request::SetInterval(seconds) => {
+                    Response::SetInterval(self.0.set_interval(seconds).await)
+                }
+                _ => panic!("This is a streaming method, must call handle_streaming"),
+            }
+        }
+        async fn handle_stream_response<S: Sink<Response, Error = Infallible> + Send + 'static>(
+            &self,
+            request: Request,
+            sink: S,
+        ) {
+            match request {
+                _ => panic!("This is not a streaming method, must call handle"),
+            }
+        }
+    }
+
+    /// A sensor which is read by clients over a constrained link
+    ///
+    /// This is the async client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::AsyncClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct SensorAsyncClient<_Client>(_Client);
+
+    #[allow(clippy::future_not_send)]
+    impl<_Client: AsyncClient<Request, Response>> SensorAsyncClient<_Client> {
+        /// Read the latest temperature
+        pub async fn temperature(&self) -> Result<f32, _Client::Error> {
+            match self.0.send(Request::Temperature()).await? {
+                Response::Temperature(value) => Ok(value),
+                other => Err(WrongResponseType::new("temperature", other.fn_name()).into()),
+            }
+        }
+        /// Set the interval between readings, in seconds
+        pub async fn set_interval(&self, seconds: u16) -> Result<(), _Client::Error> {
+            match self.0.send(Request::SetInterval(seconds)).await? {
+                Response::SetInterval(value) => Ok(value),
+                other => Err(WrongResponseType::new("set_interval", other.fn_name()).into()),
+            }
+        }
+    }
+
+    /// A sensor which is read by clients over a constrained link
+    ///
+    /// This is the blocking client for the service, it produces requests from method calls
+    /// (including chained method calls) and sends the requests with the given
+    /// [transport](::trait_rpc::client::BlockingClient) before returning the response
+    ///
+    /// The return value is always wrapped in a result: `Result<T, _Client::Error>` where `T` is the service return value
+    #[derive(Debug, Copy, Clone)]
+    pub struct SensorBlockingClient<_Client>(_Client);
+
+    impl<_Client: BlockingClient<Request, Response>> SensorBlockingClient<_Client> {
+        /// Read the latest temperature
+        pub fn temperature(&self) -> Result<f32, _Client::Error> {
+            match self.0.send(Request::Temperature())? {
+                Response::Temperature(value) => Ok(value),
+                other => Err(WrongResponseType::new("temperature", other.fn_name()).into()),
+            }
+        }
+        /// Set the interval between readings, in seconds
+        pub fn set_interval(&self, seconds: u16) -> Result<(), _Client::Error> {
+            match self.0.send(Request::SetInterval(seconds))? {
+                Response::SetInterval(value) => Ok(value),
+                other => Err(WrongResponseType::new("set_interval", other.fn_name()).into()),
+            }
+        }
+    }
+}
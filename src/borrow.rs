@@ -0,0 +1,107 @@
+//! Helpers for services declared with `#[rpc(borrow)]`, these are used by the generated code
+
+/// Serialise a byte slice as bytes rather than as a sequence, so that it can be borrowed by the server
+///
+/// It is deserialised from either bytes or a sequence, since not every format has a distinct
+/// representation for bytes
+pub mod bytes {
+    use serde::de::{SeqAccess, Visitor};
+    use serde::{Deserializer, Serializer};
+    use std::borrow::Cow;
+    use std::fmt;
+
+    /// Serialise the value as bytes
+    ///
+    /// # Errors
+    /// Returns an error if the serializer fails
+    pub fn serialize<S: Serializer>(value: &impl AsRef<[u8]>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(value.as_ref())
+    }
+
+    /// Deserialise the value from bytes, borrowing them if the deserializer allows it
+    ///
+    /// # Errors
+    /// Returns an error if the value is not bytes or a sequence of bytes
+    pub fn deserialize<'de, D: Deserializer<'de>, T: From<Cow<'de, [u8]>>>(deserializer: D) -> Result<T, D::Error> {
+        deserializer.deserialize_bytes(BytesVisitor).map(T::from)
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Cow<'de, [u8]>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("bytes")
+        }
+
+        fn visit_borrowed_bytes<E>(self, bytes: &'de [u8]) -> Result<Self::Value, E> {
+            Ok(Cow::Borrowed(bytes))
+        }
+
+        fn visit_borrowed_str<E>(self, string: &'de str) -> Result<Self::Value, E> {
+            Ok(Cow::Borrowed(string.as_bytes()))
+        }
+
+        fn visit_bytes<E>(self, bytes: &[u8]) -> Result<Self::Value, E> {
+            Ok(Cow::Owned(bytes.to_vec()))
+        }
+
+        fn visit_str<E>(self, string: &str) -> Result<Self::Value, E> {
+            Ok(Cow::Owned(string.as_bytes().to_vec()))
+        }
+
+        fn visit_byte_buf<E>(self, bytes: Vec<u8>) -> Result<Self::Value, E> {
+            Ok(Cow::Owned(bytes))
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(Cow::Owned(bytes))
+        }
+    }
+}
+
+#[cfg(all(test, feature = "json", feature = "msgpack"))]
+mod tests {
+    use crate::format::BorrowFormat;
+    use crate::format::json::Json;
+    use crate::format::msgpack::MsgPack;
+    use crate::{Rpc, rpc};
+    use std::borrow::Cow;
+
+    #[rpc(crate = crate, borrow)]
+    trait Store {
+        fn put(&self, key: &str, data: &[u8]) -> usize;
+    }
+
+    type BorrowedRequest<'req> = <Store as Rpc>::BorrowedRequest<'req>;
+
+    fn read<'de>(format: &impl BorrowFormat<'de, BorrowedRequest<'de>>, bytes: &'de [u8]) -> (Cow<'de, str>, Cow<'de, [u8]>) {
+        let store::BorrowedRequest::Put(key, data) = format.read_borrowed(bytes).unwrap();
+        (key, data)
+    }
+
+    #[test]
+    fn json_borrows_strings() {
+        let (key, data) = read(&Json, br#"{"method":"put","args":["key",[1,2]]}"#);
+        assert!(matches!(key, Cow::Borrowed("key")));
+        // JSON has no representation for bytes, so they are read from a sequence
+        assert!(matches!(data, Cow::Owned(data) if data == [1, 2]));
+        // a string with escapes cannot be borrowed
+        let (key, _) = read(&Json, br#"{"method":"put","args":["k\ney",[]]}"#);
+        assert!(matches!(key, Cow::Owned(key) if key == "k\ney"));
+    }
+
+    #[test]
+    fn msgpack_borrows_bytes() {
+        let request = <Store as Rpc>::Request::Put("key".to_string(), vec![1, 2]);
+        let bytes = rmp_serde::to_vec_named(&request).unwrap();
+        let (key, data) = read(&MsgPack, &bytes);
+        assert!(matches!(key, Cow::Borrowed("key")));
+        assert!(matches!(data, Cow::Borrowed([1, 2])));
+    }
+}
@@ -1,11 +1,19 @@
 //! Contains modules for individual client implementations
 #![allow(clippy::future_not_send, reason = "Cannot explicitly make futures `Send` while supporting WASM")]
 
+use crate::Request;
+#[cfg(any(feature = "reqwest", feature = "browser"))]
+use crate::delimited::Framing;
 use crate::format::Format;
+#[cfg(feature = "json")]
+use crate::format::jsonrpc::JsonRpcClient;
 use bon::bon;
+use bytes::Bytes;
 use futures::{Stream, StreamExt};
+use std::convert::Infallible;
+use serde::{Deserialize, Serialize};
 use std::error::Error;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display, Formatter};
 use thiserror::Error;
 
 /// Implementation for making requests from browser wasm using the Fetch API
@@ -20,6 +28,9 @@ pub mod reqwest;
 #[cfg(all(feature = "reqwest-blocking", not(target_arch = "wasm32")))]
 pub mod reqwest_blocking;
 
+/// The frame routing which is shared by the websocket clients
+#[cfg(feature = "websocket-client")]
+mod websocket_common;
 #[cfg(all(feature = "websocket-client", not(target_arch = "wasm32")))]
 pub mod tokio_websocket;
 #[cfg(all(feature = "websocket-client", not(target_arch = "wasm32")))]
@@ -49,7 +60,8 @@ impl Builder {
     #[builder(finish_fn = build)]
     pub const fn non_blocking<F, T>(
         self,
-        /// The format to be used for serialisation and deserialisation
+        /// The format to be used for serialisation and deserialisation, use
+        /// [Split](crate::format::split::Split) to receive responses in a different format to requests
         ///
         /// IMPORTANT, the format must be supported by the server
         format: F,
@@ -69,7 +81,8 @@ impl Builder {
     #[builder(finish_fn = build)]
     pub const fn blocking<F, T>(
         self,
-        /// The format to be used for serialisation and deserialisation
+        /// The format to be used for serialisation and deserialisation, use
+        /// [Split](crate::format::split::Split) to receive responses in a different format to requests
         ///
         /// IMPORTANT, the format must be supported by the server
         format: F,
@@ -84,6 +97,20 @@ impl Builder {
     {
         SimpleClient { format, transport }
     }
+
+    /// Build a client which calls methods with [JSON-RPC 2.0](crate::format::jsonrpc), using
+    /// either an asynchronous or a blocking transport
+    ///
+    /// IMPORTANT, the server must support JSON-RPC
+    #[cfg(feature = "json")]
+    #[builder(finish_fn = build)]
+    pub const fn json_rpc<T>(
+        self,
+        /// The transport mechanism to use, this must be an HTTP transport
+        transport: T
+    ) -> JsonRpcClient<T> {
+        JsonRpcClient::new(transport)
+    }
 }
 
 /// A client implementation for sending requests asynchronously
@@ -114,6 +141,28 @@ pub trait BlockingClient<Req, Resp>: Clone {
     fn send(&self, request: Req) -> Result<Resp, Self::Error>;
 }
 
+/// An [`AsyncClient`] which can also send requests that borrow their arguments from the caller,
+/// these are encoded the same as the owned requests, so the arguments are never copied
+pub trait AsyncRefClient<Req: Request, Resp>: AsyncClient<Req, Resp> {
+    /// Send a request which borrows its arguments and receive a response
+    fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> impl Future<Output = Result<Resp, Self::Error>>;
+}
+
+/// A [`StreamClient`] which can also send requests that borrow their arguments from the caller
+pub trait StreamRefClient<Req: Request, Resp>: StreamClient<Req, Resp> {
+    /// Send a request which borrows its arguments and receive a stream of responses
+    fn send_ref_streaming_response<'req>(&'req self, request: Req::Ref<'req>) -> impl Future<Output = Result<impl Stream<Item = Result<Resp, Self::Error>>, Self::Error>>;
+}
+
+/// A [`BlockingClient`] which can also send requests that borrow their arguments from the caller
+pub trait BlockingRefClient<Req: Request, Resp>: BlockingClient<Req, Resp> {
+    /// Send a request which borrows its arguments and receive a response
+    ///
+    /// # Errors
+    /// Returns an error for the same cases as [`send`](BlockingClient::send)
+    fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> Result<Resp, Self::Error>;
+}
+
 /// A simple client which has a transport and format specified
 #[derive(Debug, Copy, Clone)]
 pub struct SimpleClient<F, T> {
@@ -123,7 +172,7 @@ pub struct SimpleClient<F, T> {
 
 impl<F, T, Req, Resp> AsyncClient<Req, Resp> for SimpleClient<F, T>
 where
-    F: Format<Resp, Req>,
+    F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
     T: AsyncTransport,
     Self: Clone
 {
@@ -136,42 +185,111 @@ where
     /// * Failed to serialise/deserialise
     /// * Received the wrong type of response
     async fn send(&self, request: Req) -> Result<Resp, Self::Error> {
-        let request = self.format.write(request).map_err(RpcError::Serialize)?;
-        let response = self.transport.send(request, self.format.content_type()).await.map_err(RpcError::Transport)??;
-        let response = self.format.read(response.as_slice()).map_err(RpcError::Deserialize)?;
-        Ok(response)
+        self.send_async(request).await
+    }
+}
+
+impl<F, T, Req, Resp> AsyncRefClient<Req, Resp> for SimpleClient<F, T>
+where
+    Req: Request,
+    F: Format<Resp, Req> + for<'req> Format<Resp, Req::Ref<'req>> + Format<ResponseError, ResponseError>,
+    T: AsyncTransport,
+    Self: Clone
+{
+    async fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> Result<Resp, Self::Error> {
+        self.send_async(request).await
     }
 }
 
 impl<F, T, Req, Resp> StreamClient<Req, Resp> for SimpleClient<F, T>
 where
-    F: Format<Resp, Req>,
+    F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
     T: StreamTransport,
     Self: Clone
 {
     async fn send_streaming_response(&self, request: Req) -> Result<impl Stream<Item=Result<Resp, Self::Error>>, Self::Error> {
-        let request = self.format.write(request).map_err(RpcError::Serialize)?;
-        let stream = self.transport.stream_resp(request, self.format.content_type()).await.map_err(RpcError::Transport)?;
-        let stream = stream.map(|response| -> Result<Resp, Self::Error> {
-            let response = response.map_err(RpcError::Transport)?;
-            let response = self.format.read(response.as_slice()).map_err(RpcError::Deserialize)?;
-            Ok(response)
-        });
-        Ok(stream)
+        self.send_stream(request).await
+    }
+}
+
+impl<F, T, Req, Resp> StreamRefClient<Req, Resp> for SimpleClient<F, T>
+where
+    Req: Request,
+    F: Format<Resp, Req> + for<'req> Format<Resp, Req::Ref<'req>> + Format<ResponseError, ResponseError>,
+    T: StreamTransport,
+    Self: Clone
+{
+    async fn send_ref_streaming_response<'req>(&'req self, request: Req::Ref<'req>) -> Result<impl Stream<Item=Result<Resp, Self::Error>>, Self::Error> {
+        self.send_stream(request).await
     }
 }
 
 impl<F, T, Req, Resp> BlockingClient<Req, Resp> for SimpleClient<F, T>
 where
-    F: Format<Resp, Req>,
+    F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
     T: BlockingTransport,
     Self: Clone
 {
     type Error = RpcError<T::Error>;
     fn send(&self, request: Req) -> Result<Resp, Self::Error> {
-        let request = self.format.write(request).map_err(RpcError::Serialize)?;
-        let response = self.transport.send(request, self.format.content_type()).map_err(RpcError::Transport)??;
-        let response = self.format.read(response.as_slice()).map_err(RpcError::Deserialize)?;
+        self.send_blocking(request)
+    }
+}
+
+impl<F, T, Req, Resp> BlockingRefClient<Req, Resp> for SimpleClient<F, T>
+where
+    Req: Request,
+    F: Format<Resp, Req> + for<'req> Format<Resp, Req::Ref<'req>> + Format<ResponseError, ResponseError>,
+    T: BlockingTransport,
+    Self: Clone
+{
+    fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> Result<Resp, Self::Error> {
+        self.send_blocking(request)
+    }
+}
+
+/// The requests which are sent by a simple client may be either owned or borrowed, so these send
+/// any request which the format can write
+impl<F, T> SimpleClient<F, T> {
+    async fn send_async<Req, Resp>(&self, request: Req) -> Result<Resp, RpcError<T::Error>>
+    where
+        F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
+        T: AsyncTransport,
+    {
+        let mut buffer = vec![0; self.transport.header_len()];
+        Format::<Resp, Req>::write_to(&self.format, request, &mut buffer).map_err(RpcError::Serialize)?;
+        let response = self.transport.send(buffer, self.format.content_type(), self.format.accept()).await.map_err(RpcError::Transport)?;
+        let response = response.map_err(|error| error.decode(&self.format))?;
+        let response = Format::<Resp, Req>::read_bytes(&self.format, response).map_err(RpcError::Deserialize)?;
+        Ok(response)
+    }
+
+    async fn send_stream<Req, Resp>(&self, request: Req) -> Result<impl Stream<Item=Result<Resp, RpcError<T::Error>>>, RpcError<T::Error>>
+    where
+        F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
+        T: StreamTransport,
+    {
+        let mut buffer = vec![0; self.transport.header_len()];
+        Format::<Resp, Req>::write_to(&self.format, request, &mut buffer).map_err(RpcError::Serialize)?;
+        let stream = self.transport.stream_resp(buffer, self.format.content_type(), self.format.accept()).await.map_err(RpcError::Transport)?;
+        let stream = stream.map(|response| -> Result<Resp, RpcError<T::Error>> {
+            let response = response.map_err(RpcError::Transport)?;
+            let response = response.map_err(|error| error.decode(&self.format))?;
+            let response = Format::<Resp, Req>::read_bytes(&self.format, response).map_err(RpcError::Deserialize)?;
+            Ok(response)
+        });
+        Ok(stream)
+    }
+
+    fn send_blocking<Req, Resp>(&self, request: Req) -> Result<Resp, RpcError<T::Error>>
+    where
+        F: Format<Resp, Req> + Format<ResponseError, ResponseError>,
+        T: BlockingTransport,
+    {
+        let request = Format::<Resp, Req>::write(&self.format, request).map_err(RpcError::Serialize)?;
+        let response = self.transport.send(request, self.format.content_type(), self.format.accept()).map_err(RpcError::Transport)?;
+        let response = response.map_err(|error| error.decode(&self.format))?;
+        let response = Format::<Resp, Req>::read_bytes(&self.format, response).map_err(RpcError::Deserialize)?;
         Ok(response)
     }
 }
@@ -188,8 +306,18 @@ where
 pub trait AsyncTransport: Clone {
     /// This is the error type which is returned in the case that some part of the transport failed
     type Error: Error + 'static;
-    /// Sends the request and returns the response
-    fn send(&self, request: Vec<u8>, content_type: &str) -> impl Future<Output=Result<Result<Vec<u8>, ResponseError>, Self::Error>>;
+    /// Sends the request, which is encoded as `content_type`, and returns the response, which is
+    /// requested as `accept`
+    ///
+    /// The first [`header_len`](Self::header_len) bytes of the request are reserved for the
+    /// transport, the encoded request follows them
+    fn send(&self, request: Vec<u8>, content_type: &str, accept: &str) -> impl Future<Output=Result<Result<Bytes, RawResponseError>, Self::Error>>;
+    /// The number of bytes which the transport writes in front of each request, the client reserves
+    /// this many bytes at the start of the request so that the transport can frame the request
+    /// without copying it. By default, this is zero
+    fn header_len(&self) -> usize {
+        0
+    }
 }
 
 /// This trait describes the transport layer of a client,
@@ -208,11 +336,12 @@ pub trait AsyncTransport: Clone {
 pub trait BlockingTransport: Clone {
     /// This is the error type which is returned in the case that some part of the transport failed
     type Error: Error + 'static;
-    /// Sends the request and returns the response
+    /// Sends the request, which is encoded as `content_type`, and returns the response, which is
+    /// requested as `accept`
     ///
     /// # Errors
     /// Returns an error in the case that the communication failed for any reason
-    fn send(&self, request: Vec<u8>, content_type: &str) -> Result<Result<Vec<u8>, ResponseError>, Self::Error>;
+    fn send(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<Result<Bytes, RawResponseError>, Self::Error>;
 }
 
 /// This trait describes the transport layer of a client,
@@ -225,24 +354,35 @@ pub trait BlockingTransport: Clone {
 ///
 /// Naturally a format and protocol the is supported by the server should be chosen
 pub trait StreamTransport: AsyncTransport {
-    /// Sends the request and returns the response
-    fn stream_resp(&self, request: Vec<u8>, content_type: &str) -> impl Future<Output = Result<impl Stream<Item=Result<Vec<u8>, Self::Error>>, Self::Error>>;
+    /// Sends the request and returns a stream of responses, the stream ends once the server has
+    /// finished sending responses. As with [send](AsyncTransport::send), the request is encoded
+    /// as `content_type` after the reserved header bytes, and the responses are requested as
+    /// `accept`
+    #[allow(clippy::type_complexity, reason = "mirrors the return type of `send`, for each response in the stream")]
+    fn stream_resp(&self, request: Vec<u8>, content_type: &str, accept: &str) -> impl Future<Output = Result<impl Stream<Item=Result<Result<Bytes, RawResponseError>, Self::Error>>, Self::Error>>;
 }
 
 /// This is a transport layer used for nesting services
 #[derive(Debug)]
-pub struct MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> {
+pub struct MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
+where
+    InnerReq: Request,
+    OuterReq: Request,
+{
     outer: T,
     args: Args,
     to_inner: fn(Result<OuterResp, WrongResponseType>) -> Result<InnerResp, WrongResponseType>,
     to_outer: fn(Args, InnerReq) -> OuterReq,
+    /// Nest a request which borrows its arguments, this also borrows the arguments of the nested
+    /// service rather than copying them
+    to_outer_ref: for<'req> fn(&'req Args, InnerReq::Ref<'req>) -> OuterReq::Ref<'req>,
 }
 
-impl<T: Copy, InnerReq, OuterReq, InnerResp, OuterResp, Args: Copy> Copy
+impl<T: Copy, InnerReq: Request, OuterReq: Request, InnerResp, OuterResp, Args: Copy> Copy
 for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 {}
 
-impl<T: Clone, InnerReq, OuterReq, InnerResp, OuterResp, Args: Clone> Clone
+impl<T: Clone, InnerReq: Request, OuterReq: Request, InnerResp, OuterResp, Args: Clone> Clone
 for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 {
     fn clone(&self) -> Self {
@@ -251,11 +391,12 @@ for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
             args: self.args.clone(),
             to_inner: self.to_inner,
             to_outer: self.to_outer,
+            to_outer_ref: self.to_outer_ref,
         }
     }
 }
 
-impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
+impl<T, InnerReq: Request, OuterReq: Request, InnerResp, OuterResp, Args>
 MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 {
     #[doc(hidden)]
@@ -265,18 +406,22 @@ MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
         args: Args,
         to_inner: fn(Result<OuterResp, WrongResponseType>) -> Result<InnerResp, WrongResponseType>,
         to_outer: fn(Args, InnerReq) -> OuterReq,
+        to_outer_ref: for<'req> fn(&'req Args, InnerReq::Ref<'req>) -> OuterReq::Ref<'req>,
     ) -> Self {
         Self {
             outer: inner,
             args,
             to_inner,
             to_outer,
+            to_outer_ref,
         }
     }
 }
 impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> AsyncClient<InnerReq, InnerResp>
 for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 where
+    InnerReq: Request,
+    OuterReq: Request,
     Args: Clone,
     T: AsyncClient<OuterReq, OuterResp>,
 {
@@ -292,9 +437,30 @@ where
     }
 }
 
+impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> AsyncRefClient<InnerReq, InnerResp>
+for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
+where
+    InnerReq: Request,
+    OuterReq: Request,
+    Args: Clone,
+    T: AsyncRefClient<OuterReq, OuterResp>,
+{
+    async fn send_ref<'req>(&'req self, request: InnerReq::Ref<'req>) -> Result<InnerResp, Self::Error> {
+        let request = (self.to_outer_ref)(&self.args, request);
+        let response = match self.outer.send_ref(request).await {
+            Ok(response) => Ok(response),
+            Err(err) => Err(err.into_wrong_response()?),
+        };
+        let response = (self.to_inner)(response)?;
+        Ok(response)
+    }
+}
+
 impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> StreamClient<InnerReq, InnerResp>
 for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 where
+    InnerReq: Request,
+    OuterReq: Request,
     Args: Clone,
     T: StreamClient<OuterReq, OuterResp>,
 {
@@ -311,9 +477,32 @@ where
     }
 }
 
+impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> StreamRefClient<InnerReq, InnerResp>
+for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
+where
+    InnerReq: Request,
+    OuterReq: Request,
+    Args: Clone,
+    T: StreamRefClient<OuterReq, OuterResp>,
+{
+    async fn send_ref_streaming_response<'req>(&'req self, request: InnerReq::Ref<'req>) -> Result<impl Stream<Item=Result<InnerResp, Self::Error>>, Self::Error> {
+        let request = (self.to_outer_ref)(&self.args, request);
+        let stream = self.outer.send_ref_streaming_response(request).await?;
+        Ok(stream.map(|response| -> Result<InnerResp, Self::Error> {
+            let response = match response {
+                Ok(response) => Ok(response),
+                Err(err) => Err(err.into_wrong_response()?),
+            };
+            Ok((self.to_inner)(response)?)
+        }))
+    }
+}
+
 impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> BlockingClient<InnerReq, InnerResp>
 for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
 where
+    InnerReq: Request,
+    OuterReq: Request,
     Args: Clone,
     T: BlockingClient<OuterReq, OuterResp>,
 {
@@ -329,9 +518,31 @@ where
     }
 }
 
+impl<T, InnerReq, OuterReq, InnerResp, OuterResp, Args> BlockingRefClient<InnerReq, InnerResp>
+for MappedClient<T, InnerReq, OuterReq, InnerResp, OuterResp, Args>
+where
+    InnerReq: Request,
+    OuterReq: Request,
+    Args: Clone,
+    T: BlockingRefClient<OuterReq, OuterResp>,
+{
+    fn send_ref<'req>(&'req self, request: InnerReq::Ref<'req>) -> Result<InnerResp, Self::Error> {
+        let request = (self.to_outer_ref)(&self.args, request);
+        let response = match self.outer.send_ref(request) {
+            Ok(response) => Ok(response),
+            Err(err) => Err(err.into_wrong_response()?),
+        };
+        let response = (self.to_inner)(response)?;
+        Ok(response)
+    }
+}
+
 /// This is a error that the client may return after a request
+///
+/// `E` is the application error declared for the method with `#[rpc(error = ...)]`, methods
+/// without one can never return [`RpcError::Application`]
 #[derive(Debug, Error)]
-pub enum RpcError<T> {
+pub enum RpcError<T, E = Infallible> {
     /// The transport layer returned an error
     #[error("Failed to send request: {0}")]
     Transport(#[source] T),
@@ -350,20 +561,266 @@ pub enum RpcError<T> {
     /// This error either means the server side is misbehaving quite badly, or the transport is not configured to the correct endpoint
     #[error(transparent)]
     WrongResponseType(#[from] WrongResponseType),
+    /// The server handled the request, but the method returned an application error
+    #[error("Application error: {0}")]
+    Application(E),
+}
+
+/// A client error which can be extended with the application error of a particular method
+///
+/// This is used by the client generated by `#[rpc]` for methods which declare an error type
+pub trait WithApplicationError<E>: Sized {
+    /// The client error, extended with the application error `E`
+    type Output;
+    /// Convert a client error into the extended error
+    fn from_client(error: Self) -> Self::Output;
+    /// Convert an application error into the extended error
+    fn application(error: E) -> Self::Output;
+}
+
+impl<T, E> WithApplicationError<E> for RpcError<T> {
+    type Output = RpcError<T, E>;
+
+    fn from_client(error: Self) -> Self::Output {
+        match error {
+            Self::Transport(error) => RpcError::Transport(error),
+            Self::Response(error) => RpcError::Response(error),
+            Self::Serialize(error) => RpcError::Serialize(error),
+            Self::Deserialize(error) => RpcError::Deserialize(error),
+            Self::WrongResponseType(error) => RpcError::WrongResponseType(error),
+            Self::Application(never) => match never {},
+        }
+    }
+
+    fn application(error: E) -> Self::Output {
+        RpcError::Application(error)
+    }
 }
 
 /// Indicates that the transport was successful, but the response indicated some problem
-#[derive(Debug, Error, Clone)]
-pub enum ResponseError {
-    /// Request was improperly formatted
-    #[error("Request was rejected: {0}")]
-    BadRequest(String),
-    /// Internal Server Error
-    #[error("Internal Server Error: {0}")]
-    InternalServerError(String),
-    /// Unexpected response
-    #[error("Unexpected response")]
+///
+/// The server encodes this in the format of the request where possible, so that it can be decoded
+/// by the client
+#[derive(Debug, Error, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[error("{kind}: {message}")]
+pub struct ResponseError {
+    /// The kind of error
+    pub kind: ErrorKind,
+    /// A message describing the error
+    pub message: String,
+    /// The name of the method which was called, if it is known
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Any further details of the error
+    #[serde(default)]
+    pub details: Option<String>,
+}
+
+impl ResponseError {
+    /// Create a new error, with no method or details
+    #[must_use]
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            method: None,
+            details: None,
+        }
+    }
+
+    /// Set the name of the method which was called
+    #[must_use]
+    pub fn with_method(mut self, method: impl Into<String>) -> Self {
+        self.method = Some(method.into());
+        self
+    }
+
+    /// Set the further details of the error
+    #[must_use]
+    pub fn with_details(mut self, details: impl Into<String>) -> Self {
+        self.details = Some(details.into());
+        self
+    }
+}
+
+/// The kind of a [`ResponseError`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The HTTP method of the request is not allowed
+    WrongMethod,
+    /// The request did not specify a Content-Type
+    NoContentType,
+    /// The Content-Type of the request is not supported
+    UnsupportedContentType,
+    /// The websocket subprotocol of the request is not supported
+    UnsupportedSubprotocol,
+    /// None of the formats which the request accepts are supported
+    NotAcceptable,
+    /// The request could not be deserialised
+    Deserialise,
+    /// The request called a method which the server does not have
+    UnknownMethod,
+    /// The response could not be serialised
+    Serialise,
+    /// An internal error occurred while handling the request
+    Internal,
+    /// The request was rejected, without a more specific reason
+    Rejected,
+    /// The server failed while handling the request, without a more specific reason
+    Failed,
+    /// The response was not understood
     Unexpected,
+    /// The response does not have the Content-Type of the request, so it cannot be decoded
+    UnexpectedContentType,
+}
+
+impl ErrorKind {
+    /// The kind of error implied by an HTTP status, used when the body is not an encoded
+    /// [`ResponseError`]
+    #[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+    pub(crate) const fn from_status(status: u16) -> Self {
+        match status {
+            405 => Self::WrongMethod,
+            406 => Self::NotAcceptable,
+            415 => Self::UnsupportedContentType,
+            400..=499 => Self::Rejected,
+            500..=599 => Self::Failed,
+            _ => Self::Unexpected,
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::WrongMethod => "HTTP method not allowed",
+            Self::NoContentType => "No Content-Type provided",
+            Self::UnsupportedContentType => "Content-Type not supported",
+            Self::UnsupportedSubprotocol => "Websocket subprotocol not supported",
+            Self::NotAcceptable => "No acceptable format",
+            Self::Deserialise => "Could not parse request",
+            Self::UnknownMethod => "Unknown method",
+            Self::Serialise => "Could not serialise response",
+            Self::Internal => "Internal error",
+            Self::Rejected => "Request was rejected",
+            Self::Failed => "Internal Server Error",
+            Self::Unexpected => "Unexpected response",
+            Self::UnexpectedContentType => "Unexpected Content-Type",
+        })
+    }
+}
+
+/// An error response as received by a transport, before it is decoded into a [`ResponseError`]
+/// by the client
+#[derive(Debug, Clone)]
+pub struct RawResponseError {
+    /// The kind of error as far as the transport can tell (eg: from the HTTP status), this is used
+    /// if the body is not an encoded [`ResponseError`]
+    pub kind: ErrorKind,
+    /// The body of the error response
+    pub body: Vec<u8>,
+    /// True if the body is a [`ResponseError`] encoded in the format of the request, otherwise it
+    /// is a UTF-8 message
+    pub encoded: bool,
+}
+
+impl RawResponseError {
+    /// An error with an encoded [`ResponseError`] as its body
+    #[must_use]
+    pub const fn encoded(kind: ErrorKind, body: Vec<u8>) -> Self {
+        Self {
+            kind,
+            body,
+            encoded: true,
+        }
+    }
+
+    /// An error with a UTF-8 message as its body
+    #[must_use]
+    pub fn message(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            body: message.into().into_bytes(),
+            encoded: false,
+        }
+    }
+
+    /// An error received in response to an HTTP request, the body is encoded if the response has
+    /// the same Content-Type as the request
+    #[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+    pub(crate) fn from_http(status: u16, response_content_type: Option<&str>, request_content_type: &str, body: Vec<u8>) -> Self {
+        Self {
+            kind: ErrorKind::from_status(status),
+            body,
+            encoded: is_content_type(response_content_type, request_content_type),
+        }
+    }
+
+    /// Check that a successful HTTP response has the Content-Type of the request, since otherwise
+    /// it cannot be read with the format of the request
+    ///
+    /// # Errors
+    /// Returns an [`ErrorKind::UnexpectedContentType`] error if the Content-Type is different or missing
+    #[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+    pub(crate) fn check_content_type(response_content_type: Option<&str>, request_content_type: &str) -> Result<(), Self> {
+        if is_content_type(response_content_type, request_content_type) {
+            return Ok(());
+        }
+        Err(Self::unexpected_content_type(response_content_type, request_content_type))
+    }
+
+    /// Check that a successful streamed HTTP response can be read with the format of the request,
+    /// returning the framing of its body. A length-delimited body has the Content-Type of its
+    /// items, whereas newline-delimited JSON and event streams only name how items are delimited
+    ///
+    /// # Errors
+    /// Returns an [`ErrorKind::UnexpectedContentType`] error if the items are not in the format of
+    /// the request
+    #[cfg(any(feature = "reqwest", feature = "browser"))]
+    pub(crate) fn check_stream_content_type(response_content_type: Option<&str>, request_content_type: &str) -> Result<Framing, Self> {
+        let framing = response_content_type.map_or(Framing::LengthDelimited, Framing::for_body);
+        let readable = match framing {
+            Framing::LengthDelimited => is_content_type(response_content_type, request_content_type),
+            // newline-delimited items are always JSON
+            Framing::NewlineDelimited => Framing::for_format(request_content_type) == Framing::NewlineDelimited,
+            Framing::EventStream => true,
+        };
+        if readable {
+            Ok(framing)
+        } else {
+            Err(Self::unexpected_content_type(response_content_type, request_content_type))
+        }
+    }
+
+    fn unexpected_content_type(response_content_type: Option<&str>, request_content_type: &str) -> Self {
+        Self::message(
+            ErrorKind::UnexpectedContentType,
+            format!(
+                "expected a response with Content-Type {request_content_type}, received: {}",
+                response_content_type.unwrap_or("no Content-Type")
+            ),
+        )
+    }
+
+    /// Decode the error with the given format, falling back to the message in the body if it is
+    /// not an encoded [`ResponseError`]
+    pub fn decode(self, format: &impl Format<ResponseError, ResponseError>) -> ResponseError {
+        if self.encoded
+            && let Ok(error) = format.read(&self.body)
+        {
+            return error;
+        }
+        ResponseError::new(self.kind, String::from_utf8_lossy(&self.body))
+    }
+}
+
+/// Returns true if the Content-Type of a response is the given Content-Type, ignoring any parameters
+fn is_content_type(response_content_type: Option<&str>, content_type: &str) -> bool {
+    response_content_type.is_some_and(|response_content_type| {
+        response_content_type.split(';').next().unwrap_or(response_content_type).trim() == content_type
+    })
 }
 
 /// Response was the wrong type: sent a request for one function, but received the response of a different one
@@ -408,7 +865,7 @@ pub trait MaybeWrongResponse: Sized {
     fn into_wrong_response(self) -> Result<WrongResponseType, Self>;
 }
 
-impl<T: Error> MaybeWrongResponse for RpcError<T> {
+impl<T: Error, E> MaybeWrongResponse for RpcError<T, E> {
     fn into_wrong_response(self) -> Result<WrongResponseType, Self> {
         if let Self::WrongResponseType(err) = self {
             Ok(err)
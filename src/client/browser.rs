@@ -2,16 +2,23 @@
 //!
 //! This provides a transport implementation which uses the Browser's Fetch API to send http
 //! requests and parse the response body as JSON
+//!
+//! Streaming responses are received as [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events),
+//! read from the body of a fetch request, so the server must have SSE enabled
 
 use bon::bon;
-use crate::{AsyncTransport};
+use bytes::Bytes;
+use crate::{AsyncTransport, APPLICATION_ERROR_STATUS};
 use thiserror::Error;
+use futures::future::{self, Either};
+use futures::{stream, Stream};
 use wasm_bindgen_futures::JsFuture;
 use wasm_bindgen_futures::wasm_bindgen::JsCast;
 use web_sys::wasm_bindgen::JsValue;
-use web_sys::{Request, RequestInit, RequestMode, Response, Window};
+use web_sys::{ReadableStreamDefaultReader, ReadableStreamReadResult, Request, RequestInit, RequestMode, Response, Window};
 use web_sys::js_sys::{Uint8Array};
-use crate::client::ResponseError;
+use crate::client::{ErrorKind, RawResponseError, StreamTransport};
+use crate::delimited::{Decoded, Decoder, EVENT_STREAM_CONTENT_TYPE};
 
 /// A client which uses the browsers Fetch API along with JSON format (via serde),
 /// only supported on wasm32 architecture
@@ -50,10 +57,8 @@ impl Browser {
     }
 }
 
-impl AsyncTransport for Browser {
-    type Error = Error;
-
-    async fn send(&self, request: Vec<u8>, content_type: &str) -> Result<Result<Vec<u8>, ResponseError>, Self::Error> {
+impl Browser {
+    async fn fetch(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<Response, Error> {
         let opts = self.request_options.clone();
         let body = Uint8Array::from(request.as_slice());
         opts.set_body(&body);
@@ -64,20 +69,113 @@ impl AsyncTransport for Browser {
             .headers()
             .set("Content-Type", content_type)
             .map_err(Error::SetHeader)?;
+        request
+            .headers()
+            .set("Accept", accept)
+            .map_err(Error::SetHeader)?;
 
         let promise = self.window.fetch_with_request(&request);
         let future = JsFuture::from(promise);
         let response = future.await.map_err(Error::Fetch)?;
-        let response: Response = response.dyn_into().map_err(Error::CastResponse)?;
-        let body = response.array_buffer().map_err(Error::ReadBody)?;
-        let body = JsFuture::from(body).await.map_err(Error::ReadBody)?;
-        let body = Uint8Array::new(&body).to_vec();
+        response.dyn_into().map_err(Error::CastResponse)
+    }
+}
+
+impl AsyncTransport for Browser {
+    type Error = Error;
+
+    async fn send(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<Result<Bytes, RawResponseError>, Self::Error> {
+        let response = self.fetch(request, content_type, accept).await?;
+        let body = read_body(&response).await?;
         match response.status() {
-            200..=299 => Ok(Ok(body)),
-            400..=499 => Ok(Err(ResponseError::BadRequest(String::from_utf8(body).unwrap()))),
-            500..=599 => Ok(Err(ResponseError::InternalServerError(String::from_utf8(body).unwrap()))),
-            _ => Ok(Err(ResponseError::Unexpected))
+            // an application error is still an encoded response
+            200..=299 | APPLICATION_ERROR_STATUS => Ok(Ok(body.into())),
+            _ => Ok(Err(response_error(&response, accept, body))),
+        }
+    }
+}
+
+impl StreamTransport for Browser {
+    async fn stream_resp(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<impl Stream<Item=Result<Result<Bytes, RawResponseError>, Self::Error>>, Self::Error> {
+        // the items of the event stream are in the requested format
+        let response = self.fetch(request, content_type, &format!("{EVENT_STREAM_CONTENT_TYPE}, {accept}")).await?;
+        if !response.ok() {
+            let error = response_error(&response, accept, read_body(&response).await?);
+            return Ok(Either::Left(stream::once(future::ready(Ok(Err(error))))));
         }
+        let content_type = response.headers().get("Content-Type").ok().flatten();
+        let framing = match RawResponseError::check_stream_content_type(content_type.as_deref(), accept) {
+            Ok(framing) => framing,
+            Err(error) => return Ok(Either::Left(stream::once(future::ready(Ok(Err(error)))))),
+        };
+        let reader = response
+            .body()
+            .ok_or_else(|| Error::ReadBody(JsValue::from_str("response has no body")))?
+            .get_reader()
+            .dyn_into()
+            .map_err(|reader| Error::ReadBody(reader.into()))?;
+        let state = Some((BodyReader(reader), Decoder::new(framing)));
+        Ok(Either::Right(stream::unfold(state, |state| async move {
+            let (reader, mut decoder) = state?;
+            loop {
+                match decoder.next_part() {
+                    Some(Decoded::Item(item)) => return Some((Ok(Ok(item)), Some((reader, decoder)))),
+                    Some(Decoded::End) => return None,
+                    Some(Decoded::Error(error)) => {
+                        return Some((Ok(Err(RawResponseError::encoded(ErrorKind::Failed, error.into_bytes()))), None));
+                    }
+                    Some(Decoded::Invalid(error)) => {
+                        return Some((Ok(Err(RawResponseError::message(ErrorKind::Unexpected, error))), None));
+                    }
+                    None => {}
+                }
+                match reader.read().await {
+                    Ok(Some(chunk)) => decoder.push(&chunk),
+                    Err(error) => return Some((Err(error), None)),
+                    // the body ended part way through a response
+                    Ok(None) if decoder.has_remainder() => {
+                        let error = RawResponseError::message(ErrorKind::Unexpected, "response body ended part way through a response");
+                        return Some((Ok(Err(error)), None));
+                    }
+                    Ok(None) => return None,
+                }
+            }
+        })))
+    }
+}
+
+async fn read_body(response: &Response) -> Result<Vec<u8>, Error> {
+    let body = response.array_buffer().map_err(Error::ReadBody)?;
+    let body = JsFuture::from(body).await.map_err(Error::ReadBody)?;
+    Ok(Uint8Array::new(&body).to_vec())
+}
+
+fn response_error(response: &Response, accept: &str, body: Vec<u8>) -> RawResponseError {
+    let response_content_type = response.headers().get("Content-Type").ok().flatten();
+    RawResponseError::from_http(response.status(), response_content_type.as_deref(), accept, body)
+}
+
+/// Reads the chunks of a streamed body, the body is cancelled when this is dropped, so that the
+/// server stops sending responses which are no longer wanted
+struct BodyReader(ReadableStreamDefaultReader);
+
+impl BodyReader {
+    /// Read the next chunk of the body, returns `None` once the body has ended
+    async fn read(&self) -> Result<Option<Vec<u8>>, Error> {
+        let result: ReadableStreamReadResult = JsFuture::from(self.0.read())
+            .await
+            .map_err(Error::ReadBody)?
+            .unchecked_into();
+        if result.get_done().unwrap_or_default() {
+            return Ok(None);
+        }
+        Ok(Some(Uint8Array::new(&result.get_value()).to_vec()))
+    }
+}
+
+impl Drop for BodyReader {
+    fn drop(&mut self) {
+        let _ = self.0.cancel();
     }
 }
 
@@ -1,11 +1,17 @@
 //! This module defines the reqwest transport support
 
 use bon::bon;
-use crate::AsyncTransport;
+use bytes::Bytes;
+use futures::future::{self, Either};
+use futures::{stream, Stream, StreamExt};
+use crate::{AsyncTransport, APPLICATION_ERROR_STATUS};
 pub use reqwest::Error;
-use crate::client::ResponseError;
+use crate::client::{ErrorKind, RawResponseError, StreamTransport};
+use crate::delimited::{Decoded, Decoder};
 
 /// An [`AsyncTransport`] which uses the [reqwest] crate
+///
+/// Streaming responses are read from the body incrementally, so they work over plain HTTP
 #[derive(Debug, Clone)]
 pub struct Reqwest {
     client: reqwest::Client,
@@ -34,25 +40,88 @@ impl Reqwest {
     }
 }
 
-impl AsyncTransport for Reqwest {
-    type Error = Error;
-
-    async fn send(&self, request: Vec<u8>, content_type: &str) -> Result<Result<Vec<u8>, ResponseError>, Self::Error> {
-        let response = self
-            .client
+impl Reqwest {
+    fn request(&self, request: Vec<u8>, content_type: &str, accept: &str) -> reqwest::RequestBuilder {
+        self.client
             .request(self.method.clone(), &self.url)
             .body(request)
             .header(reqwest::header::CONTENT_TYPE, content_type)
-            .send()
-            .await?;
-        if response.status().is_success() {
-            Ok(Ok(response.json().await?))
-        } else if response.status().is_client_error() {
-            Ok(Err(ResponseError::BadRequest(response.text().await?)))
-        } else if response.status().is_server_error() {
-            Ok(Err(ResponseError::InternalServerError(response.text().await?)))
+            .header(reqwest::header::ACCEPT, accept)
+    }
+}
+
+impl AsyncTransport for Reqwest {
+    type Error = Error;
+
+    async fn send(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<Result<Bytes, RawResponseError>, Self::Error> {
+        let response = self.request(request, content_type, accept).send().await?;
+        // an application error is still an encoded response
+        if response.status().is_success() || response.status().as_u16() == APPLICATION_ERROR_STATUS {
+            if let Err(error) = RawResponseError::check_content_type(header_content_type(&response).as_deref(), accept) {
+                return Ok(Err(error));
+            }
+            Ok(Ok(response.bytes().await?))
         } else {
-            Ok(Err(ResponseError::Unexpected))
+            Ok(Err(response_error(response, accept).await?))
         }
     }
-}
\ No newline at end of file
+}
+
+impl StreamTransport for Reqwest {
+    async fn stream_resp(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<impl Stream<Item=Result<Result<Bytes, RawResponseError>, Self::Error>>, Self::Error> {
+        let response = self.request(request, content_type, accept).send().await?;
+        if !response.status().is_success() {
+            let error = response_error(response, accept).await?;
+            return Ok(Either::Left(stream::once(future::ready(Ok(Err(error))))));
+        }
+        let framing = match RawResponseError::check_stream_content_type(header_content_type(&response).as_deref(), accept) {
+            Ok(framing) => framing,
+            Err(error) => return Ok(Either::Left(stream::once(future::ready(Ok(Err(error)))))),
+        };
+        let state = Some((Box::pin(response.bytes_stream()), Decoder::new(framing)));
+        Ok(Either::Right(stream::unfold(state, |state| async move {
+            let (mut chunks, mut decoder) = state?;
+            loop {
+                match decoder.next_part() {
+                    Some(Decoded::Item(item)) => return Some((Ok(Ok(item)), Some((chunks, decoder)))),
+                    Some(Decoded::End) => return None,
+                    Some(Decoded::Error(error)) => {
+                        return Some((Ok(Err(RawResponseError::encoded(ErrorKind::Failed, error.into_bytes()))), None));
+                    }
+                    Some(Decoded::Invalid(error)) => {
+                        return Some((Ok(Err(RawResponseError::message(ErrorKind::Unexpected, error))), None));
+                    }
+                    None => {}
+                }
+                match chunks.next().await {
+                    Some(Ok(chunk)) => decoder.push(&chunk),
+                    Some(Err(error)) => return Some((Err(error), None)),
+                    // the body ended part way through a response
+                    None if decoder.has_remainder() => {
+                        let error = RawResponseError::message(ErrorKind::Unexpected, "response body ended part way through a response");
+                        return Some((Ok(Err(error)), None));
+                    }
+                    None => return None,
+                }
+            }
+        })))
+    }
+}
+
+/// Read the error from a response with an unsuccessful status, the error is encoded if it has the
+/// requested Content-Type
+async fn response_error(response: reqwest::Response, accept: &str) -> Result<RawResponseError, Error> {
+    let status = response.status().as_u16();
+    let response_content_type = header_content_type(&response);
+    let body = response.bytes().await?.to_vec();
+    Ok(RawResponseError::from_http(status, response_content_type.as_deref(), accept, body))
+}
+
+/// The Content-Type of a response, if it has one
+fn header_content_type(response: &reqwest::Response) -> Option<String> {
+    response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .map(ToString::to_string)
+}
@@ -1,9 +1,10 @@
 use bon::bon;
-use crate::BlockingTransport;
+use bytes::Bytes;
+use crate::{BlockingTransport, APPLICATION_ERROR_STATUS};
 pub use reqwest::Error;
 use reqwest::blocking::Client;
 use reqwest::{Method};
-use crate::client::ResponseError;
+use crate::client::RawResponseError;
 
 /// A [`AsyncTransport`] which uses the [reqwest] crate
 #[derive(Debug, Clone)]
@@ -36,21 +37,29 @@ impl ReqwestBlocking {
 impl BlockingTransport for ReqwestBlocking {
     type Error = Error;
 
-    fn send(&self, request: Vec<u8>, content_type: &str) -> Result<Result<Vec<u8>, ResponseError>, Self::Error> {
+    fn send(&self, request: Vec<u8>, content_type: &str, accept: &str) -> Result<Result<Bytes, RawResponseError>, Self::Error> {
         let response = self
             .client
             .request(self.method.clone(), &self.url)
             .body(request)
             .header(reqwest::header::CONTENT_TYPE, content_type)
+            .header(reqwest::header::ACCEPT, accept)
             .send()?;
-        if response.status().is_success() {
-            Ok(Ok(response.json()?))
-        } else if response.status().is_client_error() {
-            Ok(Err(ResponseError::BadRequest(response.text()?)))
-        } else if response.status().is_server_error() {
-            Ok(Err(ResponseError::InternalServerError(response.text()?)))
+        // an application error is still an encoded response
+        let response_content_type = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .map(ToString::to_string);
+        if response.status().is_success() || response.status().as_u16() == APPLICATION_ERROR_STATUS {
+            if let Err(error) = RawResponseError::check_content_type(response_content_type.as_deref(), accept) {
+                return Ok(Err(error));
+            }
+            Ok(Ok(response.bytes()?))
         } else {
-            Ok(Err(ResponseError::Unexpected))
+            let status = response.status().as_u16();
+            let body = response.bytes()?.to_vec();
+            Ok(Err(RawResponseError::from_http(status, response_content_type.as_deref(), accept, body)))
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,245 @@
+//! The parts of the websocket clients which do not depend on the socket, each client only runs a
+//! worker which moves frames between the socket and the [`Routes`] of its requests
+
+use crate::client::{ErrorKind, RawResponseError};
+use crate::{get_header, new_frame, write_header, FrameKind};
+use bytes::Bytes;
+use futures::channel::{mpsc, oneshot};
+use futures::lock::Mutex;
+use futures::{SinkExt, Stream, StreamExt};
+use std::collections::HashMap;
+use std::mem;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use thiserror::Error;
+use tracing::warn;
+
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+type SenderMap = Arc<Mutex<HashMap<u32, oneshot::Sender<Reply>>>>;
+type StreamSenderMap = Arc<Mutex<HashMap<u32, mpsc::UnboundedSender<Reply>>>>;
+pub type Reply = Result<Result<Bytes, RawResponseError>, WebsocketError>;
+
+/// The client side of a websocket connection, this sends requests to the worker and waits for the
+/// worker to route their responses back
+#[derive(Clone)]
+pub struct Connection {
+    sender: Arc<Mutex<mpsc::Sender<Vec<u8>>>>,
+    routes: Routes,
+    cancel: mpsc::UnboundedSender<u32>,
+    content_type: &'static str,
+}
+
+/// The worker side of a websocket connection
+pub struct Worker {
+    /// The frames of new requests, which are sent to the server
+    pub requests: mpsc::Receiver<Vec<u8>>,
+    /// The ids of cancelled requests, the server is told about these with [`Routes::cancel`]
+    pub cancelled: mpsc::UnboundedReceiver<u32>,
+    pub routes: Routes,
+}
+
+/// Open a connection which sends requests in the given format
+pub fn connection(content_type: &'static str) -> (Connection, Worker) {
+    let (sender, requests) = mpsc::channel(100);
+    let (cancel, cancelled) = mpsc::unbounded();
+    let routes = Routes::default();
+    let connection = Connection {
+        sender: Arc::new(Mutex::new(sender)),
+        routes: routes.clone(),
+        cancel,
+        content_type,
+    };
+    (connection, Worker { requests, cancelled, routes })
+}
+
+impl Connection {
+    /// Check that the client is using the format of the connection, for both requests and responses
+    fn check_content_type(&self, content_type: &str, accept: &str) -> Result<(), WebsocketError> {
+        for content_type in [content_type, accept] {
+            if self.content_type != content_type {
+                return Err(WebsocketError::IncorrectContentType {
+                    expected: self.content_type,
+                    received: content_type.to_string(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Send a request and wait for its response
+    pub async fn send(&self, mut request: Vec<u8>, content_type: &str, accept: &str) -> Reply {
+        self.check_content_type(content_type, accept)?;
+        let (sender, receiver) = oneshot::channel();
+        let request_id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        write_header(&mut request, request_id, FrameKind::Message);
+        let mut guard = CancelOnDrop::new(request_id, &self.cancel);
+        self.routes.senders.lock().await.insert(request_id, sender);
+        self.send_frame(request).await?;
+        let reply = receiver
+            .await
+            .map_err(|_| WebsocketError::ResponseChannelClosed)?;
+        guard.finish();
+        reply
+    }
+
+    /// Send a request and return the stream of its responses
+    pub async fn stream_resp(&self, mut request: Vec<u8>, content_type: &str, accept: &str) -> Result<ResponseStream, WebsocketError> {
+        self.check_content_type(content_type, accept)?;
+        let (sender, receiver) = mpsc::unbounded();
+        let request_id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        write_header(&mut request, request_id, FrameKind::Message);
+        let guard = CancelOnDrop::new(request_id, &self.cancel);
+        self.routes.stream_senders.lock().await.insert(request_id, sender);
+        self.send_frame(request).await?;
+        Ok(ResponseStream { receiver, guard })
+    }
+
+    async fn send_frame(&self, frame: Vec<u8>) -> Result<(), WebsocketError> {
+        self.sender
+            .lock()
+            .await
+            .send(frame)
+            .await
+            .map_err(|_| WebsocketError::RequestChannelClosed)
+    }
+}
+
+/// The requests which are waiting for responses from the server
+#[derive(Clone, Default)]
+pub struct Routes {
+    senders: SenderMap,
+    stream_senders: StreamSenderMap,
+}
+
+impl Routes {
+    /// Route a frame received from the server to the request that it belongs to
+    pub async fn dispatch(&self, frame: &Bytes) {
+        let Some((request_id, kind, response)) = get_header(frame) else {
+            warn!("Received malformed frame from server");
+            return;
+        };
+        let reply = match kind {
+            FrameKind::Message => Ok(frame.slice_ref(response)),
+            FrameKind::End => {
+                // dropping the sender ends the stream
+                self.stream_senders.lock().await.remove(&request_id);
+                return;
+            }
+            FrameKind::Rejected => Err(RawResponseError::encoded(ErrorKind::Rejected, response.to_vec())),
+            FrameKind::Failed => Err(RawResponseError::encoded(ErrorKind::Failed, response.to_vec())),
+            FrameKind::Cancel => {
+                warn!("Received unexpected cancel frame from server");
+                return;
+            }
+        };
+        let sender = self.senders.lock().await.remove(&request_id);
+        if let Some(sender) = sender {
+            let _: Result<(), _> = sender.send(Ok(reply));
+        } else if reply.is_err() {
+            // an error always ends the stream
+            let sender = self.stream_senders.lock().await.remove(&request_id);
+            if let Some(sender) = sender {
+                let _: Result<(), _> = sender.unbounded_send(Ok(reply));
+            }
+        } else {
+            let stream_senders = self.stream_senders.lock().await;
+            if let Some(sender) = stream_senders.get(&request_id) {
+                let _: Result<(), _> = sender.unbounded_send(Ok(reply));
+            } else {
+                warn!("No request found for response: {request_id}");
+            }
+        }
+    }
+
+    /// Forget a request which has been cancelled by the client, returning the frame which tells the
+    /// server to cancel it
+    pub async fn cancel(&self, request_id: u32) -> Vec<u8> {
+        self.senders.lock().await.remove(&request_id);
+        self.stream_senders.lock().await.remove(&request_id);
+        new_frame(request_id, FrameKind::Cancel)
+    }
+
+    /// Fail every request which is still waiting, once the connection has closed
+    pub async fn close(&self) {
+        let senders = mem::take(&mut *self.senders.lock().await);
+        for (_, sender) in senders {
+            let _ = sender.send(Err(WebsocketError::ConnectionClosed));
+        }
+        let stream_senders = mem::take(&mut *self.stream_senders.lock().await);
+        for (_, sender) in stream_senders {
+            let _ = sender.unbounded_send(Err(WebsocketError::ConnectionClosed));
+        }
+    }
+}
+
+/// Cancels a request when dropped, unless the request has already finished
+struct CancelOnDrop {
+    request_id: u32,
+    cancel: Option<mpsc::UnboundedSender<u32>>,
+}
+
+impl CancelOnDrop {
+    fn new(request_id: u32, cancel: &mpsc::UnboundedSender<u32>) -> Self {
+        Self {
+            request_id,
+            cancel: Some(cancel.clone()),
+        }
+    }
+
+    /// Mark the request as finished, so that there is nothing left to cancel
+    fn finish(&mut self) {
+        self.cancel = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(cancel) = self.cancel.take() {
+            let _: Result<(), _> = cancel.unbounded_send(self.request_id);
+        }
+    }
+}
+
+/// The stream of responses to a streaming request, the request is cancelled if this is dropped
+/// before the server has finished sending responses
+pub struct ResponseStream {
+    receiver: mpsc::UnboundedReceiver<Reply>,
+    guard: CancelOnDrop,
+}
+
+impl Stream for ResponseStream {
+    type Item = Reply;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let next = self.receiver.poll_next_unpin(cx);
+        if matches!(next, Poll::Ready(None)) {
+            self.guard.finish();
+        }
+        next
+    }
+}
+
+/// An error from the websocket client
+#[derive(Debug, Error)]
+pub enum WebsocketError {
+    /// The websocket worker has closed the request channel, this is not expected
+    #[error("Failed to send request to worker: channel closed")]
+    RequestChannelClosed,
+    /// The websocket worker has closed the response channel, this is not expected
+    #[error("Failed to read response from worker: channel closed")]
+    ResponseChannelClosed,
+    /// The client is not using the same content type as the websocket transport
+    #[error("The client is not using the same content type as the websocket transport, expected: {expected}, received: {received}")]
+    IncorrectContentType {
+        /// The content type defined in the websocket transport
+        expected: &'static str,
+        /// The content type defined in the client
+        received: String,
+    },
+    /// The websocket connection has closed
+    #[error("Websocket connection closed")]
+    ConnectionClosed,
+}
@@ -0,0 +1,446 @@
+//! Reads requests so that a method which the service does not have can be told apart from any
+//! other invalid request, this is used by the generated code
+//!
+//! The derived deserialisation of a request is given a [`RequestDeserializer`], which checks the
+//! tag of the request against the [`METHODS`](crate::Request::METHODS) of its service before the
+//! derived code reads it. The tag is the name of the method, or its index for services declared
+//! with `#[rpc(variant_index)]`
+
+use crate::MethodInfo;
+use serde::de::{DeserializeSeed, Deserializer, EnumAccess, Error, MapAccess, SeqAccess, Visitor};
+use std::cell::Cell;
+use std::fmt;
+
+thread_local! {
+    /// Set when the request being read called a method which its service does not have
+    static UNKNOWN_METHOD: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Read a request, also returning true if it could not be read because it called a method which
+/// the service does not have
+#[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+pub(crate) fn catch_unknown_method<T>(read: impl FnOnce() -> T) -> (T, bool) {
+    UNKNOWN_METHOD.set(false);
+    let value = read();
+    (value, UNKNOWN_METHOD.replace(false))
+}
+
+/// Returns true if `variants` are the variants of a request with the given methods, either their
+/// names or their aliases
+fn is_request(variants: &[&str], methods: &[MethodInfo]) -> bool {
+    variants.iter().all(|variant| is_method(variant, methods))
+        && methods.iter().all(|method| variants.contains(&method.name))
+}
+
+fn is_method(name: &str, methods: &[MethodInfo]) -> bool {
+    methods
+        .iter()
+        .any(|method| method.name == name || method.aliases.contains(&name))
+}
+
+fn unknown_method<E: Error>(method: impl fmt::Display) -> E {
+    UNKNOWN_METHOD.set(true);
+    E::custom(format_args!("unknown method {method}"))
+}
+
+/// A [Deserializer] which checks the tag of a request before it is read, every other value is read
+/// by the wrapped deserializer
+pub struct RequestDeserializer<D> {
+    deserializer: D,
+    methods: &'static [MethodInfo],
+    /// True if this is the value of the tag, rather than the whole request
+    tag: bool,
+}
+
+impl<D> RequestDeserializer<D> {
+    /// Read a request for a service with the given methods from `deserializer`
+    pub const fn new(deserializer: D, methods: &'static [MethodInfo]) -> Self {
+        Self {
+            deserializer,
+            methods,
+            tag: false,
+        }
+    }
+}
+
+macro_rules! forward {
+    ($($method:ident($($arg:ident: $ty:ty),*)),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, Self::Error> {
+                self.deserializer.$method($($arg,)* visitor)
+            }
+        )*
+    };
+}
+
+impl<'de, D: Deserializer<'de>> Deserializer<'de> for RequestDeserializer<D> {
+    type Error = D::Error;
+
+    forward! {
+        deserialize_any(), deserialize_bool(),
+        deserialize_i8(), deserialize_i16(), deserialize_i32(), deserialize_i64(), deserialize_i128(),
+        deserialize_u8(), deserialize_u16(), deserialize_u32(), deserialize_u64(), deserialize_u128(),
+        deserialize_f32(), deserialize_f64(), deserialize_char(),
+        deserialize_str(), deserialize_string(), deserialize_bytes(), deserialize_byte_buf(),
+        deserialize_option(), deserialize_unit(), deserialize_unit_struct(name: &'static str),
+        deserialize_newtype_struct(name: &'static str), deserialize_seq(), deserialize_tuple(len: usize),
+        deserialize_tuple_struct(name: &'static str, len: usize), deserialize_map(),
+        deserialize_ignored_any(),
+    }
+
+    /// An adjacently tagged request, the tag is the first field
+    fn deserialize_struct<V: Visitor<'de>>(self, name: &'static str, fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        if self.tag {
+            return self.deserializer.deserialize_struct(name, fields, visitor);
+        }
+        let visitor = TaggedVisitor {
+            visitor,
+            methods: self.methods,
+        };
+        self.deserializer.deserialize_struct(name, fields, visitor)
+    }
+
+    /// Either an externally tagged request, or the tag of an adjacently tagged request, which are
+    /// both read as the variant of an enum
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        if !is_request(variants, self.methods) {
+            return self.deserializer.deserialize_enum(name, variants, visitor);
+        }
+        let visitor = TagVisitor {
+            visitor,
+            methods: self.methods,
+        };
+        self.deserializer.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_identifier<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if !self.tag {
+            return self.deserializer.deserialize_identifier(visitor);
+        }
+        let visitor = TagVisitor {
+            visitor,
+            methods: self.methods,
+        };
+        self.deserializer.deserialize_identifier(visitor)
+    }
+
+    fn is_human_readable(&self) -> bool {
+        self.deserializer.is_human_readable()
+    }
+}
+
+/// Reads the values of an adjacently tagged request with a [`RequestDeserializer`], so that its tag
+/// is checked
+struct TaggedVisitor<V> {
+    visitor: V,
+    methods: &'static [MethodInfo],
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TaggedVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_map(Tagged {
+            access: map,
+            methods: self.methods,
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_seq(Tagged {
+            access: seq,
+            methods: self.methods,
+        })
+    }
+}
+
+/// The fields of an adjacently tagged request, each value is read with a [`RequestDeserializer`]
+/// for the tag, which only checks a value which is read as the tag
+struct Tagged<A> {
+    access: A,
+    methods: &'static [MethodInfo],
+}
+
+impl<'de, A: MapAccess<'de>> MapAccess<'de> for Tagged<A> {
+    type Error = A::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Self::Error> {
+        self.access.next_key_seed(seed)
+    }
+
+    fn next_value_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value, Self::Error> {
+        self.access.next_value_seed(TagSeed {
+            seed,
+            methods: self.methods,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.access.size_hint()
+    }
+}
+
+impl<'de, A: SeqAccess<'de>> SeqAccess<'de> for Tagged<A> {
+    type Error = A::Error;
+
+    fn next_element_seed<S: DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>, Self::Error> {
+        self.access.next_element_seed(TagSeed {
+            seed,
+            methods: self.methods,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.access.size_hint()
+    }
+}
+
+/// Reads a value which may be the tag of a request
+struct TagSeed<S> {
+    seed: S,
+    methods: &'static [MethodInfo],
+}
+
+impl<'de, S: DeserializeSeed<'de>> DeserializeSeed<'de> for TagSeed<S> {
+    type Value = S::Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        self.seed.deserialize(RequestDeserializer {
+            deserializer,
+            methods: self.methods,
+            tag: true,
+        })
+    }
+}
+
+/// Checks the tag of a request, either as the variant of an enum or as an identifier
+struct TagVisitor<V> {
+    visitor: V,
+    methods: &'static [MethodInfo],
+}
+
+impl<'de, V: Visitor<'de>> Visitor<'de> for TagVisitor<V> {
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.visitor.expecting(formatter)
+    }
+
+    fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<Self::Value, A::Error> {
+        self.visitor.visit_enum(TagAccess {
+            access: data,
+            methods: self.methods,
+        })
+    }
+
+    fn visit_u64<E: Error>(self, index: u64) -> Result<Self::Value, E> {
+        if !usize::try_from(index).is_ok_and(|index| index < self.methods.len()) {
+            return Err(unknown_method(format_args!("index {index}")));
+        }
+        self.visitor.visit_u64(index)
+    }
+
+    fn visit_str<E: Error>(self, name: &str) -> Result<Self::Value, E> {
+        self.check(name)?;
+        self.visitor.visit_str(name)
+    }
+
+    fn visit_borrowed_str<E: Error>(self, name: &'de str) -> Result<Self::Value, E> {
+        self.check(name)?;
+        self.visitor.visit_borrowed_str(name)
+    }
+
+    fn visit_string<E: Error>(self, name: String) -> Result<Self::Value, E> {
+        self.check(&name)?;
+        self.visitor.visit_string(name)
+    }
+
+    fn visit_bytes<E: Error>(self, name: &[u8]) -> Result<Self::Value, E> {
+        self.check_bytes(name)?;
+        self.visitor.visit_bytes(name)
+    }
+
+    fn visit_borrowed_bytes<E: Error>(self, name: &'de [u8]) -> Result<Self::Value, E> {
+        self.check_bytes(name)?;
+        self.visitor.visit_borrowed_bytes(name)
+    }
+
+    fn visit_byte_buf<E: Error>(self, name: Vec<u8>) -> Result<Self::Value, E> {
+        self.check_bytes(&name)?;
+        self.visitor.visit_byte_buf(name)
+    }
+}
+
+impl<V> TagVisitor<V> {
+    fn check<E: Error>(&self, name: &str) -> Result<(), E> {
+        if is_method(name, self.methods) {
+            Ok(())
+        } else {
+            Err(unknown_method(format_args!("`{name}`")))
+        }
+    }
+
+    /// A name which is not UTF-8 is left to the derived code to reject
+    fn check_bytes<E: Error>(&self, name: &[u8]) -> Result<(), E> {
+        std::str::from_utf8(name).map_or(Ok(()), |name| self.check(name))
+    }
+}
+
+/// The variant of an enum which is a request, or the tag of a request, its identifier is checked
+struct TagAccess<A> {
+    access: A,
+    methods: &'static [MethodInfo],
+}
+
+impl<'de, A: EnumAccess<'de>> EnumAccess<'de> for TagAccess<A> {
+    type Error = A::Error;
+    type Variant = A::Variant;
+
+    fn variant_seed<S: DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self::Variant), Self::Error> {
+        self.access.variant_seed(TagSeed {
+            seed,
+            methods: self.methods,
+        })
+    }
+}
+
+#[cfg(all(test, feature = "json", feature = "cbor", feature = "msgpack", feature = "postcard"))]
+mod tests {
+    use super::*;
+    use crate::format::cbor::Cbor;
+    use crate::format::json::Json;
+    use crate::format::msgpack::MsgPack;
+    use crate::format::postcard::Postcard;
+    use crate::format::Format;
+    use crate::rpc;
+    use serde::{Deserialize, Serialize};
+    use serde::de::DeserializeOwned;
+    use std::fmt::Debug;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+    enum Status {
+        Open,
+        Done,
+    }
+
+    /// The status as it is known to newer clients
+    #[derive(Serialize, Deserialize, Debug, PartialEq, Eq)]
+    enum NewStatus {
+        Open,
+        Done,
+        Archived,
+    }
+
+    /// The service as the server knows it
+    #[rpc(crate = crate, derive(PartialEq, Eq))]
+    trait Old {
+        #[rpc(alias = "hi")]
+        fn hello(&self) -> u32;
+        fn set(&self, status: Status);
+    }
+
+    /// The service as a newer client knows it
+    #[rpc(crate = crate, derive(PartialEq, Eq))]
+    trait New {
+        fn hi(&self) -> u32;
+        fn set(&self, status: NewStatus);
+        fn goodbye(&self);
+    }
+
+    #[rpc(crate = crate)]
+    trait OldApi {
+        fn old(&self) -> impl Old;
+    }
+
+    #[rpc(crate = crate)]
+    trait NewApi {
+        fn old(&self) -> impl New;
+    }
+
+    #[rpc(crate = crate, variant_index, derive(PartialEq, Eq))]
+    trait OldIndexed {
+        fn hello(&self) -> u32;
+    }
+
+    #[rpc(crate = crate, variant_index)]
+    trait NewIndexed {
+        fn hello(&self) -> u32;
+        fn goodbye(&self);
+    }
+
+    /// Write a request as one service and read it as another, also returning whether it called a
+    /// method which the other service does not have
+    fn recode<From: Serialize, To: DeserializeOwned + Debug>(
+        format: &(impl Format<To, To> + Format<From, From>),
+        request: From,
+    ) -> (Result<To, String>, bool) {
+        let bytes = Format::<From, From>::write(format, request).unwrap();
+        let (result, unknown) = catch_unknown_method(|| Format::<To, To>::read(format, &bytes));
+        (result.map_err(|error| error.to_string()), unknown)
+    }
+
+    fn formats(check: impl Fn(&dyn Fn(new::Request) -> (Result<old::Request, String>, bool))) {
+        check(&|request| recode(&Json, request));
+        check(&|request| recode(&Cbor, request));
+        check(&|request| recode(&MsgPack, request));
+    }
+
+    #[test]
+    fn unknown_method() {
+        formats(|recode| {
+            let (result, unknown) = recode(new::Request::Goodbye());
+            assert!(unknown);
+            assert!(result.unwrap_err().contains("unknown method"));
+        });
+    }
+
+    #[test]
+    fn known_method() {
+        formats(|recode| {
+            assert_eq!(recode(new::Request::Hi()), (Ok(old::Request::Hello()), false));
+            assert_eq!(recode(new::Request::Set(NewStatus::Done)), (Ok(old::Request::Set(Status::Done)), false));
+        });
+    }
+
+    #[test]
+    fn invalid_argument() {
+        // an unknown variant of an argument is not an unknown method
+        formats(|recode| {
+            let (result, unknown) = recode(new::Request::Set(NewStatus::Archived));
+            assert!(result.is_err());
+            assert!(!unknown);
+        });
+    }
+
+    #[test]
+    fn nested_unknown_method() {
+        let request = new_api::Request::Old(new::Request::Goodbye());
+        let (result, unknown) = recode::<_, old_api::Request>(&Json, request);
+        assert!(result.is_err());
+        assert!(unknown);
+    }
+
+    #[test]
+    fn unknown_index() {
+        assert_eq!(
+            recode(&Postcard, new_indexed::Request::Hello()),
+            (Ok(old_indexed::Request::Hello()), false)
+        );
+        // postcard does not keep the message of the error, only the flag tells it apart
+        let (result, unknown) = recode::<_, old_indexed::Request>(&Postcard, new_indexed::Request::Goodbye());
+        assert!(result.is_err());
+        assert!(unknown);
+    }
+
+    #[test]
+    fn flag_is_reset() {
+        let (_, unknown) = recode::<_, old::Request>(&Json, new::Request::Goodbye());
+        assert!(unknown);
+        let ((), unknown) = catch_unknown_method(|| ());
+        assert!(!unknown);
+    }
+}
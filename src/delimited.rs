@@ -0,0 +1,374 @@
+//! Framing for a stream of encoded responses which are sent as a single body, such as a chunked
+//! HTTP response or a stream of server-sent events
+
+use bytes::{Buf, Bytes, BytesMut};
+
+const JSON_CONTENT_TYPE: &str = "application/json";
+const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+pub const EVENT_STREAM_CONTENT_TYPE: &str = "text/event-stream";
+/// The largest length-delimited item which is decoded, a larger length is treated as a corrupt body
+/// rather than waiting for the rest of it to be buffered
+const MAX_FRAME_SIZE: usize = 64 * 1024 * 1024;
+
+/// Describes how individual items are delimited within a body
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Framing {
+    /// Each item is prefixed by its length as a 4 byte little-endian integer
+    LengthDelimited,
+    /// Each item is followed by a newline, this is only used for JSON since it never contains a
+    /// raw newline
+    NewlineDelimited,
+    /// Each item is sent as an `item` [server-sent event](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+    /// followed by either an `end` or `error` event. Events can only carry text, so this only
+    /// supports formats which write UTF-8, such as JSON
+    EventStream,
+}
+
+impl Framing {
+    /// The framing used by the server when streaming items in the given format
+    pub fn for_format(content_type: &str) -> Self {
+        if content_type == JSON_CONTENT_TYPE {
+            Self::NewlineDelimited
+        } else {
+            Self::LengthDelimited
+        }
+    }
+
+    /// The framing used by a streamed body with the given Content-Type
+    pub fn for_body(content_type: &str) -> Self {
+        let content_type = content_type.split(';').next().unwrap_or(content_type).trim();
+        match content_type {
+            NDJSON_CONTENT_TYPE => Self::NewlineDelimited,
+            EVENT_STREAM_CONTENT_TYPE => Self::EventStream,
+            _ => Self::LengthDelimited,
+        }
+    }
+
+    /// Returns true if the media type only describes how a streamed body is framed, rather than
+    /// the format of its items
+    pub const fn is_framing_type(media_type: &str) -> bool {
+        media_type.eq_ignore_ascii_case(NDJSON_CONTENT_TYPE)
+            || media_type.eq_ignore_ascii_case(EVENT_STREAM_CONTENT_TYPE)
+    }
+
+    /// The Content-Type of a streamed body with this framing, for items in the given format
+    pub const fn content_type(self, format: &'static str) -> &'static str {
+        match self {
+            Self::LengthDelimited => format,
+            Self::NewlineDelimited => NDJSON_CONTENT_TYPE,
+            Self::EventStream => EVENT_STREAM_CONTENT_TYPE,
+        }
+    }
+
+    /// Encode a single item, which is written into the frame by `write` rather than being copied
+    /// into it, returning an error message if it cannot be sent with this framing
+    pub fn encode(self, write: impl FnOnce(&mut Vec<u8>) -> Result<(), String>) -> Result<Vec<u8>, String> {
+        match self {
+            Self::LengthDelimited => {
+                // the length is filled in once the item has been written
+                let mut bytes = vec![0; 4];
+                write(&mut bytes)?;
+                let length = u32::try_from(bytes.len() - 4)
+                    .map_err(|_| format!("response of {} bytes is too large to be streamed", bytes.len() - 4))?;
+                bytes[..4].copy_from_slice(&length.to_le_bytes());
+                Ok(bytes)
+            }
+            Self::NewlineDelimited => {
+                let mut bytes = Vec::new();
+                write(&mut bytes)?;
+                bytes.push(b'\n');
+                Ok(bytes)
+            }
+            Self::EventStream => {
+                let mut item = Vec::new();
+                write(&mut item)?;
+                let item = String::from_utf8(item)
+                    .map_err(|_| "server-sent events can only carry text formats".to_string())?;
+                Ok(event("item", &item))
+            }
+        }
+    }
+
+    /// The marker sent once all items have been sent, if this framing has one
+    pub fn end(self) -> Option<Vec<u8>> {
+        match self {
+            Self::LengthDelimited | Self::NewlineDelimited => None,
+            Self::EventStream => Some(event("end", "")),
+        }
+    }
+
+    /// The marker sent when the stream fails, if this framing has one
+    pub fn error(self, message: &str) -> Option<Vec<u8>> {
+        match self {
+            Self::LengthDelimited | Self::NewlineDelimited => None,
+            Self::EventStream => Some(event("error", message)),
+        }
+    }
+}
+
+fn event(name: &str, data: &str) -> Vec<u8> {
+    let mut event = format!("event: {name}\n");
+    for line in data.split('\n') {
+        event.push_str("data: ");
+        event.push_str(line);
+        event.push('\n');
+    }
+    event.push('\n');
+    event.into_bytes()
+}
+
+/// A single decoded part of a streamed body
+#[derive(Debug, PartialEq, Eq)]
+pub enum Decoded {
+    /// A single encoded item
+    Item(Bytes),
+    /// The server has finished sending items
+    End,
+    /// The server failed while producing items, no more items will be sent
+    Error(String),
+    /// The body cannot be decoded, no more items can be read from it
+    Invalid(String),
+}
+
+/// Incrementally decodes items from the chunks of a streamed body
+#[derive(Debug)]
+pub struct Decoder {
+    framing: Framing,
+    buffer: BytesMut,
+}
+
+impl Decoder {
+    pub fn new(framing: Framing) -> Self {
+        Self {
+            framing,
+            buffer: BytesMut::new(),
+        }
+    }
+
+    /// Add a chunk of the body
+    pub fn push(&mut self, chunk: &[u8]) {
+        if self.framing == Framing::EventStream {
+            // lines may be terminated by CRLF, an item never contains a raw CR
+            self.buffer.extend(chunk.iter().filter(|byte| **byte != b'\r'));
+        } else {
+            self.buffer.extend_from_slice(chunk);
+        }
+    }
+
+    /// Take the next complete part of the body, if there is one
+    pub fn next_part(&mut self) -> Option<Decoded> {
+        match self.framing {
+            Framing::LengthDelimited => {
+                let [a, b, c, d, ..] = self.buffer[..] else {
+                    return None;
+                };
+                let length = u32::from_le_bytes([a, b, c, d]);
+                let Some(frame) = usize::try_from(length)
+                    .ok()
+                    .filter(|length| *length <= MAX_FRAME_SIZE)
+                    .and_then(|length| length.checked_add(4))
+                else {
+                    self.buffer.clear();
+                    return Some(Decoded::Invalid(format!("item of {length} bytes is too large")));
+                };
+                if self.buffer.len() < frame {
+                    return None;
+                }
+                let mut item = self.buffer.split_to(frame);
+                item.advance(4);
+                Some(Decoded::Item(item.freeze()))
+            }
+            Framing::NewlineDelimited => loop {
+                let end = self.buffer.iter().position(|byte| *byte == b'\n')?;
+                let mut item = self.buffer.split_to(end + 1);
+                // lines may be terminated by CRLF
+                item.truncate(if item[..end].ends_with(b"\r") { end - 1 } else { end });
+                // skip blank lines
+                if !item.iter().all(u8::is_ascii_whitespace) {
+                    return Some(Decoded::Item(item.freeze()));
+                }
+            },
+            Framing::EventStream => loop {
+                let end = self.buffer.windows(2).position(|bytes| bytes == b"\n\n")?;
+                let event = self.buffer.split_to(end + 2);
+                if let Some(part) = parse_event(&String::from_utf8_lossy(&event)) {
+                    return Some(part);
+                }
+            },
+        }
+    }
+
+    /// Returns true if there is an incomplete item left in the buffer
+    pub fn has_remainder(&self) -> bool {
+        match self.framing {
+            Framing::LengthDelimited => !self.buffer.is_empty(),
+            Framing::NewlineDelimited | Framing::EventStream => {
+                !self.buffer.iter().all(u8::is_ascii_whitespace)
+            }
+        }
+    }
+}
+
+/// Parse a single server-sent event, returning `None` for events which carry nothing, such as
+/// comments used as keep-alives
+fn parse_event(event: &str) -> Option<Decoded> {
+    let mut name = None;
+    let mut data: Option<String> = None;
+    for line in event.lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => name = Some(value),
+            "data" => {
+                if let Some(data) = &mut data {
+                    data.push('\n');
+                    data.push_str(value);
+                } else {
+                    data = Some(value.to_string());
+                }
+            }
+            // comments, ids and retry intervals are not used
+            _ => {}
+        }
+    }
+    match name {
+        Some("end") => Some(Decoded::End),
+        Some("error") => Some(Decoded::Error(data.unwrap_or_default())),
+        Some("item" | "message") | None => data.map(|data| Decoded::Item(data.into())),
+        Some(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::iter;
+
+    fn length_delimited(item: &[u8]) -> Vec<u8> {
+        Framing::LengthDelimited
+            .encode(|bytes| {
+                bytes.extend_from_slice(item);
+                Ok(())
+            })
+            .expect("failed to encode item")
+    }
+
+    #[test]
+    fn length_prefix_split_across_chunks() {
+        let frame = length_delimited(b"hello");
+        let mut decoder = Decoder::new(Framing::LengthDelimited);
+        decoder.push(&frame[..2]);
+        assert_eq!(decoder.next_part(), None);
+        decoder.push(&frame[2..]);
+        assert_eq!(decoder.next_part(), Some(Decoded::Item(Bytes::from_static(b"hello"))));
+        assert!(!decoder.has_remainder());
+    }
+
+    #[test]
+    fn frame_split_across_chunks() {
+        let frame = length_delimited(b"hello");
+        let mut decoder = Decoder::new(Framing::LengthDelimited);
+        decoder.push(&frame[..6]);
+        assert_eq!(decoder.next_part(), None);
+        decoder.push(&frame[6..]);
+        assert_eq!(decoder.next_part(), Some(Decoded::Item(Bytes::from_static(b"hello"))));
+        assert_eq!(decoder.next_part(), None);
+    }
+
+    #[test]
+    fn truncated_frame_at_end_of_body() {
+        let frame = length_delimited(b"hello");
+        let mut decoder = Decoder::new(Framing::LengthDelimited);
+        decoder.push(&frame[..frame.len() - 1]);
+        assert_eq!(decoder.next_part(), None);
+        assert!(decoder.has_remainder());
+    }
+
+    #[test]
+    fn empty_frame() {
+        let mut body = length_delimited(b"");
+        body.extend(length_delimited(b"next"));
+        let mut decoder = Decoder::new(Framing::LengthDelimited);
+        decoder.push(&body);
+        assert_eq!(decoder.next_part(), Some(Decoded::Item(Bytes::new())));
+        assert_eq!(decoder.next_part(), Some(Decoded::Item(Bytes::from_static(b"next"))));
+        assert!(!decoder.has_remainder());
+    }
+
+    #[test]
+    fn oversized_frame() {
+        let mut decoder = Decoder::new(Framing::LengthDelimited);
+        decoder.push(&u32::MAX.to_le_bytes());
+        assert!(matches!(decoder.next_part(), Some(Decoded::Invalid(_))));
+        assert!(!decoder.has_remainder());
+    }
+
+    fn decode_all(framing: Framing, chunks: &[&str]) -> Vec<Decoded> {
+        let mut decoder = Decoder::new(framing);
+        let mut parts = Vec::new();
+        for chunk in chunks {
+            decoder.push(chunk.as_bytes());
+            parts.extend(iter::from_fn(|| decoder.next_part()));
+        }
+        assert!(!decoder.has_remainder(), "the body has an incomplete item");
+        parts
+    }
+
+    fn item(item: &'static str) -> Decoded {
+        Decoded::Item(Bytes::from_static(item.as_bytes()))
+    }
+
+    #[test]
+    fn event_stream_crlf() {
+        let parts = decode_all(Framing::EventStream, &["event: item\r\ndata: 1\r", "\n\r\nevent: end\r\n\r\n"]);
+        assert_eq!(parts, [item("1"), Decoded::End]);
+    }
+
+    #[test]
+    fn event_stream_multi_line_data() {
+        let parts = decode_all(Framing::EventStream, &["event: item\ndata: {\ndata: \"a\": 1\ndata: }\n\n"]);
+        assert_eq!(parts, [item("{\n\"a\": 1\n}")]);
+        let event = Framing::EventStream.encode(|bytes| {
+            bytes.extend_from_slice(b"x\ny");
+            Ok(())
+        });
+        let event = String::from_utf8(event.expect("failed to encode item")).expect("event is not UTF-8");
+        assert_eq!(decode_all(Framing::EventStream, &[&event]), [item("x\ny")]);
+    }
+
+    #[test]
+    fn event_stream_comments() {
+        let parts = decode_all(Framing::EventStream, &[": keep-alive\n\n", "event: item\n: a comment\ndata: 1\n\n"]);
+        assert_eq!(parts, [item("1")]);
+    }
+
+    #[test]
+    fn event_stream_blank_lines() {
+        let parts = decode_all(Framing::EventStream, &["\n\n", "event: item\ndata: 1\n\n\n\n", "event: error\ndata: failed\n\n"]);
+        assert_eq!(parts, [item("1"), Decoded::Error("failed".to_string())]);
+    }
+
+    #[test]
+    fn event_stream_unterminated_event() {
+        let mut decoder = Decoder::new(Framing::EventStream);
+        decoder.push(b"event: item\ndata: 1\n\nevent: item\ndata: 2\n");
+        assert_eq!(decoder.next_part(), Some(item("1")));
+        assert_eq!(decoder.next_part(), None);
+        assert!(decoder.has_remainder());
+    }
+
+    #[test]
+    fn newline_delimited() {
+        let parts = decode_all(Framing::NewlineDelimited, &["{\"a\"", ": 1}\r\n\n  \n2\n"]);
+        assert_eq!(parts, [item("{\"a\": 1}"), item("2")]);
+    }
+
+    #[test]
+    fn newline_delimited_unterminated_item() {
+        let mut decoder = Decoder::new(Framing::NewlineDelimited);
+        decoder.push(b"1\n2");
+        assert_eq!(decoder.next_part(), Some(item("1")));
+        assert_eq!(decoder.next_part(), None);
+        assert!(decoder.has_remainder());
+    }
+}
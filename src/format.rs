@@ -1,21 +1,36 @@
 //! Defines format behaviour
 #![allow(clippy::missing_errors_doc, reason = "Errors are obvious")]
 
+use bytes::Bytes;
 use std::error::Error;
+use std::io;
 
 #[cfg(feature = "json")]
 pub mod json;
+#[cfg(feature = "json")]
+pub mod jsonrpc;
 #[cfg(all(feature = "browser-json", target_arch = "wasm32"))]
 pub mod browser_json;
 #[cfg(all(feature = "browser-json", not(target_arch = "wasm32")))]
 compile_error!("browser-json is only available on wasm32 arch");
 #[cfg(feature = "cbor")]
 pub mod cbor;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+#[cfg(feature = "postcard")]
+pub mod postcard;
+pub mod split;
+pub mod compressed;
 
 /// This specifies types which represent formats, but does not prescribe supporting any particular type
 pub trait IsFormat {
     /// The HTTP content-type related to this format
     fn content_type(&self) -> &'static str;
+    /// The HTTP content-type which responses are requested in, this is the same as
+    /// [`content_type`](Self::content_type) unless the format reads and writes different formats
+    fn accept(&self) -> &'static str {
+        self.content_type()
+    }
 }
 
 /// A format which is able to deserialise `Read` and serialise `Write`
@@ -24,6 +39,32 @@ pub trait Format<Read, Write>: IsFormat + Send + Sync {
     fn read(&self, reader: &[u8]) -> Result<Read, Box<dyn Error + Send>>;
     /// write the given value to the given [Write]
     fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>>;
+    /// Write the given value to the end of `writer`, rather than to a new buffer. Transports use
+    /// this to write a value directly after their own framing
+    ///
+    /// By default, this writes to a new buffer and copies it into `writer`, formats should
+    /// override this if they can write incrementally
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        let bytes = self.write(value)?;
+        writer.write_all(&bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+    /// Read a value from the given [Bytes], which are shared with the transport rather than being
+    /// copied out of it
+    ///
+    /// By default, this reads from the bytes as a slice, formats should override this if they can
+    /// decode from the shared bytes without copying them
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        self.read(&bytes)
+    }
+}
+
+/// A format which is able to deserialise `Read` borrowing from the input, so that strings and
+/// bytes do not need to be copied out of it
+///
+/// CBOR and compressed payloads cannot be borrowed from, servers read them as owned requests
+pub trait BorrowFormat<'de, Read>: IsFormat + Send + Sync {
+    /// Read a value which may borrow from the given bytes
+    fn read_borrowed(&self, reader: &'de [u8]) -> Result<Read, Box<dyn Error + Send>>;
 }
 
 impl<Read, Write> dyn Format<Read, Write> {}
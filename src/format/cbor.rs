@@ -1,9 +1,11 @@
 //! Provides support for the CBOR ([Concise Binary Object Representation](https://cbor.io/)) format
 
 use crate::format::{Format, IsFormat};
+use bytes::Bytes;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 use std::error::Error;
+use std::io;
 
 const CONTENT_TYPE: &str = "application/cbor";
 
@@ -24,9 +26,17 @@ where Read: DeserializeOwned, Write: Serialize
         ciborium::from_reader(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
     }
 
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        ciborium::from_reader(&*bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
     fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
         let mut buffer = Vec::new();
         ciborium::into_writer(&value, &mut buffer).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
         Ok(buffer)
     }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        ciborium::into_writer(&value, writer).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
 }
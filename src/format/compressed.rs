@@ -0,0 +1,330 @@
+//! Provides a format which compresses the payloads of another format
+
+use crate::format::{Format, IsFormat};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::error::Error;
+use std::io;
+use std::sync::{Mutex, OnceLock};
+
+/// Payloads smaller than this are not compressed by default, since compression would make little
+/// difference to their size
+const DEFAULT_THRESHOLD: usize = 1024;
+/// Payloads which decompress to more than this are rejected by default, so that a small payload
+/// cannot be used to exhaust the memory of its reader
+const DEFAULT_MAX_SIZE: usize = 64 * 1024 * 1024;
+
+/// Marks a payload which was written without compression
+const UNCOMPRESSED: u8 = 0;
+/// Marks a payload which was compressed
+const COMPRESSED: u8 = 1;
+
+/// A compression algorithm
+pub trait Codec: Send + Sync {
+    /// The name of the algorithm, this is appended to the Content-Type of the wrapped format
+    fn name(&self) -> &'static str;
+    /// Compress the given data
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>>;
+    /// Decompress the given data, failing if it decompresses to more than `max_size` bytes
+    ///
+    /// # Errors
+    /// Implementations should use [`read_limited`] to read the decompressed data, which fails
+    /// without reading past the limit
+    fn decompress(&self, data: &[u8], max_size: usize) -> io::Result<Vec<u8>>;
+}
+
+/// Read everything from a decompressing reader, failing if it produces more than `max_size` bytes
+///
+/// # Errors
+/// Returns an error if the reader fails or produces more than `max_size` bytes
+pub fn read_limited(reader: impl io::Read, max_size: usize) -> io::Result<Vec<u8>> {
+    use std::io::Read as _;
+    let mut decoded = Vec::new();
+    reader
+        .take(u64::try_from(max_size).unwrap_or(u64::MAX).saturating_add(1))
+        .read_to_end(&mut decoded)?;
+    if decoded.len() > max_size {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("payload decompresses to more than the maximum of {max_size} bytes"),
+        ));
+    }
+    Ok(decoded)
+}
+
+/// Wraps a format, compressing any payload which is larger than a threshold
+///
+/// Each payload is prefixed by a single byte which marks whether it was compressed. The
+/// Content-Type is the Content-Type of the wrapped format with the name of the codec appended
+/// (eg: `application/json+gzip`), so the server can support it alongside the uncompressed format
+/// and clients can ask for it in the `Accept` header
+#[derive(Debug, Clone)]
+pub struct Compressed<F, C> {
+    format: F,
+    codec: C,
+    threshold: usize,
+    max_size: usize,
+    content_type: OnceLock<&'static str>,
+}
+
+impl<F, C> Compressed<F, C> {
+    /// Wrap the given format, compressing payloads larger than 1 KiB with the given codec and
+    /// rejecting payloads which decompress to more than 64 MiB
+    pub const fn new(format: F, codec: C) -> Self {
+        Self {
+            format,
+            codec,
+            threshold: DEFAULT_THRESHOLD,
+            max_size: DEFAULT_MAX_SIZE,
+            content_type: OnceLock::new(),
+        }
+    }
+
+    /// Set the size, in bytes, above which payloads are compressed
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: usize) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Set the size, in bytes, above which decompressed payloads are rejected
+    #[must_use]
+    pub const fn with_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size;
+        self
+    }
+}
+
+impl<F: IsFormat, C: Codec> IsFormat for Compressed<F, C> {
+    fn content_type(&self) -> &'static str {
+        self.content_type
+            .get_or_init(|| content_type(self.format.content_type(), self.codec.name()))
+    }
+}
+
+impl<Read, Write, F, C> Format<Read, Write> for Compressed<F, C>
+where
+    F: Format<Read, Write>,
+    C: Codec,
+{
+    fn read(&self, reader: &[u8]) -> Result<Read, Box<dyn Error + Send>> {
+        match reader.split_first() {
+            Some((&UNCOMPRESSED, payload)) => self.format.read(payload),
+            Some((&COMPRESSED, payload)) => {
+                let payload = self.codec.decompress(payload, self.max_size).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
+                self.format.read(&payload)
+            }
+            _ => Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "payload has no compression marker"))),
+        }
+    }
+
+    fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        let mut bytes = Vec::new();
+        self.write_to(value, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        let mut bytes = vec![UNCOMPRESSED];
+        self.format.write_to(value, &mut bytes)?;
+        if bytes.len() - 1 > self.threshold {
+            let payload = self.codec.compress(&bytes[1..]).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
+            bytes.clear();
+            bytes.push(COMPRESSED);
+            bytes.extend(payload);
+        }
+        writer.write_all(&bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        match bytes.first() {
+            Some(&UNCOMPRESSED) => self.format.read_bytes(bytes.slice(1..)),
+            Some(&COMPRESSED) => {
+                let payload = self.codec.decompress(&bytes[1..], self.max_size).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
+                self.format.read_bytes(payload.into())
+            }
+            _ => Err(Box::new(io::Error::new(io::ErrorKind::InvalidData, "payload has no compression marker"))),
+        }
+    }
+}
+
+/// Build the Content-Type of a compressed format, each Content-Type is only allocated once so
+/// that it can be shared by every instance of the format
+fn content_type(format: &'static str, codec: &'static str) -> &'static str {
+    static CONTENT_TYPES: OnceLock<Mutex<HashMap<(&'static str, &'static str), &'static str>>> = OnceLock::new();
+    let mut content_types = CONTENT_TYPES
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    content_types
+        .entry((format, codec))
+        .or_insert_with(|| String::leak(format!("{format}+{codec}")))
+}
+
+/// The [gzip](https://www.gzip.org/) compression algorithm
+#[cfg(feature = "gzip")]
+#[derive(Debug, Copy, Clone)]
+pub struct Gzip {
+    level: u32,
+}
+
+#[cfg(feature = "gzip")]
+impl Gzip {
+    /// Use the default compression level
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { level: 6 }
+    }
+
+    /// Use the given compression level, from 0 (no compression) to 9 (best compression)
+    #[must_use]
+    pub const fn with_level(level: u32) -> Self {
+        Self { level }
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Default for Gzip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl Codec for Gzip {
+    fn name(&self) -> &'static str {
+        "gzip"
+    }
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        use std::io::Write as _;
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::new(self.level));
+        encoder.write_all(data)?;
+        encoder.finish()
+    }
+
+    fn decompress(&self, data: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        read_limited(flate2::read::GzDecoder::new(data), max_size)
+    }
+}
+
+/// The [Zstandard](https://facebook.github.io/zstd/) compression algorithm
+#[cfg(feature = "zstd")]
+#[derive(Debug, Copy, Clone)]
+pub struct Zstd {
+    level: i32,
+}
+
+#[cfg(feature = "zstd")]
+impl Zstd {
+    /// Use the default compression level
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { level: 3 }
+    }
+
+    /// Use the given compression level, from 1 (fastest) to 22 (best compression)
+    #[must_use]
+    pub const fn with_level(level: i32) -> Self {
+        Self { level }
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Default for Zstd {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl Codec for Zstd {
+    fn name(&self) -> &'static str {
+        "zstd"
+    }
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        zstd::encode_all(data, self.level)
+    }
+
+    fn decompress(&self, data: &[u8], max_size: usize) -> io::Result<Vec<u8>> {
+        read_limited(zstd::stream::read::Decoder::new(data)?, max_size)
+    }
+}
+
+#[cfg(all(test, feature = "json", feature = "gzip", feature = "zstd"))]
+mod tests {
+    use super::*;
+    use crate::format::json::Json;
+
+    fn read(format: &impl Format<Vec<u32>, Vec<u32>>, bytes: &[u8]) -> Result<Vec<u32>, Box<dyn Error + Send>> {
+        format.read(bytes)
+    }
+
+    fn read_bytes(format: &impl Format<Vec<u32>, Vec<u32>>, bytes: Vec<u8>) -> Result<Vec<u32>, Box<dyn Error + Send>> {
+        format.read_bytes(bytes.into())
+    }
+
+    fn write(format: &impl Format<Vec<u32>, Vec<u32>>, value: Vec<u32>) -> Vec<u8> {
+        format.write(value).unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let value: Vec<u32> = (0..1000).collect();
+        let format = Compressed::new(Json, Gzip::new());
+        let bytes = write(&format, value.clone());
+        assert_eq!(bytes[0], COMPRESSED);
+        assert_eq!(read(&format, &bytes).unwrap(), value);
+        assert_eq!(read_bytes(&format, bytes).unwrap(), value);
+
+        let format = Compressed::new(Json, Zstd::new());
+        let bytes = write(&format, value.clone());
+        assert_eq!(bytes[0], COMPRESSED);
+        assert_eq!(read(&format, &bytes).unwrap(), value);
+        assert_eq!(read_bytes(&format, bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn threshold() {
+        // `[1,2]` is 5 bytes long
+        let format = Compressed::new(Json, Gzip::new()).with_threshold(5);
+        let bytes = write(&format, vec![1, 2]);
+        assert_eq!(bytes, b"\x00[1,2]");
+        assert_eq!(read(&format, &bytes).unwrap(), vec![1, 2]);
+
+        let format = format.with_threshold(4);
+        let bytes = write(&format, vec![1, 2]);
+        assert_eq!(bytes[0], COMPRESSED);
+        assert_eq!(read(&format, &bytes).unwrap(), vec![1, 2]);
+    }
+
+    #[test]
+    fn bad_marker() {
+        let format = Compressed::new(Json, Gzip::new());
+        assert!(read(&format, b"\x02[1,2]").is_err());
+        assert!(read(&format, b"").is_err());
+        assert!(read_bytes(&format, b"\x02[1,2]".to_vec()).is_err());
+    }
+
+    #[test]
+    fn oversized() {
+        let value = vec![0; 10_000];
+        let writer = Compressed::new(Json, Gzip::new());
+        let bytes = write(&writer, value.clone());
+        // the payload is 20001 bytes long, but is still small once compressed
+        assert!(bytes.len() < 1024);
+
+        let reader = Compressed::new(Json, Gzip::new()).with_max_size(20_001);
+        assert_eq!(read(&reader, &bytes).unwrap(), value);
+        let reader = reader.with_max_size(20_000);
+        let error = read(&reader, &bytes).unwrap_err();
+        assert!(error.to_string().contains("maximum of 20000 bytes"), "{error}");
+        assert!(read_bytes(&reader, bytes).is_err());
+
+        let writer = Compressed::new(Json, Zstd::new());
+        let bytes = write(&writer, value);
+        let reader = Compressed::new(Json, Zstd::new()).with_max_size(1000);
+        assert!(read(&reader, &bytes).is_err());
+    }
+}
@@ -1,9 +1,12 @@
 //! Provides support for the CBOR ([JavaScript Object Notation](https://www.json.org/)) format
 
-use crate::format::{Format, IsFormat};
+use crate::format::{BorrowFormat, Format, IsFormat};
+use bytes::Bytes;
+use serde::Deserialize;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
 use std::error::Error;
+use std::io;
 
 const CONTENT_TYPE: &str = "application/json";
 
@@ -26,7 +29,21 @@ where
         serde_json::from_slice(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
     }
 
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        serde_json::from_slice(&bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
     fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
         serde_json::to_vec(&value).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
     }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        serde_json::to_writer(writer, &value).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+}
+
+impl<'de, Read: Deserialize<'de>> BorrowFormat<'de, Read> for Json {
+    fn read_borrowed(&self, reader: &'de [u8]) -> Result<Read, Box<dyn Error + Send>> {
+        serde_json::from_slice(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
 }
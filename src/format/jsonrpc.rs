@@ -0,0 +1,662 @@
+//! Provides support for [JSON-RPC 2.0](https://www.jsonrpc.org/specification)
+//!
+//! Each method is called by its name, with the names of any nested services before it, joined by
+//! dots (eg: `users.by_id.get`). The methods inherited from a supertrait are a nested service named
+//! after the supertrait, so `ping` inherited from `HealthService` is called as
+//! `health_service.ping`. A method may also be called by any of its aliases. The params are the
+//! arguments of every method in that chain, either in order or by name, so `users.by_id.get` with
+//! the params `[7, "name"]` calls `get("name")` on the service returned by `by_id(7)`. An
+//! application error, as declared with `#[rpc(error = ...)]`, is returned as an error object with
+//! the code [`APPLICATION_ERROR`] and the error as its data
+//!
+//! A JSON-RPC response does not name the method that it answers, and must echo the ID of its
+//! request, so this is not a [Format](crate::format::Format). Clients are built with
+//! [`Builder::json_rpc`](crate::client::Builder::json_rpc), and servers handle requests with
+//! [handle]. Streaming methods cannot be called, and services declared with
+//! `#[rpc(variant_index)]` are not supported
+#![allow(clippy::future_not_send, reason = "Cannot explicitly make futures `Send` while supporting WASM")]
+
+use crate::client::{AsyncClient, AsyncRefClient, AsyncTransport, BlockingClient, BlockingRefClient, BlockingTransport, ErrorKind, ResponseError, RpcError};
+use crate::format::json::Json;
+use crate::{MethodInfo, Request};
+use futures::future;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use thiserror::Error;
+
+const CONTENT_TYPE: &str = "application/json";
+const VERSION: &str = "2.0";
+
+/// Invalid JSON was received
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON sent is not a valid request object
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// The params of the method are invalid
+pub const INVALID_PARAMS: i64 = -32602;
+/// An internal error occurred while handling the request
+pub const INTERNAL_ERROR: i64 = -32603;
+/// The method returned an application error, which is the data of the error object
+pub const APPLICATION_ERROR: i64 = -32000;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(Debug, Copy, Clone)]
+/// [JSON-RPC 2.0](https://www.jsonrpc.org/specification), this converts the requests and responses
+/// of a service to and from JSON values
+pub struct JsonRpc;
+
+/// Converts the values read and written by a server to and from JSON, so that they can be
+/// restructured as JSON-RPC messages
+pub trait JsonValue<Read, Write>: Send + Sync {
+    /// Read a value from JSON
+    ///
+    /// # Errors
+    /// Returns an error if the JSON does not match `Read`
+    fn read_value(&self, value: Value) -> Result<Read, serde_json::Error>;
+    /// Write a value as JSON
+    ///
+    /// # Errors
+    /// Returns an error if the value cannot be serialised
+    fn write_value(&self, value: Write) -> Result<Value, serde_json::Error>;
+}
+
+impl<Read, Write> JsonValue<Read, Write> for JsonRpc
+where
+    Read: DeserializeOwned,
+    Write: Serialize,
+{
+    fn read_value(&self, value: Value) -> Result<Read, serde_json::Error> {
+        serde_json::from_value(value)
+    }
+
+    fn write_value(&self, value: Write) -> Result<Value, serde_json::Error> {
+        serde_json::to_value(value)
+    }
+}
+
+/// A JSON-RPC error object
+#[derive(Debug, Error, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[error("{message} ({code})")]
+pub struct ErrorObject {
+    /// The kind of error, see the constants in this module for the codes used
+    pub code: i64,
+    /// A short description of the error
+    pub message: String,
+    /// Any further details of the error, or the application error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl ErrorObject {
+    fn new(code: i64, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
+    fn invalid_request(details: impl Into<Value>) -> Self {
+        Self::new(INVALID_REQUEST, "Invalid Request").with_data(details)
+    }
+
+    fn invalid_params(details: impl Into<Value>) -> Self {
+        Self::new(INVALID_PARAMS, "Invalid params").with_data(details)
+    }
+
+    fn with_data(mut self, data: impl Into<Value>) -> Self {
+        self.data = Some(data.into());
+        self
+    }
+}
+
+impl From<ErrorObject> for ResponseError {
+    fn from(error: ErrorObject) -> Self {
+        let kind = match error.code {
+            PARSE_ERROR | INVALID_PARAMS => ErrorKind::Deserialise,
+            INVALID_REQUEST => ErrorKind::Rejected,
+            METHOD_NOT_FOUND => ErrorKind::UnknownMethod,
+            INTERNAL_ERROR => ErrorKind::Internal,
+            _ => ErrorKind::Failed,
+        };
+        let response = Self::new(kind, error.message);
+        match error.data {
+            Some(Value::String(details)) => response.with_details(details),
+            Some(details) => response.with_details(details.to_string()),
+            None => response,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct RequestObject {
+    jsonrpc: &'static str,
+    method: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    params: Vec<Value>,
+    id: u64,
+}
+
+#[derive(Deserialize)]
+struct ResponseObject {
+    #[serde(default)]
+    result: Value,
+    #[serde(default)]
+    error: Option<ErrorObject>,
+    id: Value,
+}
+
+/// Flatten an encoded request into the dotted name of the method that it calls and its params,
+/// along with the chain of methods which are called
+fn flatten(mut request: Value, mut methods: &'static [MethodInfo]) -> Result<(String, Vec<Value>, Vec<&'static MethodInfo>), String> {
+    let mut name = String::new();
+    let mut params = Vec::new();
+    let mut path = Vec::new();
+    loop {
+        let Value::Object(mut object) = request else {
+            return Err("expected the request to be an object".to_string());
+        };
+        let method_name = object.get("method").and_then(Value::as_str).ok_or("the request has no method")?;
+        let method = find_method(methods, method_name).ok_or_else(|| format!("unknown method: {method_name}"))?;
+        if !path.is_empty() {
+            name.push('.');
+        }
+        name.push_str(method.name);
+        path.push(method);
+        let args = object.remove("args").unwrap_or_default();
+        let mut fields = split_fields(args, method)?;
+        let Some(nested) = method.nested else {
+            params.extend(fields);
+            return Ok((name, params, path));
+        };
+        request = fields.pop().unwrap_or_default();
+        params.extend(fields);
+        methods = nested;
+    }
+}
+
+/// Find the method which is called by the given name, this may be one of its aliases
+fn find_method(methods: &'static [MethodInfo], name: &str) -> Option<&'static MethodInfo> {
+    methods
+        .iter()
+        .find(|method| method.name == name || method.aliases.contains(&name))
+}
+
+/// The names of the fields in a request variant with named args
+fn field_names(method: &MethodInfo) -> impl Iterator<Item = &'static str> {
+    method.args.iter().copied().chain(method.nested.map(|_| "request"))
+}
+
+/// Split the encoded fields of a request variant, a variant with a single field is encoded as the
+/// field itself, unless its args are named, in which case the fields are a map
+fn split_fields(args: Value, method: &MethodInfo) -> Result<Vec<Value>, String> {
+    let len = method.args.len() + usize::from(method.nested.is_some());
+    match args {
+        Value::Object(mut fields) if method.named => Ok(field_names(method)
+            .map(|name| fields.remove(name).unwrap_or_default())
+            .collect()),
+        field if !method.named && len == 1 => Ok(vec![field]),
+        Value::Array(fields) if !method.named && fields.len() == len => Ok(fields),
+        _ => Err(format!("expected {len} fields in the request")),
+    }
+}
+
+/// Join the fields of a request variant, this is the reverse of [`split_fields`]
+///
+/// A missing field is left for the server to default, this is only possible if the args are named,
+/// or if it is one of the last fields of a variant with more than one field
+fn join_fields(mut fields: Vec<Option<Value>>, method: &MethodInfo) -> Result<Value, ErrorObject> {
+    if method.named {
+        let fields = field_names(method).zip(fields).filter_map(|(name, field)| Some((name.to_string(), field?)));
+        return Ok(Value::Object(fields.collect()));
+    }
+    let single = fields.len() == 1;
+    if !single {
+        fields.truncate(fields.iter().rposition(Option::is_some).map_or(0, |last| last + 1));
+    }
+    let mut fields = field_names(method)
+        .zip(fields)
+        .map(|(name, field)| field.ok_or_else(|| ErrorObject::invalid_params(format!("missing param: {name}"))))
+        .collect::<Result<Vec<_>, _>>()?;
+    if single {
+        Ok(fields.pop().unwrap_or_default())
+    } else {
+        Ok(Value::Array(fields))
+    }
+}
+
+/// Build the encoded request for a dotted method name and its params, along with the chain of
+/// methods which are called
+fn unflatten(name: &str, params: Option<Value>, methods: &'static [MethodInfo]) -> Result<(Value, Vec<&'static MethodInfo>), ErrorObject> {
+    let not_found = || ErrorObject::new(METHOD_NOT_FOUND, "Method not found").with_data(name);
+    let mut path = Vec::new();
+    let mut methods = Some(methods);
+    for segment in name.split('.') {
+        let method = methods
+            .and_then(|methods| find_method(methods, segment))
+            .ok_or_else(not_found)?;
+        path.push(method);
+        methods = method.nested;
+    }
+    // the method returns a service, rather than a response
+    if methods.is_some() {
+        return Err(not_found());
+    }
+    let names: Vec<_> = path.iter().flat_map(|method| method.args.iter().copied()).collect();
+    // params may be missing if they have a default, which is checked when the request is read
+    let mut params: Vec<_> = match params {
+        None => names.iter().map(|_| None).collect(),
+        Some(Value::Array(params)) if params.len() > names.len() => {
+            return Err(ErrorObject::invalid_params(format!("expected {} params, received {}", names.len(), params.len())));
+        }
+        Some(Value::Array(params)) => {
+            let missing = names.len() - params.len();
+            params.into_iter().map(Some).chain(std::iter::repeat_n(None, missing)).collect()
+        }
+        Some(Value::Object(mut params)) => {
+            let values = names.iter().map(|name| params.remove(*name)).collect();
+            if let Some(name) = params.keys().next() {
+                return Err(ErrorObject::invalid_params(format!("unknown param: {name}")));
+            }
+            values
+        }
+        Some(_) => return Err(ErrorObject::invalid_request("params must be an array or an object")),
+    };
+    // each request contains the request of the nested service after it
+    let mut request = None;
+    for method in path.iter().rev() {
+        let mut fields = params.split_off(params.len() - method.args.len());
+        fields.extend(request.take().map(Some));
+        let args = join_fields(fields, method)?;
+        request = Some(json!({ "method": method.name, "args": args }));
+    }
+    Ok((request.unwrap_or_default(), path))
+}
+
+/// Take the result of the last method in the chain from an encoded response, or the application
+/// error that it returned
+fn unwrap_response(mut response: Value, path: &[&MethodInfo]) -> Result<Value, ErrorObject> {
+    for _ in path {
+        let Value::Object(mut object) = response else {
+            return Err(ErrorObject::new(INTERNAL_ERROR, "Internal error").with_data("expected the response to be an object"));
+        };
+        response = object.remove("result").unwrap_or_default();
+    }
+    if !path.last().is_some_and(|method| method.error) {
+        return Ok(response);
+    }
+    match response {
+        Value::Object(mut result) if result.len() == 1 => {
+            match (result.remove("Ok"), result.remove("Err")) {
+                (Some(value), _) => Ok(value),
+                (None, Some(error)) => Err(ErrorObject::new(APPLICATION_ERROR, "Application error").with_data(error)),
+                (None, None) => Err(ErrorObject::new(INTERNAL_ERROR, "Internal error").with_data("expected the response to be a result")),
+            }
+        }
+        _ => Err(ErrorObject::new(INTERNAL_ERROR, "Internal error").with_data("expected the response to be a result")),
+    }
+}
+
+/// Build the encoded response for the result of the last method in the chain, or the error
+/// returned in its place
+fn wrap_response(result: Result<Value, ErrorObject>, path: &[&MethodInfo]) -> Result<Value, ResponseError> {
+    let error = path.last().is_some_and(|method| method.error);
+    let mut response = match result {
+        Ok(value) if error => json!({ "Ok": value }),
+        Ok(value) => value,
+        Err(ErrorObject { code: APPLICATION_ERROR, data, .. }) if error => json!({ "Err": data }),
+        Err(error) => return Err(error.into()),
+    };
+    for method in path.iter().rev() {
+        response = json!({ "method": method.name, "result": response });
+    }
+    Ok(response)
+}
+
+/// Handle the body of a JSON-RPC request, which may be a batch of calls, this returns the body of
+/// the response, unless every call was a notification
+///
+/// This is used by servers, which answer requests with `handle`
+pub async fn handle<Req, Resp, F>(format: &dyn JsonValue<Req, Resp>, body: &[u8], handle: impl Fn(Req) -> F) -> Option<Vec<u8>>
+where
+    Req: Request,
+    F: Future<Output = Resp>,
+{
+    let response = match serde_json::from_slice(body) {
+        Ok(Value::Array(calls)) if calls.is_empty() => {
+            Some(error_object(&Value::Null, &ErrorObject::invalid_request("empty batch")))
+        }
+        Ok(Value::Array(calls)) => {
+            let responses: Vec<_> = future::join_all(calls.into_iter().map(|call| handle_call(format, call, &handle)))
+                .await
+                .into_iter()
+                .flatten()
+                .collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        Ok(call) => handle_call(format, call, &handle).await,
+        Err(error) => Some(error_object(&Value::Null, &ErrorObject::new(PARSE_ERROR, "Parse error").with_data(error.to_string()))),
+    };
+    // a value can always be written
+    response.map(|response| response.to_string().into_bytes())
+}
+
+/// Handle a single call, returning its response object unless it is a notification
+async fn handle_call<Req, Resp, F>(format: &dyn JsonValue<Req, Resp>, call: Value, handle: impl Fn(Req) -> F) -> Option<Value>
+where
+    Req: Request,
+    F: Future<Output = Resp>,
+{
+    let Value::Object(mut call) = call else {
+        return Some(error_object(&Value::Null, &ErrorObject::invalid_request("expected an object")));
+    };
+    let id = call.remove("id");
+    let result = call_method(format, call, handle).await;
+    let id = id?;
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": VERSION, "result": result, "id": id }),
+        Err(error) => error_object(&id, &error),
+    })
+}
+
+async fn call_method<Req, Resp, F>(format: &dyn JsonValue<Req, Resp>, mut call: Map<String, Value>, handle: impl Fn(Req) -> F) -> Result<Value, ErrorObject>
+where
+    Req: Request,
+    F: Future<Output = Resp>,
+{
+    if call.get("jsonrpc").and_then(Value::as_str) != Some(VERSION) {
+        return Err(ErrorObject::invalid_request("jsonrpc must be \"2.0\""));
+    }
+    let Some(Value::String(name)) = call.remove("method") else {
+        return Err(ErrorObject::invalid_request("method must be a string"));
+    };
+    let (request, path) = unflatten(&name, call.remove("params"), Req::METHODS)?;
+    if path.last().is_some_and(|method| method.streaming) {
+        return Err(ErrorObject::invalid_request("streaming methods cannot be called with JSON-RPC"));
+    }
+    let request = format
+        .read_value(request)
+        .map_err(|error| ErrorObject::invalid_params(error.to_string()))?;
+    let response = format
+        .write_value(handle(request).await)
+        .map_err(|error| ErrorObject::new(INTERNAL_ERROR, "Internal error").with_data(error.to_string()))?;
+    unwrap_response(response, &path)
+}
+
+fn error_object(id: &Value, error: &ErrorObject) -> Value {
+    json!({ "jsonrpc": VERSION, "error": error, "id": id })
+}
+
+/// A client which calls methods with JSON-RPC, over either an asynchronous or a blocking transport
+///
+/// Websocket transports are not supported
+#[derive(Debug, Copy, Clone)]
+pub struct JsonRpcClient<T> {
+    transport: T,
+}
+
+impl<T> JsonRpcClient<T> {
+    pub(crate) const fn new(transport: T) -> Self {
+        Self { transport }
+    }
+
+    /// Write the request object for a request to the end of `buffer`, returning the ID of the
+    /// request and the chain of methods which it calls
+    fn write_request<Req: Request + Serialize>(request: Req, buffer: &mut Vec<u8>) -> Result<(u64, Vec<&'static MethodInfo>), Box<dyn Error + Send>> {
+        let request = serde_json::to_value(request).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
+        let (method, params, path) = flatten(request, Req::METHODS)
+            .map_err(|error| Box::<dyn Error + Send + Sync>::from(error) as Box<dyn Error + Send>)?;
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        let request = RequestObject {
+            jsonrpc: VERSION,
+            method,
+            params,
+            id,
+        };
+        serde_json::to_writer(buffer, &request).map_err(|error| Box::new(error) as Box<dyn Error + Send>)?;
+        Ok((id, path))
+    }
+
+    /// Read the response object for a request
+    fn read_response<Resp: DeserializeOwned, E>(response: &[u8], id: u64, path: &[&MethodInfo]) -> Result<Resp, RpcError<E>> {
+        let response: ResponseObject = serde_json::from_slice(response).map_err(|error| RpcError::Deserialize(Box::new(error)))?;
+        if response.id != id {
+            let error = format!("expected a response to request {id}, received: {}", response.id);
+            return Err(RpcError::Deserialize(Box::<dyn Error + Send + Sync>::from(error)));
+        }
+        let result = response.error.map_or(Ok(response.result), Err);
+        let response = wrap_response(result, path)?;
+        serde_json::from_value(response).map_err(|error| RpcError::Deserialize(Box::new(error)))
+    }
+}
+
+impl<T, Req, Resp> AsyncClient<Req, Resp> for JsonRpcClient<T>
+where
+    T: AsyncTransport,
+    Req: Request + Serialize,
+    Resp: DeserializeOwned,
+{
+    type Error = RpcError<T::Error>;
+
+    async fn send(&self, request: Req) -> Result<Resp, Self::Error> {
+        self.send_async(request).await
+    }
+}
+
+impl<T, Req, Resp> AsyncRefClient<Req, Resp> for JsonRpcClient<T>
+where
+    T: AsyncTransport,
+    Req: Request + Serialize,
+    for<'req> Req::Ref<'req>: Serialize,
+    Resp: DeserializeOwned,
+{
+    async fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> Result<Resp, Self::Error> {
+        self.send_async(request).await
+    }
+}
+
+impl<T, Req, Resp> BlockingClient<Req, Resp> for JsonRpcClient<T>
+where
+    T: BlockingTransport,
+    Req: Request + Serialize,
+    Resp: DeserializeOwned,
+{
+    type Error = RpcError<T::Error>;
+
+    fn send(&self, request: Req) -> Result<Resp, Self::Error> {
+        self.send_blocking(request)
+    }
+}
+
+impl<T, Req, Resp> BlockingRefClient<Req, Resp> for JsonRpcClient<T>
+where
+    T: BlockingTransport,
+    Req: Request + Serialize,
+    for<'req> Req::Ref<'req>: Serialize,
+    Resp: DeserializeOwned,
+{
+    fn send_ref<'req>(&'req self, request: Req::Ref<'req>) -> Result<Resp, Self::Error> {
+        self.send_blocking(request)
+    }
+}
+
+/// The requests which are sent by the client may be either owned or borrowed, so these send any
+/// request which can be serialised
+impl<T> JsonRpcClient<T> {
+    async fn send_async<Req: Request + Serialize, Resp: DeserializeOwned>(&self, request: Req) -> Result<Resp, RpcError<T::Error>>
+    where
+        T: AsyncTransport,
+    {
+        let mut buffer = vec![0; self.transport.header_len()];
+        let (id, path) = Self::write_request(request, &mut buffer).map_err(RpcError::Serialize)?;
+        let response = self.transport.send(buffer, CONTENT_TYPE, CONTENT_TYPE).await.map_err(RpcError::Transport)?;
+        let response = response.map_err(|error| error.decode(&Json))?;
+        Self::read_response(&response, id, &path)
+    }
+
+    fn send_blocking<Req: Request + Serialize, Resp: DeserializeOwned>(&self, request: Req) -> Result<Resp, RpcError<T::Error>>
+    where
+        T: BlockingTransport,
+    {
+        let mut buffer = Vec::new();
+        let (id, path) = Self::write_request(request, &mut buffer).map_err(RpcError::Serialize)?;
+        let response = self.transport.send(buffer, CONTENT_TYPE, CONTENT_TYPE).map_err(RpcError::Transport)?;
+        let response = response.map_err(|error| error.decode(&Json))?;
+        Self::read_response(&response, id, &path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const fn method(name: &'static str, args: &'static [&'static str]) -> MethodInfo {
+        MethodInfo {
+            name,
+            aliases: &[],
+            args,
+            named: false,
+            streaming: false,
+            error: false,
+            nested: None,
+        }
+    }
+
+    static HEALTH: &[MethodInfo] = &[method("ping", &[])];
+    static USER: &[MethodInfo] = &[
+        method("get", &["field"]),
+        MethodInfo { named: true, ..method("set", &["field", "value"]) },
+    ];
+    static METHODS: &[MethodInfo] = &[
+        method("add", &["a", "b"]),
+        MethodInfo { aliases: &["sqrt"], ..method("square_root", &["x"]) },
+        MethodInfo { named: true, ..method("divide", &["a", "b"]) },
+        MethodInfo { error: true, ..method("parse", &["text"]) },
+        MethodInfo { nested: Some(HEALTH), ..method("health_service", &[]) },
+        MethodInfo { nested: Some(USER), ..method("user", &["id"]) },
+    ];
+
+    fn flat(request: Value) -> (String, Vec<Value>) {
+        let (name, params, path) = flatten(request, METHODS).unwrap();
+        assert_eq!(path.last().unwrap().name, name.rsplit('.').next().unwrap());
+        (name, params)
+    }
+
+    fn unflat(name: &str, params: Value) -> Result<Value, ErrorObject> {
+        unflatten(name, Some(params), METHODS).map(|(request, _)| request)
+    }
+
+    #[test]
+    fn positional_params() {
+        let request = json!({ "method": "add", "args": [1, 2] });
+        assert_eq!(flat(request.clone()), ("add".to_string(), vec![json!(1), json!(2)]));
+        assert_eq!(unflat("add", json!([1, 2])).unwrap(), request);
+        // a single arg is encoded as the arg itself
+        let request = json!({ "method": "square_root", "args": 4 });
+        assert_eq!(flat(request.clone()), ("square_root".to_string(), vec![json!(4)]));
+        assert_eq!(unflat("square_root", json!([4])).unwrap(), request);
+    }
+
+    #[test]
+    fn named_params() {
+        let request = json!({ "method": "divide", "args": { "a": 6, "b": 3 } });
+        assert_eq!(flat(request.clone()), ("divide".to_string(), vec![json!(6), json!(3)]));
+        assert_eq!(unflat("divide", json!([6, 3])).unwrap(), request);
+        assert_eq!(unflat("divide", json!({ "b": 3, "a": 6 })).unwrap(), request);
+        let error = unflat("divide", json!({ "a": 6, "c": 3 })).unwrap_err();
+        assert_eq!(error.code, INVALID_PARAMS);
+    }
+
+    #[test]
+    fn aliases() {
+        let request = json!({ "method": "square_root", "args": 4 });
+        assert_eq!(unflat("sqrt", json!([4])).unwrap(), request);
+        // the client always sends the name of the method
+        let (name, _) = flat(json!({ "method": "sqrt", "args": 4 }));
+        assert_eq!(name, "square_root");
+    }
+
+    #[test]
+    fn nested_methods() {
+        let request = json!({ "method": "health_service", "args": { "method": "ping", "args": [] } });
+        assert_eq!(flat(request.clone()), ("health_service.ping".to_string(), vec![]));
+        assert_eq!(unflat("health_service.ping", json!([])).unwrap(), request);
+
+        let request = json!({ "method": "user", "args": [7, { "method": "get", "args": "name" }] });
+        assert_eq!(flat(request.clone()), ("user.get".to_string(), vec![json!(7), json!("name")]));
+        assert_eq!(unflat("user.get", json!([7, "name"])).unwrap(), request);
+        assert_eq!(unflat("user.get", json!({ "id": 7, "field": "name" })).unwrap(), request);
+
+        // the args of each method in the chain are encoded separately
+        let request = json!({ "method": "user", "args": [7, { "method": "set", "args": { "field": "name", "value": "bob" } }] });
+        assert_eq!(flat(request.clone()), ("user.set".to_string(), vec![json!(7), json!("name"), json!("bob")]));
+        assert_eq!(unflat("user.set", json!([7, "name", "bob"])).unwrap(), request);
+    }
+
+    #[test]
+    fn unknown_methods() {
+        for name in ["nope", "add.nope", "user.nope", "user", "health_service.", ""] {
+            let error = unflat(name, json!([])).unwrap_err();
+            assert_eq!(error.code, METHOD_NOT_FOUND, "{name}");
+            assert_eq!(ResponseError::from(error).kind, ErrorKind::UnknownMethod);
+        }
+        assert!(flatten(json!({ "method": "nope", "args": [] }), METHODS).is_err());
+    }
+
+    #[test]
+    fn missing_params() {
+        let add = &METHODS[0];
+        let divide = &METHODS[2];
+        // missing trailing params are left for the server to default
+        assert_eq!(join_fields(vec![Some(json!(1)), None], add).unwrap(), json!([1]));
+        assert_eq!(join_fields(vec![None, Some(json!(2))], divide).unwrap(), json!({ "b": 2 }));
+        let error = join_fields(vec![None, Some(json!(2))], add).unwrap_err();
+        assert_eq!(error.code, INVALID_PARAMS);
+        let error = unflat("add", json!([1, 2, 3])).unwrap_err();
+        assert_eq!(error.code, INVALID_PARAMS);
+        let error = unflat("add", json!("1, 2")).unwrap_err();
+        assert_eq!(error.code, INVALID_REQUEST);
+    }
+
+    #[test]
+    fn application_errors() {
+        let path = [&METHODS[3]];
+        let ok = json!({ "method": "parse", "result": { "Ok": 1 } });
+        assert_eq!(unwrap_response(ok.clone(), &path).unwrap(), json!(1));
+        assert_eq!(wrap_response(Ok(json!(1)), &path).unwrap(), ok);
+
+        let err = json!({ "method": "parse", "result": { "Err": "bad number" } });
+        let error = unwrap_response(err.clone(), &path).unwrap_err();
+        assert_eq!(error.code, APPLICATION_ERROR);
+        assert_eq!(error.data, Some(json!("bad number")));
+        assert_eq!(wrap_response(Err(error), &path).unwrap(), err);
+
+        // any other error is not a response
+        let error = ErrorObject::invalid_params("missing param: text");
+        let error = wrap_response(Err(error), &path).unwrap_err();
+        assert_eq!(error.kind, ErrorKind::Deserialise);
+        assert_eq!(error.details.as_deref(), Some("missing param: text"));
+    }
+
+    #[test]
+    fn error_kinds() {
+        for (code, kind) in [
+            (PARSE_ERROR, ErrorKind::Deserialise),
+            (INVALID_REQUEST, ErrorKind::Rejected),
+            (METHOD_NOT_FOUND, ErrorKind::UnknownMethod),
+            (INVALID_PARAMS, ErrorKind::Deserialise),
+            (INTERNAL_ERROR, ErrorKind::Internal),
+            (APPLICATION_ERROR, ErrorKind::Failed),
+        ] {
+            let error = ResponseError::from(ErrorObject::new(code, "message").with_data(json!({ "a": 1 })));
+            assert_eq!(error.kind, kind);
+            assert_eq!(error.message, "message");
+            assert_eq!(error.details.as_deref(), Some(r#"{"a":1}"#));
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Provides support for the [MessagePack](https://msgpack.org/) format
+
+use crate::format::{BorrowFormat, Format, IsFormat};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use std::error::Error;
+use std::io;
+
+const CONTENT_TYPE: &str = "application/msgpack";
+
+#[derive(Debug, Copy, Clone)]
+/// [MessagePack](https://msgpack.org/)
+///
+/// Structs are written as maps with named fields, rather than as arrays, so that they can be read
+/// by consumers which are not using this crate
+pub struct MsgPack;
+
+impl IsFormat for MsgPack {
+    fn content_type(&self) -> &'static str {
+        CONTENT_TYPE
+    }
+}
+
+impl<Read, Write> Format<Read, Write> for MsgPack
+where Read: DeserializeOwned, Write: Serialize
+{
+    fn read(&self, reader: &[u8]) -> Result<Read, Box<dyn Error + Send>> {
+        rmp_serde::from_slice(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        rmp_serde::from_slice(&bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        rmp_serde::to_vec_named(&value).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        rmp_serde::encode::write_named(writer, &value).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+}
+
+impl<'de, Read: Deserialize<'de>> BorrowFormat<'de, Read> for MsgPack {
+    fn read_borrowed(&self, reader: &'de [u8]) -> Result<Read, Box<dyn Error + Send>> {
+        rmp_serde::from_slice(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{ErrorKind, ResponseError};
+    use crate::rpc;
+    use std::fmt::Debug;
+
+    #[rpc(crate = crate, derive(Clone, PartialEq, Eq))]
+    trait Api {
+        fn users(&self) -> impl Users;
+        fn login(&self, username: String, password: String) -> Option<u64>;
+    }
+
+    #[rpc(crate = crate, derive(Clone, PartialEq, Eq))]
+    trait Users {
+        fn by_id(&self, id: u64) -> String;
+        fn list(&self) -> Vec<String>;
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned + Clone + PartialEq + Debug>(value: &T) -> Vec<u8> {
+        let bytes = Format::<T, T>::write(&MsgPack, value.clone()).unwrap();
+        assert_eq!(&Format::<T, T>::read(&MsgPack, &bytes).unwrap(), value);
+        assert_eq!(&Format::<T, T>::read_bytes(&MsgPack, Bytes::from(bytes.clone())).unwrap(), value);
+        bytes
+    }
+
+    fn contains(bytes: &[u8], key: &str) -> bool {
+        bytes.windows(key.len()).any(|window| window == key.as_bytes())
+    }
+
+    #[test]
+    fn requests() {
+        let bytes = round_trip(&api::Request::Login("user".to_string(), "password".to_string()));
+        // requests are tagged by the name of the method, with their args alongside it
+        assert!(contains(&bytes, "method") && contains(&bytes, "args") && contains(&bytes, "login"));
+        let bytes = round_trip(&api::Request::Users(users::Request::ById(7)));
+        assert!(contains(&bytes, "users") && contains(&bytes, "by_id"));
+        round_trip(&api::Request::Users(users::Request::List()));
+    }
+
+    #[test]
+    fn responses() {
+        let bytes = round_trip(&api::Response::Login(Some(1)));
+        assert!(contains(&bytes, "method") && contains(&bytes, "result") && contains(&bytes, "login"));
+        round_trip(&api::Response::Login(None));
+        round_trip(&api::Response::Users(users::Response::List(vec!["a".to_string(), "b".to_string()])));
+    }
+
+    #[test]
+    fn errors() {
+        round_trip(&ResponseError::new(ErrorKind::UnknownMethod, "Unknown method"));
+        round_trip(
+            &ResponseError::new(ErrorKind::Rejected, "Too many requests")
+                .with_method("login")
+                .with_details("try again later"),
+        );
+    }
+}
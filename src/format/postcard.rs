@@ -0,0 +1,104 @@
+//! Provides support for the [postcard](https://postcard.jamesmunns.com/) format
+//!
+//! Postcard is not self-describing, so it cannot read the method names which identify requests
+//! and responses by default, services must be declared with `#[rpc(variant_index)]` to be used
+//! with this format
+
+use crate::format::{BorrowFormat, Format, IsFormat};
+use bytes::Bytes;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+use std::error::Error;
+use std::io;
+
+const CONTENT_TYPE: &str = "application/x-postcard";
+
+#[derive(Debug, Copy, Clone)]
+/// [postcard](https://postcard.jamesmunns.com/), a compact binary format for constrained environments
+pub struct Postcard;
+
+impl IsFormat for Postcard {
+    fn content_type(&self) -> &'static str {
+        CONTENT_TYPE
+    }
+}
+
+impl<Read, Write> Format<Read, Write> for Postcard
+where Read: DeserializeOwned, Write: Serialize
+{
+    fn read(&self, reader: &[u8]) -> Result<Read, Box<dyn Error + Send>> {
+        postcard::from_bytes(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        postcard::from_bytes(&bytes).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        postcard::to_allocvec(&value).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        postcard::to_io(&value, writer)
+            .map(drop)
+            .map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+}
+
+impl<'de, Read: Deserialize<'de>> BorrowFormat<'de, Read> for Postcard {
+    fn read_borrowed(&self, reader: &'de [u8]) -> Result<Read, Box<dyn Error + Send>> {
+        postcard::from_bytes(reader).map_err(|error| Box::new(error) as Box<dyn Error + Send>)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{ErrorKind, ResponseError};
+    use crate::rpc;
+    use std::fmt::Debug;
+
+    #[rpc(crate = crate, variant_index, derive(Clone, PartialEq, Eq))]
+    trait Sensor {
+        fn temperature(&self) -> i16;
+        fn set_interval(&self, seconds: u16, label: Option<String>);
+    }
+
+    /// The same service, identified by the names of its methods
+    #[rpc(crate = crate, derive(Clone, PartialEq, Eq))]
+    trait NamedSensor {
+        fn temperature(&self) -> i16;
+        fn set_interval(&self, seconds: u16, label: Option<String>);
+    }
+
+    fn round_trip<T: Serialize + DeserializeOwned + Clone + PartialEq + Debug>(value: &T) {
+        let bytes = Format::<T, T>::write(&Postcard, value.clone()).unwrap();
+        assert_eq!(&Format::<T, T>::read(&Postcard, &bytes).unwrap(), value);
+        assert_eq!(&Format::<T, T>::read_bytes(&Postcard, Bytes::from(bytes.clone())).unwrap(), value);
+        let mut written = Vec::new();
+        Format::<T, T>::write_to(&Postcard, value.clone(), &mut written).unwrap();
+        assert_eq!(written, bytes);
+    }
+
+    #[test]
+    fn variant_index() {
+        round_trip(&sensor::Request::Temperature());
+        round_trip(&sensor::Request::SetInterval(10, Some("kitchen".to_string())));
+        round_trip(&sensor::Response::Temperature(-5));
+        round_trip(&sensor::Response::SetInterval(()));
+        round_trip(&ResponseError::new(ErrorKind::Rejected, "Too many requests").with_details("try again later"));
+
+        // the method is identified by a single byte
+        let bytes = Format::<sensor::Request, sensor::Request>::write(&Postcard, sensor::Request::Temperature()).unwrap();
+        assert_eq!(bytes, [0]);
+    }
+
+    #[test]
+    fn method_names() {
+        // requests which are identified by name can be written, but not read
+        let request = named_sensor::Request::SetInterval(10, None);
+        let bytes = Format::<named_sensor::Request, named_sensor::Request>::write(&Postcard, request).unwrap();
+        assert!(Format::<named_sensor::Request, named_sensor::Request>::read(&Postcard, &bytes).is_err());
+    }
+}
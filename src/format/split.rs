@@ -0,0 +1,66 @@
+//! Provides a format which sends requests in one format and asks for responses in another
+
+use crate::format::{BorrowFormat, Format, IsFormat};
+use bytes::Bytes;
+use std::error::Error;
+use std::io;
+
+/// Writes requests with the `Request` format and reads responses with the `Response` format
+///
+/// The server must support both formats, the response format is requested with the `Accept`
+/// header. This is useful when a format is cheap to produce but expensive to receive (eg: a browser
+/// which posts JSON, but receives large responses as CBOR)
+#[derive(Debug, Copy, Clone)]
+pub struct Split<Request, Response> {
+    request: Request,
+    response: Response,
+}
+
+impl<Request, Response> Split<Request, Response> {
+    /// Create a new format which writes requests with `request` and reads responses with `response`
+    pub const fn new(request: Request, response: Response) -> Self {
+        Self { request, response }
+    }
+}
+
+impl<Request: IsFormat, Response: IsFormat> IsFormat for Split<Request, Response> {
+    fn content_type(&self) -> &'static str {
+        self.request.content_type()
+    }
+
+    fn accept(&self) -> &'static str {
+        self.response.content_type()
+    }
+}
+
+impl<Read, Write, Request, Response> Format<Read, Write> for Split<Request, Response>
+where
+    Request: Format<Read, Write>,
+    Response: Format<Read, Write>,
+{
+    fn read(&self, reader: &[u8]) -> Result<Read, Box<dyn Error + Send>> {
+        self.response.read(reader)
+    }
+
+    fn write(&self, value: Write) -> Result<Vec<u8>, Box<dyn Error + Send>> {
+        self.request.write(value)
+    }
+
+    fn write_to(&self, value: Write, writer: &mut dyn io::Write) -> Result<(), Box<dyn Error + Send>> {
+        self.request.write_to(value, writer)
+    }
+
+    fn read_bytes(&self, bytes: Bytes) -> Result<Read, Box<dyn Error + Send>> {
+        self.response.read_bytes(bytes)
+    }
+}
+
+impl<'de, Read, Request, Response> BorrowFormat<'de, Read> for Split<Request, Response>
+where
+    Request: IsFormat + Send + Sync,
+    Response: BorrowFormat<'de, Read>,
+{
+    fn read_borrowed(&self, reader: &'de [u8]) -> Result<Read, Box<dyn Error + Send>> {
+        self.response.read_borrowed(reader)
+    }
+}
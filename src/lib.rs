@@ -7,6 +7,13 @@ pub use futures;
 pub mod server;
 pub mod client;
 pub mod format;
+#[doc(hidden)]
+pub mod borrow;
+#[doc(hidden)]
+pub mod de;
+#[cfg(any(feature = "axum", feature = "reqwest", feature = "browser"))]
+#[allow(dead_code, reason = "the server only encodes and the client only decodes, so some items are unused with only one enabled")]
+mod delimited;
 
 pub use macros::rpc;
 pub use crate::client::{AsyncTransport, BlockingTransport, MappedClient, RpcError};
@@ -21,9 +28,13 @@ pub trait Rpc: Sized {
     /// This is the blocking client type used for accessing the RPC service
     type BlockingClient<T: BlockingClient<Self::Request, Self::Response>>;
     /// This is the request type accepted by the service
-    type Request: Request + 'static;
+    type Request: Request + Send + 'static;
+    /// This is the request type read by the server, which may borrow from the bytes that it was
+    /// read from. It is the same as [Request](Self::Request) unless the service is declared with
+    /// `#[rpc(borrow)]`
+    type BorrowedRequest<'req>: Request + Into<Self::Request> + Send;
     /// This is the response type returned by the service
-    type Response: 'static;
+    type Response: Response + 'static;
 
     /// Create a new asynchronous client, using the given underlying transport, if you wish to re-use the
     /// client for multiple calls, ensure you pass a copyable transport (eg: a reference)
@@ -47,29 +58,119 @@ pub trait RpcWithServer<Server>: Rpc {
 
 /// Defines a RPC request
 pub trait Request {
+    /// The methods of the service which this request calls, in the order that they are declared
+    const METHODS: &'static [MethodInfo];
+    /// The request which the client sends when it borrows arguments from the caller rather than
+    /// owning them, it is encoded the same as this request. This is the same as this request unless
+    /// a method takes a reference or calls a nested service
+    type Ref<'req>: Request;
     /// Returns true if this request has a streaming response
     fn is_streaming_response(&self) -> bool;
+    /// The name of the method which this request calls
+    fn method_name(&self) -> &'static str;
+}
+
+/// Describes a method of a service, this is used by protocols which do not encode requests and
+/// responses as they are declared, such as JSON-RPC
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MethodInfo {
+    /// The name of the method, as it is encoded
+    pub name: &'static str,
+    /// The other names which the method is also decoded from, as declared with
+    /// `#[rpc(alias = ...)]`
+    pub aliases: &'static [&'static str],
+    /// The names of the arguments of the method, this does not include the request for a nested
+    /// service
+    pub args: &'static [&'static str],
+    /// True if the arguments are encoded as a map keyed by their names, as declared with
+    /// `#[rpc(named_args)]`, in which case the request for a nested service is named `request`
+    pub named: bool,
+    /// True if the method has a streaming response
+    pub streaming: bool,
+    /// True if the method may return an application error, as declared with `#[rpc(error = ...)]`
+    pub error: bool,
+    /// The methods of the nested service which is returned by this method, if it returns one
+    ///
+    /// The methods inherited from a supertrait are also described as a nested service, which is
+    /// named after the supertrait
+    pub nested: Option<&'static [Self]>,
+}
+
+/// Defines a RPC response
+pub trait Response {
+    /// Returns true if this response carries an application error, as declared with
+    /// `#[rpc(error = ...)]`
+    fn is_error(&self) -> bool;
 }
 
 #[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
-/// Build a request/response from a request ID and a payload. Useful for implementing transport
-/// protocols that share a single connection for many concurrent requests
-fn prepend_id(request_id:u32, payload: Vec<u8>) -> Vec<u8> {
-    let mut bytes = Vec::with_capacity(payload.len() + 4);
-    bytes.extend(request_id.to_le_bytes());
-    bytes.extend(payload);
-    bytes
+/// The HTTP status used for a response which carries an application error, the body is still an
+/// encoded response
+const APPLICATION_ERROR_STATUS: u16 = 422;
+
+#[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+/// The kind of a frame sent over a transport protocol which shares a single connection for many
+/// concurrent requests
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u8)]
+enum FrameKind {
+    /// A request from the client, or a response (or a single item of a streamed response) from
+    /// the server
+    Message = 0,
+    /// The server has finished sending a streamed response
+    End = 1,
+    /// The server rejected the request, the payload is a [`ResponseError`](client::ResponseError)
+    /// encoded in the format of the connection
+    Rejected = 2,
+    /// The server failed while producing a response, the payload is a
+    /// [`ResponseError`](client::ResponseError) encoded in the format of the connection
+    Failed = 3,
+    /// The client is no longer interested in the response, the server should stop handling the
+    /// request
+    Cancel = 4,
 }
 
 #[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
-/// Get request id and payload from the given request/response. Useful for implementing transport
-/// protocols that share a single connection for many concurrent requests
-fn get_request_id(request: &[u8]) -> (u32, &[u8]) {
-    let request_id = u32::from_le_bytes([
-        request[0],
-        request[1],
-        request[2],
-        request[3],
-    ]);
-    (request_id, &request[4..])
+/// The length of the header at the start of each frame, the request ID followed by the frame kind
+const HEADER_LEN: usize = 5;
+
+#[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+/// Build a frame from a request ID and frame kind, the payload can then be written to the end of
+/// it. Useful for implementing transport protocols that share a single connection for many
+/// concurrent requests
+fn new_frame(request_id: u32, kind: FrameKind) -> Vec<u8> {
+    let mut frame = vec![0; HEADER_LEN];
+    write_header(&mut frame, request_id, kind);
+    frame
 }
+
+#[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+/// Write the header of a frame into the first [`HEADER_LEN`] bytes of `frame`, which must have
+/// been reserved for it
+fn write_header(frame: &mut [u8], request_id: u32, kind: FrameKind) {
+    frame[..4].copy_from_slice(&request_id.to_le_bytes());
+    frame[4] = kind as u8;
+}
+
+#[allow(dead_code, reason = "only using in certain features, but better to leave it open")]
+/// Get request id, frame kind and payload from the given frame, returns `None` if the frame is
+/// malformed. Useful for implementing transport protocols that share a single connection for many
+/// concurrent requests
+fn get_header(frame: &[u8]) -> Option<(u32, FrameKind, &[u8])> {
+    let [a, b, c, d, kind, payload @ ..] = frame else {
+        return None;
+    };
+    let request_id = u32::from_le_bytes([*a, *b, *c, *d]);
+    let kind = match kind {
+        0 => FrameKind::Message,
+        1 => FrameKind::End,
+        2 => FrameKind::Rejected,
+        3 => FrameKind::Failed,
+        4 => FrameKind::Cancel,
+        _ => return None,
+    };
+    Some((request_id, kind, payload))
+}
+
+#[cfg(all(test, feature = "json", feature = "cbor", feature = "msgpack"))]
+mod tests;
@@ -20,6 +20,14 @@ pub trait Handler: Send {
         &self,
         request: <Self::Rpc as Rpc>::Request,
     ) -> impl Future<Output = <Self::Rpc as Rpc>::Response> + Send;
+    /// takes a request which borrows from the bytes it was read from and returns a response, the
+    /// request is converted to an owned request and passed to [handle](Self::handle) by default
+    fn handle_borrowed(
+        &self,
+        request: <Self::Rpc as Rpc>::BorrowedRequest<'_>,
+    ) -> impl Future<Output = <Self::Rpc as Rpc>::Response> + Send {
+        self.handle(request.into())
+    }
     /// takes the request and returns a response, see [trait documentation](Self) for details
     fn handle_stream_response<S: Sink<<Self::Rpc as Rpc>::Response, Error = Infallible> + Send + 'static>(
         &self,
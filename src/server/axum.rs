@@ -1,24 +1,34 @@
 #[allow(unused_imports, reason = "only used if certain features are enabled")]
 use crate::format;
-use crate::format::{Format, IsFormat};
-use crate::{Handler, Rpc, get_request_id, prepend_id};
+use crate::client::{ErrorKind, ResponseError};
+use crate::format::{BorrowFormat, Format};
+#[cfg(feature = "json")]
+use crate::format::jsonrpc::JsonValue;
+use crate::delimited::{EVENT_STREAM_CONTENT_TYPE, Framing};
+use crate::{APPLICATION_ERROR_STATUS, FrameKind, Handler, Request as _, Response as _, Rpc, de, get_header, new_frame};
 use axum::RequestExt;
-use axum::body::Bytes;
+use axum::body::{Body, Bytes};
 use axum::extract::ws::{Message, WebSocket};
 use axum::extract::{ConnectInfo, FromRequest, FromRequestParts, Request, WebSocketUpgrade};
-use axum::http::header::CONTENT_TYPE;
+use axum::http::header::{ACCEPT, CACHE_CONTROL, CONTENT_TYPE};
 use axum::http::{Method, StatusCode};
 use axum::response::{IntoResponse, Response};
 use bon::__::IsUnset;
 use bon::Builder;
-use futures::FutureExt;
-use futures::future::BoxFuture;
+use futures::channel::mpsc;
+use futures::future::{self, AbortHandle, BoxFuture, abortable};
+use futures::stream::{self, FuturesUnordered};
+use futures::{FutureExt, Sink, SinkExt, StreamExt, select};
+use std::collections::{HashMap, VecDeque};
 use std::convert::Infallible;
 use std::marker::PhantomData;
 use std::net::SocketAddr;
+use std::num::NonZeroUsize;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::task::{Context, Poll};
 use tower::Service;
-use tracing::{Instrument, debug, info, info_span};
+use tracing::{Instrument, debug, info, info_span, warn};
 use crate::server::axum::axum_builder::{SetRpc, SetServer};
 use crate::server::IntoHandler;
 
@@ -42,6 +52,24 @@ where
     state: State,
     #[builder(default)]
     enable_websockets: bool,
+    /// Answer streaming requests with [server-sent events](https://html.spec.whatwg.org/multipage/server-sent-events.html)
+    /// when the client accepts `text/event-stream`, each response is sent as an `item` event,
+    /// followed by an `end` or `error` event. Only formats which write UTF-8 (eg: JSON) can be
+    /// carried by events
+    #[builder(default)]
+    enable_sse: bool,
+    /// The maximum number of requests which are handled concurrently on a single websocket
+    /// connection, further requests are queued until an earlier request has finished. A streaming
+    /// request holds its slot only until its stream has started, unlimited by default
+    max_concurrent_requests: Option<NonZeroUsize>,
+    /// The maximum number of streams which may be open at once on a single websocket connection,
+    /// further streaming requests are rejected until an earlier stream has ended, unlimited by
+    /// default
+    max_concurrent_streams: Option<NonZeroUsize>,
+    /// The maximum number of requests which may be queued on a single websocket connection while
+    /// `max_concurrent_requests` are being handled, further requests are rejected
+    #[builder(default = 64)]
+    max_queued_requests: usize,
 }
 
 impl<R, Server, State> Clone for Axum<R, Server, State>
@@ -59,6 +87,10 @@ where
             server: PhantomData,
             state: self.state.clone(),
             enable_websockets: self.enable_websockets,
+            enable_sse: self.enable_sse,
+            max_concurrent_requests: self.max_concurrent_requests,
+            max_concurrent_streams: self.max_concurrent_streams,
+            max_queued_requests: self.max_queued_requests,
         }
     }
 }
@@ -89,12 +121,35 @@ where
         self.server_type(PhantomData)
     }
 
-    /// Add a format to support
+    /// Add a format to support, errors are encoded in the same format as the request
     pub fn format(
         mut self,
-        format: &'static impl for<'a> Format<RpcRequest<R>, RpcResponse<R>>,
+        format: &'static (impl for<'a> Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError>),
+    ) -> Self {
+        self.formats.push(RpcFormat {
+            rpc: format,
+            errors: format,
+            borrowed: None,
+            #[cfg(feature = "json")]
+            json_rpc: None,
+        });
+        self
+    }
+
+    /// Add a format to support, requests are read borrowing from the request body, so that
+    /// services declared with `#[rpc(borrow)]` do not copy their borrowed arguments. Errors are
+    /// encoded in the same format as the request
+    pub fn borrowing_format(
+        mut self,
+        format: &'static (impl Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError> + for<'de> BorrowFormat<'de, RpcBorrowedRequest<'de, R>>),
     ) -> Self {
-        self.formats.push(format);
+        self.formats.push(RpcFormat {
+            rpc: format,
+            errors: format,
+            borrowed: Some(format),
+            #[cfg(feature = "json")]
+            json_rpc: None,
+        });
         self
     }
 
@@ -108,20 +163,59 @@ where
     #[cfg(feature = "json")]
     pub fn allow_json(self) -> Self
     where
-        format::json::Json: for<'a> Format<RpcRequest<R>, RpcResponse<R>>,
+        format::json::Json: Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError> + for<'de> BorrowFormat<'de, RpcBorrowedRequest<'de, R>>,
     {
-        self.format(&format::json::Json)
+        self.borrowing_format(&format::json::Json)
+    }
+
+    /// Add [JSON-RPC 2.0](format::jsonrpc) support to this server, errors which occur before the
+    /// request is read are encoded as they are for [JSON](Self::allow_json)
+    ///
+    /// This has the same Content-Type as JSON, so the two should be served on different routes.
+    /// Websockets are not supported
+    #[cfg(feature = "json")]
+    pub fn allow_json_rpc(mut self) -> Self
+    where
+        format::json::Json: Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError>,
+        format::jsonrpc::JsonRpc: JsonValue<RpcRequest<R>, RpcResponse<R>>,
+    {
+        self.formats.push(RpcFormat {
+            rpc: &format::json::Json,
+            errors: &format::json::Json,
+            borrowed: None,
+            json_rpc: Some(&format::jsonrpc::JsonRpc),
+        });
+        self
     }
 
     /// Add CBOR support to this server
     #[cfg(feature = "cbor")]
     pub fn allow_cbor(self) -> Self
     where
-        format::cbor::Cbor: for<'a> Format<RpcRequest<R>, RpcResponse<R>>,
+        format::cbor::Cbor: for<'a> Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError>,
     {
         self.format(&format::cbor::Cbor)
     }
 
+    /// Add [MessagePack](https://msgpack.org/) support to this server
+    #[cfg(feature = "msgpack")]
+    pub fn allow_msgpack(self) -> Self
+    where
+        format::msgpack::MsgPack: Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError> + for<'de> BorrowFormat<'de, RpcBorrowedRequest<'de, R>>,
+    {
+        self.borrowing_format(&format::msgpack::MsgPack)
+    }
+
+    /// Add [postcard](https://postcard.jamesmunns.com/) support to this server, the service must
+    /// be declared with `#[rpc(variant_index)]`
+    #[cfg(feature = "postcard")]
+    pub fn allow_postcard(self) -> Self
+    where
+        format::postcard::Postcard: Format<RpcRequest<R>, RpcResponse<R>> + Format<ResponseError, ResponseError> + for<'de> BorrowFormat<'de, RpcBorrowedRequest<'de, R>>,
+    {
+        self.borrowing_format(&format::postcard::Postcard)
+    }
+
     /// Allow POST requests
     pub fn allow_post(self) -> Self {
         self.method(Method::POST)
@@ -138,11 +232,105 @@ where
     }
 }
 
-type Formats<R> = Vec<&'static dyn Format<<R as Rpc>::Request, <R as Rpc>::Response>>;
-type RpcFormat<H> = &'static dyn Format<RpcRequest<H>, RpcResponse<H>>;
+type Formats<R> = Vec<RpcFormat<R>>;
 type RpcRequest<R> = <R as Rpc>::Request;
+type RpcBorrowedRequest<'req, R> = <R as Rpc>::BorrowedRequest<'req>;
 type RpcResponse<R> = <R as Rpc>::Response;
 
+/// A format supported by the server, which is used for both the requests and responses of the
+/// RPC and for any errors
+struct RpcFormat<R: Rpc + 'static> {
+    rpc: &'static dyn Format<RpcRequest<R>, RpcResponse<R>>,
+    errors: &'static dyn Format<ResponseError, ResponseError>,
+    /// Reads requests borrowing from the bytes they were received in, if the format can
+    borrowed: Option<&'static dyn for<'de> BorrowFormat<'de, RpcBorrowedRequest<'de, R>>>,
+    /// Handles requests with JSON-RPC, rather than as they are declared, if this is the JSON-RPC
+    /// format
+    #[cfg(feature = "json")]
+    json_rpc: Option<&'static dyn JsonValue<RpcRequest<R>, RpcResponse<R>>>,
+}
+
+impl<R: Rpc + 'static> Clone for RpcFormat<R> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<R: Rpc + 'static> Copy for RpcFormat<R> {}
+
+impl<R: Rpc + 'static> RpcFormat<R> {
+    fn content_type(self) -> &'static str {
+        self.rpc.content_type()
+    }
+
+    /// Returns true if requests can be sent with this format over a websocket
+    const fn supports_websockets(self) -> bool {
+        #[cfg(feature = "json")]
+        if self.json_rpc.is_some() {
+            return false;
+        }
+        true
+    }
+
+    /// Encode an error, falling back to a UTF-8 message if it cannot be encoded
+    fn write_error(self, error: ResponseError) -> Vec<u8> {
+        let message = plain_text(&error);
+        self.errors.write(error).unwrap_or_else(|write_error| {
+            warn!("Failed to encode error response: {write_error}");
+            message.into_bytes()
+        })
+    }
+
+    /// Read a request, borrowing from `bytes` if this format can
+    fn read_request<Server>(self, bytes: &Bytes) -> Result<ServerRequest<'_, R>, Error<Server>> {
+        let (request, unknown_method) = de::catch_unknown_method(|| {
+            self.borrowed.map_or_else(
+                || self.rpc.read_bytes(bytes.clone()).map(ServerRequest::Owned),
+                |format| format.read_borrowed(bytes).map(ServerRequest::Borrowed),
+            )
+        });
+        request.map_err(|error| {
+            if unknown_method {
+                Error::UnknownMethod(error.to_string())
+            } else {
+                Error::Deserialise(error.to_string())
+            }
+        })
+    }
+}
+
+/// A request read by the server, which borrows from the request body if the format supports it
+enum ServerRequest<'req, R: Rpc> {
+    Owned(RpcRequest<R>),
+    Borrowed(RpcBorrowedRequest<'req, R>),
+}
+
+impl<R: Rpc> ServerRequest<'_, R> {
+    fn method_name(&self) -> &'static str {
+        match self {
+            Self::Owned(request) => request.method_name(),
+            Self::Borrowed(request) => request.method_name(),
+        }
+    }
+
+    /// Take the request if it has a streaming response, streams are always handled with an owned
+    /// request since they outlive the request body
+    fn into_streaming(self) -> Result<RpcRequest<R>, Self> {
+        match self {
+            Self::Owned(request) if request.is_streaming_response() => Ok(request),
+            Self::Borrowed(request) if request.is_streaming_response() => Ok(request.into()),
+            request => Err(request),
+        }
+    }
+
+    async fn handle(self, handler: &(impl Handler<Rpc = R> + Sync)) -> RpcResponse<R> {
+        match self {
+            Self::Owned(request) => handler.handle(request).await,
+            Self::Borrowed(request) => handler.handle_borrowed(request).await,
+        }
+    }
+}
+
 impl<R, Server, State> Service<Request> for Axum<R, Server, State>
 where
     R: Rpc + 'static,
@@ -177,6 +365,12 @@ where
         let methods = self.methods.clone();
         let formats = self.formats.clone();
         let state = self.state.clone();
+        let enable_sse = self.enable_sse;
+        let limits = WebsocketLimits {
+            requests: self.max_concurrent_requests.map_or(usize::MAX, NonZeroUsize::get),
+            streams: self.max_concurrent_streams.map_or(usize::MAX, NonZeroUsize::get),
+            queued: self.max_queued_requests,
+        };
         async move {
             let server: Server = req.extract_parts_with_state(&state).await.map_err(Error::LoadServer)?;
             let handler = server.into_handler();
@@ -187,7 +381,8 @@ where
                 let protocols: Vec<_> = formats
                     .iter()
                     .copied()
-                    .map(IsFormat::content_type)
+                    .filter(|format| format.supports_websockets())
+                    .map(RpcFormat::content_type)
                     .collect();
                 ws = ws.protocols(protocols.clone());
                 let protocol = ws
@@ -195,56 +390,145 @@ where
                     .ok_or_else(|| Error::UnsupportedSubprotocol(protocols.clone()))?;
                 let format = formats
                     .iter()
-                    .find(|format| format.content_type() == protocol)
+                    .copied()
+                    .find(|format| format.supports_websockets() && format.content_type() == protocol)
                     .ok_or(Error::UnsupportedSubprotocol(protocols))?;
-                let format: RpcFormat<R> = *format;
                 return Ok(ws.on_upgrade(move |socket|
-                    Self::handle_websocket(socket, format, handler).instrument(
+                    Self::handle_websocket(socket, format, handler, limits).instrument(
                         info_span!(target: "websocket", "Websocket connection", address = addr.to_string())
                     )
                 ));
             }
+            // once the response format is known, any error is encoded in it
+            let request_formats = request_format(&formats, &req).and_then(|request_format| {
+                Ok((request_format, response_format(&formats, &req, request_format)?))
+            });
             if !methods.contains(req.method()) {
-                return Err(Error::WrongMethod);
+                return request_formats.map_or_else(
+                    |_| Err(Error::WrongMethod),
+                    |(_, format)| encoded_error(format, Error::WrongMethod),
+                );
             }
-            let content_type = req
-                .headers()
-                .get(CONTENT_TYPE)
-                .ok_or(Error::NoContentType)?;
-            let content_type = content_type
-                .to_str()
-                .map_err(|error| Error::Internal(error.to_string()))?;
-            let content_type = content_type.split(';').next().unwrap_or(content_type);
-            let format = formats
-                .iter()
-                .find(|format| format.content_type() == content_type)
-                .ok_or(Error::UnsupportedContentType)?;
-            let bytes = Bytes::from_request(req, &())
+            let (request_format, format) = request_formats?;
+            #[cfg(feature = "json")]
+            if let Some(json_rpc) = request_format.json_rpc {
+                return Self::handle_json_rpc(req, request_format.content_type(), json_rpc, handler)
+                    .await
+                    .or_else(|error| encoded_error(format, error));
+            }
+            let framing = if enable_sse && accepts_event_stream(&req) {
+                Framing::EventStream
+            } else {
+                Framing::for_format(format.content_type())
+            };
+            Self::handle_http(req, request_format, format, framing, handler)
                 .await
-                .map_err(|error| Error::Internal(error.to_string()))?;
-            let request = format
-                .read(&bytes)
-                .map_err(|error| Error::Deserialise(error.to_string()))?;
-            let response = handler.handle(request).await;
-            let response = format
-                .write(response)
-                .map_err(|error| Error::Serialise(error.to_string()))?;
-            Ok((
-                StatusCode::OK,
-                [(CONTENT_TYPE, format.content_type())],
-                response,
-            )
-                .into_response())
+                .or_else(|error| encoded_error(format, error))
         }
     }
 
+    /// Handle a plain HTTP request, which is read with `request_format` and answered with `format`
+    async fn handle_http(
+        req: Request,
+        request_format: RpcFormat<R>,
+        format: RpcFormat<R>,
+        framing: Framing,
+        handler: <Server as IntoHandler<R>>::Handler,
+    ) -> Result<Response, Error<<Server as FromRequestParts<State>>::Rejection>> {
+        let bytes = Bytes::from_request(req, &())
+            .await
+            .map_err(|error| Error::Internal(error.to_string()))?;
+        let request = request_format.read_request(&bytes)?;
+        let request = match request.into_streaming() {
+            Ok(request) => return Ok(Self::stream_body(format, framing, handler, request)),
+            Err(request) => request,
+        };
+        let method = request.method_name();
+        let response = request.handle(&handler).await;
+        let status = if response.is_error() {
+            StatusCode::from_u16(APPLICATION_ERROR_STATUS).expect("status code is valid")
+        } else {
+            StatusCode::OK
+        };
+        let mut body = Vec::new();
+        format
+            .rpc
+            .write_to(response, &mut body)
+            .map_err(|error| Error::Serialise { method, error: error.to_string() })?;
+        Ok((
+            status,
+            [(CONTENT_TYPE, format.content_type())],
+            body,
+        )
+            .into_response())
+    }
+
+    /// Handle a JSON-RPC request, any error in handling the calls is sent in the body with a
+    /// successful status
+    #[cfg(feature = "json")]
+    async fn handle_json_rpc(
+        req: Request,
+        content_type: &'static str,
+        json_rpc: &'static dyn JsonValue<RpcRequest<R>, RpcResponse<R>>,
+        handler: <Server as IntoHandler<R>>::Handler,
+    ) -> Result<Response, Error<<Server as FromRequestParts<State>>::Rejection>> {
+        let bytes = Bytes::from_request(req, &())
+            .await
+            .map_err(|error| Error::Internal(error.to_string()))?;
+        let response = format::jsonrpc::handle(json_rpc, &bytes, |request| handler.handle(request)).await;
+        // every call was a notification
+        let Some(body) = response else {
+            return Ok(StatusCode::NO_CONTENT.into_response());
+        };
+        Ok((
+            StatusCode::OK,
+            [(CONTENT_TYPE, content_type)],
+            body,
+        )
+            .into_response())
+    }
+
+    /// Respond to a streaming request over plain HTTP, each response is written to a chunked body
+    /// as soon as it is produced, delimited according to the given [Framing]
+    fn stream_body(
+        format: RpcFormat<R>,
+        framing: Framing,
+        handler: <Server as IntoHandler<R>>::Handler,
+        request: RpcRequest<R>,
+    ) -> Response {
+        let (sender, receiver) = mpsc::unbounded();
+        let sink = BodySink::<R> {
+            method: request.method_name(),
+            format,
+            framing,
+            sender,
+            failed: false,
+        };
+        // the handler is driven by polling the body, so it is dropped if the client disconnects
+        let handler = async move { handler.handle_stream_response(request, sink).await }
+            .into_stream()
+            .filter_map(|()| future::ready(None));
+        let body = stream::select(handler, receiver.map(Ok::<_, Infallible>));
+        (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, framing.content_type(format.content_type())),
+                (CACHE_CONTROL, "no-cache"),
+            ],
+            Body::from_stream(body),
+        )
+            .into_response()
+    }
+
     async fn handle_websocket(
-        mut socket: WebSocket,
-        format: &'static dyn Format<RpcRequest<R>, RpcResponse<R>>,
+        socket: WebSocket,
+        format: RpcFormat<R>,
         handler: <Server as IntoHandler<R>>::Handler,
+        limits: WebsocketLimits,
     ) {
         info!("Started websocket connection");
-        if socket
+        let (mut socket_sender, mut socket_receiver) = socket.split();
+        if socket_sender
             .send(Message::Ping(Bytes::from_static(&[1, 2, 3])))
             .await
             .is_err()
@@ -254,42 +538,93 @@ where
         }
         debug!("Sent ping message");
 
+        // streamed responses are written to this channel so that they can be sent while other
+        // requests are being handled
+        let (sender, mut outgoing) = mpsc::unbounded();
+        // the tasks which have started streaming, these no longer count towards the request limit
+        let (started, mut streams_started) = mpsc::unbounded();
+        let streams = OpenStreams {
+            open: AtomicUsize::new(0),
+            max: limits.streams,
+            started,
+        };
+        // requests which are currently being handled, these are aborted if the client cancels them
+        let mut in_flight: HashMap<u32, AbortHandle> = HashMap::new();
+        let mut tasks = FuturesUnordered::new();
+        // requests which have been received but are waiting for other requests to finish
+        let mut queued: VecDeque<(u32, BoxFuture<'_, Option<Message>>)> = VecDeque::new();
         loop {
-            let Some(msg) = socket.recv().await else {
-                info!("Websocket disconnected abruptly");
-                return;
-            };
-            let msg = match msg {
-                Ok(msg) => msg,
-                Err(error) => {
-                    info!("Websocket disconnected with error: {error}");
-                    return;
-                }
-            };
-            let response = match msg {
-                Message::Text(_) => Some(Message::Text("text frames not supported".into())),
-                Message::Binary(bytes) => {
-                    Some(match Self::handle_request(format, &bytes, &handler).await {
-                        Ok(msg) => Message::Binary(msg.into()),
-                        Err(error) => Message::Text(error.into()),
-                    })
-                }
-                Message::Ping(bytes) => Some(Message::Pong(bytes)),
-                Message::Pong(_) => None,
-                Message::Close(frame) => {
-                    if let Some(frame) = frame {
-                        info!(
-                            "Websocket connection closed, code: {}, reason: {}",
-                            frame.code, frame.reason
-                        );
-                    } else {
-                        info!("Websocket connection closed without frame");
+            while tasks.len().saturating_sub(streams.open.load(Ordering::Relaxed)) < limits.requests
+                && let Some((request_id, task)) = queued.pop_front()
+            {
+                let (task, abort) = abortable(task);
+                in_flight.insert(request_id, abort);
+                tasks.push(task.map(move |response| (request_id, response.ok().flatten())));
+            }
+            let response = select! {
+                response = outgoing.select_next_some() => Some(response),
+                // a request has started streaming, so a queued request may take its slot
+                () = streams_started.select_next_some() => None,
+                (request_id, response) = tasks.select_next_some() => {
+                    in_flight.remove(&request_id);
+                    response
+                },
+                msg = socket_receiver.next().fuse() => {
+                    let Some(msg) = msg else {
+                        info!("Websocket disconnected abruptly");
+                        return;
+                    };
+                    let msg = match msg {
+                        Ok(msg) => msg,
+                        Err(error) => {
+                            info!("Websocket disconnected with error: {error}");
+                            return;
+                        }
+                    };
+                    match msg {
+                        Message::Text(_) => Some(Message::Text("text frames not supported".into())),
+                        Message::Binary(bytes) => match read_frame::<R>(format, &bytes) {
+                            Ok(Frame::Request(request_id, _))
+                                if tasks.len().saturating_sub(streams.open.load(Ordering::Relaxed)) >= limits.requests
+                                    && queued.len() >= limits.queued =>
+                            {
+                                let error = ResponseError::new(ErrorKind::Rejected, "Too many requests are queued");
+                                Some(error_frame(format, request_id, FrameKind::Rejected, error))
+                            }
+                            Ok(Frame::Request(request_id, request)) => {
+                                let task = Self::handle_websocket_request(format, &handler, &streams, request_id, request, sender.clone());
+                                queued.push_back((request_id, task.boxed()));
+                                None
+                            }
+                            Ok(Frame::Cancel(request_id)) => {
+                                if let Some(abort) = in_flight.remove(&request_id) {
+                                    debug!("Cancelled request: {request_id}");
+                                    abort.abort();
+                                } else {
+                                    queued.retain(|(queued_id, _)| *queued_id != request_id);
+                                }
+                                None
+                            }
+                            Err(error) => Some(error),
+                        },
+                        Message::Ping(bytes) => Some(Message::Pong(bytes)),
+                        Message::Pong(_) => None,
+                        Message::Close(frame) => {
+                            if let Some(frame) = frame {
+                                info!(
+                                    "Websocket connection closed, code: {}, reason: {}",
+                                    frame.code, frame.reason
+                                );
+                            } else {
+                                info!("Websocket connection closed without frame");
+                            }
+                            Some(Message::Close(None))
+                        }
                     }
-                    Some(Message::Close(None))
                 }
             };
             if let Some(response) = response
-                && socket.send(response).await.is_err()
+                && socket_sender.send(response).await.is_err()
             {
                 debug!("Failed to send response message");
                 return;
@@ -297,25 +632,374 @@ where
         }
     }
 
-    async fn handle_request(
+    /// Handle a single request received on a websocket, returning the response frame unless the
+    /// response is streamed to `sender`
+    ///
+    /// The request is read here rather than when the frame is received, so that it can borrow from
+    /// the frame
+    async fn handle_websocket_request(
         format: RpcFormat<R>,
-        request: &[u8],
         handler: &<Server as IntoHandler<R>>::Handler,
-    ) -> Result<Vec<u8>, String> {
-        let (request_id, request) = get_request_id(request);
-        let request: RpcRequest<R> = format
-            .read(request)
-            .map_err(|error| format!("Failed to parse request: {error}"))?;
-        let response = handler.handle(request).await;
-        let response = format
-            .write(response)
-            .map_err(|error| format!("Failed to write response: {error}"))?;
-        let response = prepend_id(request_id, response);
-        Ok(response)
+        streams: &OpenStreams,
+        request_id: u32,
+        request: Bytes,
+        sender: mpsc::UnboundedSender<Message>,
+    ) -> Option<Message> {
+        let request = match format.read_request::<Infallible>(&request) {
+            Ok(request) => request,
+            Err(error) => {
+                let Ok((_, error)) = error.into_response_error();
+                return Some(error_frame(format, request_id, FrameKind::Rejected, error));
+            }
+        };
+        let method = request.method_name();
+        match request.into_streaming() {
+            Ok(request) => {
+                let Some(_stream) = streams.start() else {
+                    let error = ResponseError::new(ErrorKind::Rejected, "Too many streams are open")
+                        .with_method(method);
+                    return Some(error_frame(format, request_id, FrameKind::Rejected, error));
+                };
+                let sink = ResponseSink::<R> {
+                    request_id,
+                    method,
+                    format,
+                    sender,
+                    failed: false,
+                };
+                handler.handle_stream_response(request, sink).await;
+                None
+            }
+            Err(request) => {
+                let response = request.handle(handler).await;
+                Some(write_response::<R>(format, request_id, method, response).unwrap_or_else(|error| error))
+            }
+        }
     }
 }
 
+/// The limits on the requests of a single websocket connection
+#[derive(Debug, Clone, Copy)]
+struct WebsocketLimits {
+    /// The number of requests which are handled concurrently, not counting open streams
+    requests: usize,
+    /// The number of streams which may be open at once
+    streams: usize,
+    /// The number of requests which may wait for a slot
+    queued: usize,
+}
+
+/// The streams which are open on a websocket connection
+struct OpenStreams {
+    open: AtomicUsize,
+    max: usize,
+    /// Signals the connection that a request has started streaming, and so released its slot
+    started: mpsc::UnboundedSender<()>,
+}
+
+impl OpenStreams {
+    /// Open a stream, unless too many are already open. The stream is closed when the returned
+    /// guard is dropped
+    fn start(&self) -> Option<OpenStream<'_>> {
+        self.open
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |open| (open < self.max).then_some(open + 1))
+            .ok()?;
+        let _: Result<(), _> = self.started.unbounded_send(());
+        Some(OpenStream(&self.open))
+    }
+}
+
+/// A stream which is open on a websocket connection
+struct OpenStream<'a>(&'a AtomicUsize);
+
+impl Drop for OpenStream<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// A frame received from the client
+enum Frame {
+    /// A new request, which has not been read yet
+    Request(u32, Bytes),
+    /// The client is no longer interested in the response to the given request
+    Cancel(u32),
+}
+
+/// Read a frame from the client, returning the frame to reply with if the frame could not be read
+fn read_frame<R: Rpc>(format: RpcFormat<R>, frame: &Bytes) -> Result<Frame, Message> {
+    let Some((request_id, kind, request)) = get_header(frame) else {
+        return Err(Message::Text("malformed frame".into()));
+    };
+    match kind {
+        FrameKind::Message => Ok(Frame::Request(request_id, frame.slice_ref(request))),
+        FrameKind::Cancel => Ok(Frame::Cancel(request_id)),
+        kind => {
+            let error = ResponseError::new(ErrorKind::Rejected, format!("unexpected frame kind: {kind:?}"));
+            Err(error_frame(format, request_id, FrameKind::Rejected, error))
+        }
+    }
+}
+
+/// Write a response frame, returning an error frame instead if the response could not be written
+fn write_response<R: Rpc>(format: RpcFormat<R>, request_id: u32, method: &'static str, response: RpcResponse<R>) -> Result<Message, Message> {
+    let mut frame = new_frame(request_id, FrameKind::Message);
+    format.rpc.write_to(response, &mut frame).map_err(|error| {
+        let error = ResponseError::new(ErrorKind::Serialise, "Could not serialise response")
+            .with_method(method)
+            .with_details(error.to_string());
+        error_frame(format, request_id, FrameKind::Failed, error)
+    })?;
+    Ok(Message::Binary(frame.into()))
+}
+
+fn error_frame<R: Rpc>(format: RpcFormat<R>, request_id: u32, kind: FrameKind, error: ResponseError) -> Message {
+    let mut frame = new_frame(request_id, kind);
+    frame.extend(format.write_error(error));
+    Message::Binary(frame.into())
+}
+
+/// A [Sink] which writes each streamed response to the websocket, tagged with the id of the
+/// request which started the stream
+///
+/// The end of the stream is signalled to the client when this sink is dropped
+struct ResponseSink<R: Rpc + 'static> {
+    request_id: u32,
+    method: &'static str,
+    format: RpcFormat<R>,
+    sender: mpsc::UnboundedSender<Message>,
+    failed: bool,
+}
+
+impl<R: Rpc + 'static> Sink<RpcResponse<R>> for ResponseSink<R> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: RpcResponse<R>) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        if this.failed {
+            // the client has already been told that this stream failed
+            return Ok(());
+        }
+        let message = write_response::<R>(this.format, this.request_id, this.method, item).unwrap_or_else(|error| {
+            this.failed = true;
+            error
+        });
+        if this.sender.unbounded_send(message).is_err() {
+            debug!("Websocket closed, dropping streamed response");
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: Rpc + 'static> Drop for ResponseSink<R> {
+    fn drop(&mut self) {
+        if self.failed {
+            return;
+        }
+        let end = Message::Binary(new_frame(self.request_id, FrameKind::End).into());
+        if self.sender.unbounded_send(end).is_err() {
+            debug!("Websocket closed, could not send end of stream");
+        }
+    }
+}
+
+/// Returns true if the request accepts a stream of server-sent events
+fn accepts_event_stream(req: &Request) -> bool {
+    media_ranges(req).any(|(media_range, quality)| {
+        quality > 0.0 && media_range.eq_ignore_ascii_case(EVENT_STREAM_CONTENT_TYPE)
+    })
+}
+
+/// The media ranges of the Accept header of a request, along with their quality values
+fn media_ranges(req: &Request) -> impl Iterator<Item = (&str, f32)> {
+    req.headers()
+        .get_all(ACCEPT)
+        .iter()
+        .filter_map(|accept| accept.to_str().ok())
+        .flat_map(|accept| accept.split(','))
+        .filter_map(|media_range| {
+            let mut params = media_range.split(';');
+            let media_range = params.next()?.trim();
+            if media_range.is_empty() {
+                return None;
+            }
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse().ok())
+                .unwrap_or(1.0);
+            Some((media_range, quality))
+        })
+}
+
+/// How closely a media range matches a Content-Type, or `None` if it does not match it at all
+fn specificity(media_range: &str, content_type: &str) -> Option<u8> {
+    if media_range == "*/*" {
+        return Some(0);
+    }
+    if media_range.eq_ignore_ascii_case(content_type) {
+        return Some(2);
+    }
+    let (range_type, range_subtype) = media_range.split_once('/')?;
+    let (content_type, _) = content_type.split_once('/')?;
+    (range_subtype == "*" && range_type.eq_ignore_ascii_case(content_type)).then_some(1)
+}
+
+/// A [Sink] which writes each streamed response to a chunked HTTP body
+///
+/// Once the status has been sent, the client can only be told that the stream failed if the
+/// [Framing] supports it, otherwise the body is ended early instead
+struct BodySink<R: Rpc + 'static> {
+    method: &'static str,
+    format: RpcFormat<R>,
+    framing: Framing,
+    sender: mpsc::UnboundedSender<Vec<u8>>,
+    failed: bool,
+}
+
+impl<R: Rpc + 'static> Sink<RpcResponse<R>> for BodySink<R> {
+    type Error = Infallible;
+
+    fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn start_send(self: Pin<&mut Self>, item: RpcResponse<R>) -> Result<(), Self::Error> {
+        let this = self.get_mut();
+        if this.failed {
+            return Ok(());
+        }
+        let format = this.format;
+        let response = this.framing.encode(|bytes| {
+            format.rpc.write_to(item, bytes).map_err(|error| error.to_string())
+        });
+        match response {
+            Ok(response) => {
+                if this.sender.unbounded_send(response).is_err() {
+                    debug!("Response body closed, dropping streamed response");
+                }
+            }
+            Err(error) => {
+                warn!("Failed to write response: {error}, ending the response body");
+                this.failed = true;
+                let error = ResponseError::new(ErrorKind::Serialise, "Could not serialise response")
+                    .with_method(this.method)
+                    .with_details(error);
+                // events can only carry text, so the error is sent as a message if it is not
+                let error = String::from_utf8(this.format.write_error(error.clone()))
+                    .unwrap_or_else(|_| plain_text(&error));
+                if let Some(error) = this.framing.error(&error) {
+                    let _: Result<(), _> = this.sender.unbounded_send(error);
+                }
+                this.sender.close_channel();
+            }
+        }
+        Ok(())
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<R: Rpc + 'static> Drop for BodySink<R> {
+    fn drop(&mut self) {
+        if !self.failed
+            && let Some(end) = self.framing.end()
+            && self.sender.unbounded_send(end).is_err()
+        {
+            debug!("Response body closed, could not send end of stream");
+        }
+    }
+}
+
+/// Find the format of a plain HTTP request from its Content-Type
+fn request_format<R: Rpc, Server>(formats: &Formats<R>, req: &Request) -> Result<RpcFormat<R>, Error<Server>> {
+    let content_type = req
+        .headers()
+        .get(CONTENT_TYPE)
+        .ok_or(Error::NoContentType)?;
+    let content_type = content_type
+        .to_str()
+        .map_err(|error| Error::Internal(error.to_string()))?;
+    let content_type = content_type.split(';').next().unwrap_or(content_type);
+    formats
+        .iter()
+        .copied()
+        .find(|format| format.content_type() == content_type)
+        .ok_or(Error::UnsupportedContentType)
+}
+
+/// Choose the format to respond in from the Accept header of a request, the format of the request
+/// is preferred when several formats are equally acceptable
+///
+/// Media types which only describe how a streamed response is framed (eg: `text/event-stream`)
+/// are ignored, the format of the request is used if there are no others
+fn response_format<R: Rpc, Server>(formats: &Formats<R>, req: &Request, request_format: RpcFormat<R>) -> Result<RpcFormat<R>, Error<Server>> {
+    let media_ranges: Vec<_> = media_ranges(req)
+        .filter(|(media_range, _)| !Framing::is_framing_type(media_range))
+        .collect();
+    if media_ranges.is_empty() {
+        return Ok(request_format);
+    }
+    // the most specific media range which matches a format decides its quality
+    let quality = |format: RpcFormat<R>| {
+        media_ranges
+            .iter()
+            .filter_map(|(media_range, quality)| Some((specificity(media_range, format.content_type())?, *quality)))
+            .max_by_key(|(specificity, _)| *specificity)
+            .map_or(0.0, |(_, quality)| quality)
+    };
+    let mut best: Option<(RpcFormat<R>, f32)> = None;
+    for format in std::iter::once(request_format).chain(formats.iter().copied()) {
+        let quality = quality(format);
+        if quality > best.map_or(0.0, |(_, best)| best) {
+            best = Some((format, quality));
+        }
+    }
+    best.map(|(format, _)| format).ok_or_else(|| {
+        Error::NotAcceptable(formats.iter().copied().map(RpcFormat::content_type).collect())
+    })
+}
+
+/// Respond with the error encoded in the given format
+fn encoded_error<R: Rpc, Server>(format: RpcFormat<R>, error: Error<Server>) -> Result<Response, Error<Server>> {
+    let (status, error) = error.into_response_error().map_err(Error::LoadServer)?;
+    Ok((
+        status,
+        [(CONTENT_TYPE, format.content_type())],
+        format.write_error(error),
+    )
+        .into_response())
+}
+
+/// The body of an error response which is not encoded
+fn plain_text(error: &ResponseError) -> String {
+    error.details.as_ref().map_or_else(
+        || error.message.clone(),
+        |details| format!("{}: {details}", error.message),
+    )
+}
+
 /// An Error which may occur when handling RPC requests
+///
+/// Once the format of the request is known, the error is encoded in that format as a
+/// [`ResponseError`], otherwise it is sent as plain text
 pub enum Error<Server> {
     /// The wrong HTTP method was used
     WrongMethod,
@@ -325,54 +1009,736 @@ pub enum Error<Server> {
     UnsupportedContentType,
     /// The given Sec-WebSocket-Protocol is not supported
     UnsupportedSubprotocol(Vec<&'static str>),
+    /// None of the formats in the Accept header are supported
+    NotAcceptable(Vec<&'static str>),
     /// An Error occurred while deserialising the request
     Deserialise(String),
+    /// The request called a method which the service does not have
+    UnknownMethod(String),
     /// An Error occurred while serialising the response
-    Serialise(String),
+    Serialise {
+        /// The name of the method which was called
+        method: &'static str,
+        /// The error from the format
+        error: String,
+    },
     /// An internal error occurred while processing the request
     Internal(String),
     /// A rejection when getting the server from the request
     LoadServer(Server),
 }
 
-impl<Server: IntoResponse> IntoResponse for Error<Server> {
-    fn into_response(self) -> Response {
-        match self {
+impl<Server> Error<Server> {
+    /// Convert this error into the status and body of an error response, a rejection when getting
+    /// the server is returned as is
+    ///
+    /// # Errors
+    /// Returns the rejection if this is [`Error::LoadServer`]
+    pub fn into_response_error(self) -> Result<(StatusCode, ResponseError), Server> {
+        Ok(match self {
             Self::WrongMethod => (
-                StatusCode::NOT_FOUND,
-                "No resource found with the provided method".to_string(),
-            )
-                .into_response(),
+                StatusCode::METHOD_NOT_ALLOWED,
+                ResponseError::new(ErrorKind::WrongMethod, "No resource found with the provided method"),
+            ),
             Self::NoContentType => (
-                StatusCode::BAD_REQUEST,
-                "No Content-Type Header provided".to_string(),
-            )
-                .into_response(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ResponseError::new(ErrorKind::NoContentType, "No Content-Type Header provided"),
+            ),
             Self::UnsupportedContentType => (
-                StatusCode::BAD_REQUEST,
-                "provided Content-Type not supported".to_string(),
-            )
-                .into_response(),
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                ResponseError::new(ErrorKind::UnsupportedContentType, "provided Content-Type not supported"),
+            ),
             Self::UnsupportedSubprotocol(subprotocols) => (
                 StatusCode::BAD_REQUEST,
-                format!(
-                    "provided subprotocol is not supported, supported subprotocols: {}",
-                    subprotocols.join(", ")
+                ResponseError::new(
+                    ErrorKind::UnsupportedSubprotocol,
+                    format!(
+                        "provided subprotocol is not supported, supported subprotocols: {}",
+                        subprotocols.join(", ")
+                    ),
                 ),
-            )
-                .into_response(),
+            ),
+            Self::NotAcceptable(formats) => (
+                StatusCode::NOT_ACCEPTABLE,
+                ResponseError::new(
+                    ErrorKind::NotAcceptable,
+                    format!("none of the accepted formats are supported, supported formats: {}", formats.join(", ")),
+                ),
+            ),
             Self::Deserialise(error) => (
                 StatusCode::BAD_REQUEST,
-                format!("Could not parse request: {error}"),
-            )
-                .into_response(),
-            Self::Serialise(error) => (
+                ResponseError::new(ErrorKind::Deserialise, "Could not parse request").with_details(error),
+            ),
+            Self::UnknownMethod(error) => (
+                StatusCode::BAD_REQUEST,
+                ResponseError::new(ErrorKind::UnknownMethod, "Unknown method").with_details(error),
+            ),
+            Self::Serialise { method, error } => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Could not serialise response: {error}"),
-            )
-                .into_response(),
-            Self::Internal(error) => (StatusCode::INTERNAL_SERVER_ERROR, error).into_response(),
-            Self::LoadServer(error) => error.into_response(),
+                ResponseError::new(ErrorKind::Serialise, "Could not serialise response")
+                    .with_method(method)
+                    .with_details(error),
+            ),
+            Self::Internal(error) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                ResponseError::new(ErrorKind::Internal, error),
+            ),
+            Self::LoadServer(error) => return Err(error),
+        })
+    }
+}
+
+impl<Server: IntoResponse> IntoResponse for Error<Server> {
+    fn into_response(self) -> Response {
+        match self.into_response_error() {
+            Ok((status, error)) => (status, plain_text(&error)).into_response(),
+            Err(error) => error.into_response(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "__test"))]
+mod tests {
+    use super::*;
+    use crate::client::RpcError;
+    use crate::client::reqwest::Reqwest;
+    use crate::format::json::Json;
+    use crate::{client, rpc};
+
+    /// The service as the server has it
+    #[rpc(crate = crate)]
+    trait Old {
+        fn hello(&self) -> u32;
+    }
+
+    /// The service as the client has it, with a method which the server does not have yet
+    #[rpc(crate = crate)]
+    trait New {
+        fn hello(&self) -> u32;
+        fn goodbye(&self) -> u32;
+    }
+
+    /// [`Old`] for formats which encode methods by their index
+    #[rpc(crate = crate, variant_index)]
+    trait OldIndexed {
+        fn hello(&self) -> u32;
+    }
+
+    /// [`New`] for formats which encode methods by their index
+    #[rpc(crate = crate, variant_index)]
+    trait NewIndexed {
+        fn hello(&self) -> u32;
+        fn goodbye(&self) -> u32;
+    }
+
+    /// A service whose only method has a default body, which borrows its argument
+    #[rpc(crate = crate)]
+    trait Greeter {
+        fn shout(&self, name: &str) -> String {
+            str::to_uppercase(name)
+        }
+    }
+
+    /// A service with a streamed response
+    #[rpc(crate = crate)]
+    trait Counter {
+        fn count(&self, to: u32) -> Stream<u32>;
+    }
+
+    struct Server;
+
+    impl FromRequestParts<()> for Server {
+        type Rejection = Infallible;
+
+        async fn from_request_parts(_: &mut axum::http::request::Parts, (): &()) -> Result<Self, Self::Rejection> {
+            Ok(Self)
+        }
+    }
+
+    impl OldServer for Server {
+        async fn hello(&self) -> u32 {
+            1
+        }
+    }
+
+    impl OldIndexedServer for Server {
+        async fn hello(&self) -> u32 {
+            1
+        }
+    }
+
+    impl GreeterServer for Server {}
+
+    impl CounterServer for Server {
+        async fn count(&self, sink: impl Sink<u32, Error = Infallible> + Send + 'static, to: u32) {
+            let mut sink = std::pin::pin!(sink);
+            for value in 0..to {
+                let Ok(()) = sink.send(value).await;
+            }
+        }
+    }
+
+    /// Serve the given routes on a local port, returning their URL
+    async fn serve(app: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await });
+        format!("http://{address}/")
+    }
+
+    /// A client for a service served at `url`, in the given format
+    fn async_client<S: Rpc, F>(url: String, format: F) -> S::AsyncClient<impl client::StreamClient<S::Request, S::Response, Error = RpcError<reqwest::Error>>>
+    where
+        F: Format<S::Response, S::Request> + Format<ResponseError, ResponseError> + Clone + 'static,
+    {
+        S::async_client(
+            client::builder()
+                .non_blocking()
+                .transport(Reqwest::builder().url(url).build())
+                .format(format)
+                .build(),
+        )
+    }
+
+    fn assert_unknown_method<T: std::fmt::Debug>(result: Result<T, RpcError<reqwest::Error>>) {
+        let Err(RpcError::Response(error)) = result else {
+            panic!("expected an error response, received: {result:?}");
+        };
+        assert_eq!(error.kind, ErrorKind::UnknownMethod);
+    }
+
+    #[tokio::test]
+    async fn unknown_method() {
+        let service = Axum::builder()
+            .rpc(PhantomData::<Old>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_json()
+            .allow_cbor()
+            .allow_msgpack()
+            .method(Method::POST)
+            .build();
+        let indexed = Axum::builder()
+            .rpc(PhantomData::<OldIndexed>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_postcard()
+            .method(Method::POST)
+            .build();
+        let url = serve(axum::Router::new().route_service("/", service).route_service("/indexed", indexed)).await;
+
+        let json = async_client::<New, _>(url.clone(), Json);
+        assert_eq!(json.hello().await.unwrap(), 1);
+        assert_unknown_method(json.goodbye().await);
+        let cbor = async_client::<New, _>(url.clone(), format::cbor::Cbor);
+        assert_eq!(cbor.hello().await.unwrap(), 1);
+        assert_unknown_method(cbor.goodbye().await);
+        let msgpack = async_client::<New, _>(url.clone(), format::msgpack::MsgPack);
+        assert_eq!(msgpack.hello().await.unwrap(), 1);
+        assert_unknown_method(msgpack.goodbye().await);
+        let postcard = async_client::<NewIndexed, _>(format!("{url}indexed"), format::postcard::Postcard);
+        assert_eq!(postcard.hello().await.unwrap(), 1);
+        assert_unknown_method(postcard.goodbye().await);
+    }
+
+    #[tokio::test]
+    async fn default_body_with_reference() {
+        let service = Axum::builder()
+            .rpc(PhantomData::<Greeter>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_json()
+            .method(Method::POST)
+            .build();
+        let url = serve(axum::Router::new().route_service("/", service)).await;
+
+        let client = Greeter::async_client(
+            client::builder()
+                .non_blocking()
+                .transport(Reqwest::builder().url(url).build())
+                .format(Json)
+                .build(),
+        );
+        assert_eq!(client.shout("bob").await.unwrap(), "BOB");
+    }
+
+    #[tokio::test]
+    async fn streamed_response() {
+        let service = Axum::builder()
+            .rpc(PhantomData::<Counter>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_json()
+            .allow_cbor()
+            .method(Method::POST)
+            .build();
+        let url = serve(axum::Router::new().route_service("/", service)).await;
+
+        // JSON is streamed as newline-delimited JSON, other formats are length-delimited
+        let client = async_client::<Counter, _>(url.clone(), Json);
+        let values: Vec<u32> = client.count(3).await.unwrap().map(Result::unwrap).collect().await;
+        assert_eq!(values, [0, 1, 2]);
+        let client = async_client::<Counter, _>(url, format::cbor::Cbor);
+        let values: Vec<u32> = client.count(3).await.unwrap().map(Result::unwrap).collect().await;
+        assert_eq!(values, [0, 1, 2]);
+    }
+
+    #[tokio::test]
+    async fn streamed_response_content_type() {
+        let app = axum::Router::new()
+            .route("/cbor", axum::routing::post(|| async { ([(CONTENT_TYPE, "application/cbor")], vec![1, 0, 0, 0, 0]) }))
+            .route("/ndjson", axum::routing::post(|| async { ([(CONTENT_TYPE, "application/x-ndjson")], "0\n") }));
+        let url = serve(app).await;
+
+        // a length-delimited body must be in the requested format
+        let client = async_client::<Counter, _>(format!("{url}cbor"), Json);
+        assert_unexpected_content_type(client.count(1).await.unwrap()).await;
+        // newline-delimited items are JSON, which cannot be read as CBOR
+        let client = async_client::<Counter, _>(format!("{url}ndjson"), format::cbor::Cbor);
+        assert_unexpected_content_type(client.count(1).await.unwrap()).await;
+    }
+
+    async fn assert_unexpected_content_type(stream: impl futures::Stream<Item = Result<u32, RpcError<reqwest::Error>>>) {
+        let mut stream = std::pin::pin!(stream);
+        let Some(Err(RpcError::Response(error))) = stream.next().await else {
+            panic!("expected an error response");
+        };
+        assert_eq!(error.kind, ErrorKind::UnexpectedContentType);
+        assert!(stream.next().await.is_none());
+    }
+
+    /// Choose the response format for a JSON request with the given Accept headers, from a server
+    /// which allows JSON, CBOR and [MessagePack](https://msgpack.org/)
+    fn negotiate(accept: &[&'static str]) -> Result<&'static str, Error<()>> {
+        let service = Axum::builder()
+            .rpc(PhantomData::<Old>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_json()
+            .allow_cbor()
+            .allow_msgpack()
+            .build();
+        let mut req = axum::http::Request::builder().header(CONTENT_TYPE, "application/json");
+        for accept in accept {
+            req = req.header(ACCEPT, *accept);
+        }
+        let req = req.body(Body::empty()).unwrap();
+        let request_format = request_format::<Old, ()>(&service.formats, &req)?;
+        response_format(&service.formats, &req, request_format).map(RpcFormat::content_type)
+    }
+
+    #[test]
+    fn accept_negotiation() {
+        // without an Accept header, the response is in the format of the request
+        assert_eq!(negotiate(&[]).ok(), Some("application/json"));
+        assert_eq!(negotiate(&["application/cbor"]).ok(), Some("application/cbor"));
+        assert_eq!(negotiate(&["APPLICATION/CBOR"]).ok(), Some("application/cbor"));
+        // the highest quality wins
+        assert_eq!(negotiate(&["application/cbor;q=0.5, application/msgpack"]).ok(), Some("application/msgpack"));
+        assert_eq!(negotiate(&["application/cbor; q=0.9", "application/msgpack; q=0.8"]).ok(), Some("application/cbor"));
+        // the format of the request is preferred when several are equally acceptable
+        assert_eq!(negotiate(&["*/*"]).ok(), Some("application/json"));
+        assert_eq!(negotiate(&["application/*"]).ok(), Some("application/json"));
+        assert_eq!(negotiate(&["application/cbor, application/json"]).ok(), Some("application/json"));
+        // the most specific media range decides the quality of a format
+        assert_eq!(negotiate(&["application/*;q=0.8, application/cbor;q=0.9"]).ok(), Some("application/cbor"));
+        assert_eq!(negotiate(&["application/json;q=0.1, */*;q=0.5"]).ok(), Some("application/cbor"));
+        // a quality of zero excludes a format, even if a wildcard would match it
+        assert_eq!(negotiate(&["application/json;q=0, */*"]).ok(), Some("application/cbor"));
+        assert_eq!(negotiate(&["application/json;q=0, application/cbor;q=0, application/*"]).ok(), Some("application/msgpack"));
+        // media types which only describe framing are ignored
+        assert_eq!(negotiate(&["text/event-stream"]).ok(), Some("application/json"));
+        assert_eq!(negotiate(&["text/event-stream, application/msgpack"]).ok(), Some("application/msgpack"));
+    }
+
+    #[test]
+    fn not_acceptable() {
+        for accept in ["text/plain", "text/*", "application/json;q=0, application/cbor;q=0, application/msgpack;q=0", "*/*;q=0"] {
+            let Err(error) = negotiate(&[accept]) else {
+                panic!("{accept} should not be acceptable");
+            };
+            assert!(matches!(&error, Error::NotAcceptable(formats) if formats == &["application/json", "application/cbor", "application/msgpack"]));
+            let (status, error) = error.into_response_error().unwrap();
+            assert_eq!(status, StatusCode::NOT_ACCEPTABLE);
+            assert_eq!(error.kind, ErrorKind::NotAcceptable);
+        }
+    }
+
+    #[tokio::test]
+    async fn not_acceptable_response() {
+        let service = Axum::builder()
+            .rpc(PhantomData::<Old>)
+            .server(PhantomData::<Server>)
+            .state(())
+            .allow_json()
+            .method(Method::POST)
+            .build();
+        let url = serve(axum::Router::new().route_service("/", service)).await;
+
+        let response = reqwest::Client::new()
+            .post(url)
+            .header(CONTENT_TYPE.as_str(), "application/json")
+            .header(ACCEPT.as_str(), "application/cbor")
+            .body(r#"{"method":"hello","args":[]}"#)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status().as_u16(), StatusCode::NOT_ACCEPTABLE.as_u16());
+    }
+
+    #[test]
+    fn media_range_specificity() {
+        assert_eq!(specificity("*/*", "application/json"), Some(0));
+        assert_eq!(specificity("application/*", "application/json"), Some(1));
+        assert_eq!(specificity("Application/*", "application/json"), Some(1));
+        assert_eq!(specificity("application/json", "application/json"), Some(2));
+        assert_eq!(specificity("text/*", "application/json"), None);
+        assert_eq!(specificity("application/cbor", "application/json"), None);
+        assert_eq!(specificity("json", "application/json"), None);
+    }
+
+    mod websocket {
+        use super::*;
+        use crate::format::json::Json;
+        use std::collections::BTreeMap;
+        use std::sync::Arc;
+        use std::time::Duration;
+        use tokio::net::TcpStream;
+        use tokio::sync::{Notify, Semaphore};
+        use tokio_tungstenite::tungstenite::{ClientRequestBuilder, Message as WsMessage};
+        use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+        #[rpc(crate = crate, derive(PartialEq, Eq))]
+        trait Jobs {
+            fn echo(&self, value: u32) -> u32;
+            /// Waits until the test releases it
+            fn wait(&self, value: u32) -> u32;
+            /// JSON cannot have a map with sequences as keys
+            fn unserialisable(&self) -> BTreeMap<(u32, u32), u32>;
+            fn count(&self, to: u32) -> Stream<u32>;
+            /// Streams until it is cancelled
+            fn ticks(&self) -> Stream<u32>;
+        }
+
+        /// The state shared between the test and the requests it makes
+        struct Shared {
+            /// Each permit releases a single `wait`
+            released: Semaphore,
+            /// The number of `wait` requests which are being handled
+            waiting: AtomicUsize,
+            /// Notified when `ticks` stops
+            stopped: Notify,
+        }
+
+        impl Default for Shared {
+            fn default() -> Self {
+                Self {
+                    released: Semaphore::new(0),
+                    waiting: AtomicUsize::new(0),
+                    stopped: Notify::new(),
+                }
+            }
+        }
+
+        struct Worker(Arc<Shared>);
+
+        impl FromRequestParts<Arc<Shared>> for Worker {
+            type Rejection = Infallible;
+
+            async fn from_request_parts(_: &mut axum::http::request::Parts, shared: &Arc<Shared>) -> Result<Self, Self::Rejection> {
+                Ok(Self(shared.clone()))
+            }
+        }
+
+        /// Counts a `wait` request while it is being handled, including when it is cancelled
+        struct Waiting<'a>(&'a AtomicUsize);
+
+        impl Drop for Waiting<'_> {
+            fn drop(&mut self) {
+                self.0.fetch_sub(1, Ordering::SeqCst);
+            }
+        }
+
+        /// Notifies the test when `ticks` stops
+        struct Stopped<'a>(&'a Notify);
+
+        impl Drop for Stopped<'_> {
+            fn drop(&mut self) {
+                self.0.notify_one();
+            }
+        }
+
+        impl JobsServer for Worker {
+            async fn echo(&self, value: u32) -> u32 {
+                value
+            }
+
+            async fn wait(&self, value: u32) -> u32 {
+                self.0.waiting.fetch_add(1, Ordering::SeqCst);
+                let _waiting = Waiting(&self.0.waiting);
+                self.0.released.acquire().await.unwrap().forget();
+                value
+            }
+
+            async fn unserialisable(&self) -> BTreeMap<(u32, u32), u32> {
+                BTreeMap::from([((1, 2), 3)])
+            }
+
+            async fn count(&self, sink: impl Sink<u32, Error = Infallible> + Send + 'static, to: u32) {
+                let mut sink = std::pin::pin!(sink);
+                for value in 0..to {
+                    let Ok(()) = sink.send(value).await;
+                }
+            }
+
+            async fn ticks(&self, sink: impl Sink<u32, Error = Infallible> + Send + 'static) {
+                let _stopped = Stopped(&self.0.stopped);
+                let mut sink = std::pin::pin!(sink);
+                for tick in 0..=u32::MAX {
+                    let Ok(()) = sink.send(tick).await;
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+            }
+        }
+
+        /// A frame received from the server
+        #[derive(Debug, PartialEq, Eq)]
+        enum Reply {
+            Response(jobs::Response),
+            End,
+            Rejected(ErrorKind, String),
+            Failed(ErrorKind),
+        }
+
+        /// A websocket connection which sends and receives raw frames
+        struct Connection(WebSocketStream<MaybeTlsStream<TcpStream>>);
+
+        impl Connection {
+            async fn send(&mut self, frame: Vec<u8>) {
+                self.0.send(WsMessage::Binary(frame.into())).await.unwrap();
+            }
+
+            async fn request(&mut self, request_id: u32, request: jobs::Request) {
+                let mut frame = new_frame(request_id, FrameKind::Message);
+                Format::<jobs::Response, jobs::Request>::write_to(&Json, request, &mut frame).unwrap();
+                self.send(frame).await;
+            }
+
+            async fn cancel(&mut self, request_id: u32) {
+                self.send(new_frame(request_id, FrameKind::Cancel)).await;
+            }
+
+            /// The next message from the server, other than a ping
+            async fn message(&mut self) -> WsMessage {
+                loop {
+                    let message = tokio::time::timeout(Duration::from_secs(5), self.0.next())
+                        .await
+                        .expect("timed out waiting for a frame")
+                        .expect("websocket closed")
+                        .unwrap();
+                    if !matches!(message, WsMessage::Ping(_) | WsMessage::Pong(_)) {
+                        return message;
+                    }
+                }
+            }
+
+            /// The next frame from the server
+            async fn reply(&mut self) -> (u32, Reply) {
+                let WsMessage::Binary(frame) = self.message().await else {
+                    panic!("expected a binary frame");
+                };
+                let (request_id, kind, payload) = get_header(&frame).expect("malformed frame");
+                let error = || Format::<ResponseError, ResponseError>::read(&Json, payload).unwrap();
+                let reply = match kind {
+                    FrameKind::Message => Reply::Response(Format::<jobs::Response, jobs::Request>::read(&Json, payload).unwrap()),
+                    FrameKind::End => Reply::End,
+                    FrameKind::Rejected => {
+                        let error = error();
+                        Reply::Rejected(error.kind, error.message)
+                    }
+                    FrameKind::Failed => Reply::Failed(error().kind),
+                    FrameKind::Cancel => panic!("the server sent a cancel frame"),
+                };
+                (request_id, reply)
+            }
+
+            /// Read frames until one is received for each of the given requests, returning the
+            /// replies to each request in the order they were received
+            async fn replies(&mut self, until: &[(u32, Reply)]) -> HashMap<u32, Vec<Reply>> {
+                let mut replies: HashMap<u32, Vec<Reply>> = HashMap::new();
+                while !until.iter().all(|(request_id, reply)| replies.get(request_id).is_some_and(|replies| replies.contains(reply))) {
+                    let (request_id, reply) = self.reply().await;
+                    replies.entry(request_id).or_default().push(reply);
+                }
+                replies
+            }
+        }
+
+        const UNLIMITED: WebsocketLimits = WebsocketLimits {
+            requests: usize::MAX,
+            streams: usize::MAX,
+            queued: 64,
+        };
+
+        /// Serve the service with the given limits on its websocket connections, returning a
+        /// connection to it
+        async fn connect(shared: &Arc<Shared>, limits: WebsocketLimits) -> Connection {
+            let service = Axum::builder()
+                .rpc(PhantomData::<Jobs>)
+                .server(PhantomData::<Worker>)
+                .state(shared.clone())
+                .allow_json()
+                .method(Method::GET)
+                .enable_websockets(true)
+                .maybe_max_concurrent_requests(NonZeroUsize::new(limits.requests))
+                .maybe_max_concurrent_streams(NonZeroUsize::new(limits.streams))
+                .max_queued_requests(limits.queued)
+                .build();
+            let url = serve(axum::Router::new().route_service("/", service)).await;
+            let url = url.replacen("http", "ws", 1).parse().unwrap();
+            let (socket, _) = tokio_tungstenite::connect_async(ClientRequestBuilder::new(url).with_sub_protocol("application/json"))
+                .await
+                .unwrap();
+            Connection(socket)
+        }
+
+        /// Wait until the given number of `wait` requests are being handled
+        async fn waiting(shared: &Shared, count: usize) {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                while shared.waiting.load(Ordering::SeqCst) != count {
+                    tokio::time::sleep(Duration::from_millis(5)).await;
+                }
+            })
+            .await
+            .unwrap_or_else(|_| panic!("expected {count} requests to be waiting"));
+        }
+
+        #[tokio::test]
+        async fn multiplexed() {
+            let shared: Arc<Shared> = Arc::default();
+            let mut connection = connect(&shared, UNLIMITED).await;
+
+            connection.request(1, jobs::Request::Wait(1)).await;
+            connection.request(2, jobs::Request::Echo(2)).await;
+            connection.request(3, jobs::Request::Count(3)).await;
+            // the later requests are answered while the first is still waiting
+            let replies = connection.replies(&[(2, Reply::Response(jobs::Response::Echo(2))), (3, Reply::End)]).await;
+            assert_eq!(replies[&2], [Reply::Response(jobs::Response::Echo(2))]);
+            assert_eq!(
+                replies[&3],
+                [
+                    Reply::Response(jobs::Response::Count(0)),
+                    Reply::Response(jobs::Response::Count(1)),
+                    Reply::Response(jobs::Response::Count(2)),
+                    Reply::End,
+                ]
+            );
+            assert!(!replies.contains_key(&1));
+
+            shared.released.add_permits(1);
+            assert_eq!(connection.reply().await, (1, Reply::Response(jobs::Response::Wait(1))));
+        }
+
+        #[tokio::test]
+        async fn errors() {
+            let shared: Arc<Shared> = Arc::default();
+            let mut connection = connect(&shared, UNLIMITED).await;
+
+            let mut unknown = new_frame(1, FrameKind::Message);
+            unknown.extend_from_slice(br#"{"method":"missing","args":[]}"#);
+            connection.send(unknown).await;
+            let (request_id, Reply::Rejected(kind, _)) = connection.reply().await else {
+                panic!("expected the unknown method to be rejected");
+            };
+            assert_eq!((request_id, kind), (1, ErrorKind::UnknownMethod));
+
+            // only the server sends the other kinds of frames
+            connection.send(new_frame(2, FrameKind::End)).await;
+            let (request_id, Reply::Rejected(kind, _)) = connection.reply().await else {
+                panic!("expected the frame to be rejected");
+            };
+            assert_eq!((request_id, kind), (2, ErrorKind::Rejected));
+
+            connection.request(3, jobs::Request::Unserialisable()).await;
+            assert_eq!(connection.reply().await, (3, Reply::Failed(ErrorKind::Serialise)));
+
+            // a frame without a header cannot be answered with a frame
+            connection.send(vec![1, 2]).await;
+            assert_eq!(connection.message().await, WsMessage::Text("malformed frame".into()));
+
+            // the connection is still usable after each error
+            connection.request(4, jobs::Request::Echo(4)).await;
+            assert_eq!(connection.reply().await, (4, Reply::Response(jobs::Response::Echo(4))));
+        }
+
+        #[tokio::test]
+        async fn cancel() {
+            let shared: Arc<Shared> = Arc::default();
+            let mut connection = connect(&shared, UNLIMITED).await;
+
+            connection.request(1, jobs::Request::Ticks()).await;
+            assert_eq!(connection.reply().await, (1, Reply::Response(jobs::Response::Ticks(0))));
+            connection.cancel(1).await;
+            tokio::time::timeout(Duration::from_secs(5), shared.stopped.notified())
+                .await
+                .expect("the stream should stop once it is cancelled");
+            // ticks which were sent before the cancel was received may still arrive
+            let replies = connection.replies(&[(1, Reply::End)]).await;
+            assert!(replies[&1][..replies[&1].len() - 1].iter().all(|reply| matches!(reply, Reply::Response(jobs::Response::Ticks(_)))));
+
+            // a request which has not finished is dropped without a reply
+            connection.request(2, jobs::Request::Wait(2)).await;
+            waiting(&shared, 1).await;
+            connection.cancel(2).await;
+            waiting(&shared, 0).await;
+            connection.request(3, jobs::Request::Echo(3)).await;
+            assert_eq!(connection.reply().await, (3, Reply::Response(jobs::Response::Echo(3))));
+        }
+
+        #[tokio::test]
+        async fn request_limits() {
+            let shared: Arc<Shared> = Arc::default();
+            let mut connection = connect(&shared, WebsocketLimits { requests: 1, queued: 1, ..UNLIMITED }).await;
+
+            connection.request(1, jobs::Request::Wait(1)).await;
+            waiting(&shared, 1).await;
+            // queued until the first request has finished
+            connection.request(2, jobs::Request::Wait(2)).await;
+            connection.request(3, jobs::Request::Wait(3)).await;
+            assert_eq!(connection.reply().await, (3, Reply::Rejected(ErrorKind::Rejected, "Too many requests are queued".to_string())));
+            assert_eq!(shared.waiting.load(Ordering::SeqCst), 1);
+
+            shared.released.add_permits(1);
+            assert_eq!(connection.reply().await, (1, Reply::Response(jobs::Response::Wait(1))));
+            waiting(&shared, 1).await;
+            shared.released.add_permits(1);
+            assert_eq!(connection.reply().await, (2, Reply::Response(jobs::Response::Wait(2))));
+
+            // an open stream does not hold a slot
+            connection.request(4, jobs::Request::Ticks()).await;
+            connection.request(5, jobs::Request::Echo(5)).await;
+            let replies = connection.replies(&[(5, Reply::Response(jobs::Response::Echo(5)))]).await;
+            assert_eq!(replies[&5], [Reply::Response(jobs::Response::Echo(5))]);
+        }
+
+        #[tokio::test]
+        async fn stream_limit() {
+            let shared: Arc<Shared> = Arc::default();
+            let mut connection = connect(&shared, WebsocketLimits { streams: 1, ..UNLIMITED }).await;
+
+            connection.request(1, jobs::Request::Ticks()).await;
+            assert_eq!(connection.reply().await, (1, Reply::Response(jobs::Response::Ticks(0))));
+            connection.request(2, jobs::Request::Count(1)).await;
+            let rejected = Reply::Rejected(ErrorKind::Rejected, "Too many streams are open".to_string());
+            let replies = connection.replies(&[(2, rejected)]).await;
+            assert_eq!(replies[&2].len(), 1);
+
+            // once the open stream has ended, another may be opened
+            connection.cancel(1).await;
+            connection.replies(&[(1, Reply::End)]).await;
+            connection.request(3, jobs::Request::Count(1)).await;
+            let replies = connection.replies(&[(3, Reply::End)]).await;
+            assert_eq!(replies[&3], [Reply::Response(jobs::Response::Count(0)), Reply::End]);
         }
     }
 }
@@ -0,0 +1,168 @@
+//! Tests of the requests generated by `#[rpc]`, as they are encoded and decoded by the formats
+
+use crate::format::Format;
+use crate::format::cbor::Cbor;
+use crate::format::json::Json;
+use crate::format::msgpack::MsgPack;
+use crate::{Rpc, rpc};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+/// Encode a value with one format, then decode it as another type with the same format
+fn recode<From: Serialize, To: DeserializeOwned>(format: &(impl Format<To, To> + Format<From, From>), value: From) -> To {
+    let bytes = Format::<From, From>::write(format, value).unwrap();
+    Format::<To, To>::read(format, &bytes).unwrap()
+}
+
+mod defaults {
+    use super::*;
+
+    /// The service as it was first deployed
+    #[rpc(crate = crate)]
+    trait Old {
+        fn search(&self, query: String, page: u32) -> Vec<String>;
+    }
+
+    /// The service after arguments were added, old clients leave them out
+    #[rpc(crate = crate)]
+    trait New {
+        fn search(&self, query: String, page: u32, #[rpc(default)] exact: bool, #[rpc(default = 20)] limit: u32) -> Vec<String>;
+    }
+
+    /// The named version of the service as it was first deployed
+    #[rpc(crate = crate, named_args)]
+    trait OldNamed {
+        fn search(&self, query: String) -> Vec<String>;
+    }
+
+    /// The named version of the service after arguments were added
+    #[rpc(crate = crate, named_args)]
+    trait NewNamed {
+        fn search(&self, #[rpc(default = 20)] limit: u32, query: String, #[rpc(default)] exact: bool) -> Vec<String>;
+    }
+
+    type OldRequest = <Old as Rpc>::Request;
+    type NewRequest = <New as Rpc>::Request;
+    type OldNamedRequest = <OldNamed as Rpc>::Request;
+    type NewNamedRequest = <NewNamed as Rpc>::Request;
+
+    fn check<F>(format: &F)
+    where
+        F: Format<OldRequest, OldRequest>
+            + Format<NewRequest, NewRequest>
+            + Format<OldNamedRequest, OldNamedRequest>
+            + Format<NewNamedRequest, NewNamedRequest>,
+    {
+        let request: NewRequest = recode(format, old::Request::Search("x".to_string(), 2));
+        assert!(matches!(request, new::Request::Search(query, 2, false, 20) if query == "x"));
+        // an arg which is sent is not replaced by its default
+        let request: NewRequest = recode(format, new::Request::Search("x".to_string(), 2, true, 5));
+        assert!(matches!(request, new::Request::Search(_, 2, true, 5)));
+
+        let request: NewNamedRequest = recode(format, old_named::Request::Search { query: "x".to_string() });
+        assert!(matches!(request, new_named::Request::Search { limit: 20, query, exact: false } if query == "x"));
+    }
+
+    #[test]
+    fn json() {
+        check(&Json);
+    }
+
+    #[test]
+    fn cbor() {
+        check(&Cbor);
+    }
+
+    #[test]
+    fn msgpack() {
+        check(&MsgPack);
+    }
+}
+
+mod named_args {
+    use super::*;
+    use serde_json::json;
+
+    #[rpc(crate = crate, named_args)]
+    trait Todos {
+        fn rename(&self, id: u32, name: String) -> bool;
+        fn clear(&self);
+    }
+
+    type Request = <Todos as Rpc>::Request;
+
+    #[test]
+    fn keyed_by_name() {
+        let request = todos::Request::Rename { id: 7, name: "x".to_string() };
+        let value = serde_json::to_value(&request).unwrap();
+        assert_eq!(value, json!({ "method": "rename", "args": { "id": 7, "name": "x" } }));
+        let value = serde_json::to_value(todos::Request::Clear {}).unwrap();
+        assert_eq!(value, json!({ "method": "clear", "args": {} }));
+
+        // the order of the names does not matter
+        let request: Request = serde_json::from_value(json!({ "method": "rename", "args": { "name": "x", "id": 7 } })).unwrap();
+        assert!(matches!(request, todos::Request::Rename { id: 7, name } if name == "x"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let request: Request = recode(&Cbor, todos::Request::Rename { id: 7, name: "x".to_string() });
+        assert!(matches!(request, todos::Request::Rename { id: 7, name } if name == "x"));
+        let request: Request = recode(&MsgPack, todos::Request::Rename { id: 7, name: "x".to_string() });
+        assert!(matches!(request, todos::Request::Rename { id: 7, name } if name == "x"));
+
+        // MessagePack keeps the names of the args
+        let bytes = Format::<Request, Request>::write(&MsgPack, todos::Request::Rename { id: 7, name: "x".to_string() }).unwrap();
+        assert!(bytes.windows(2).any(|key| key == b"id"));
+        assert!(bytes.windows(4).any(|key| key == b"name"));
+    }
+}
+
+mod rename {
+    use super::*;
+    use crate::Request as _;
+    use serde_json::json;
+
+    #[rpc(crate = crate, rename_all = "camelCase")]
+    trait Todos {
+        #[rpc(rename = "get_todo")]
+        fn find_todo(&self, name: String) -> Option<String>;
+        #[rpc(alias = "delete", alias = "deleteTodo")]
+        fn remove_todo(&self, name: String);
+        fn clear_all(&self);
+    }
+
+    /// The service before `remove_todo` was renamed
+    #[rpc(crate = crate)]
+    trait Old {
+        fn delete(&self, name: String);
+    }
+
+    type Request = <Todos as Rpc>::Request;
+
+    #[test]
+    fn wire_names() {
+        let names: Vec<_> = Request::METHODS.iter().map(|method| method.name).collect();
+        assert_eq!(names, ["get_todo", "removeTodo", "clearAll"]);
+        assert_eq!(Request::METHODS[1].aliases, ["delete", "deleteTodo"]);
+
+        let request = todos::Request::FindTodo("x".to_string());
+        assert_eq!(request.method_name(), "get_todo");
+        assert_eq!(serde_json::to_value(&request).unwrap(), json!({ "method": "get_todo", "args": "x" }));
+        assert_eq!(todos::Request::ClearAll().method_name(), "clearAll");
+    }
+
+    #[test]
+    fn aliases() {
+        for name in ["removeTodo", "delete", "deleteTodo"] {
+            let request: Request = serde_json::from_value(json!({ "method": name, "args": "x" })).unwrap();
+            assert!(matches!(request, todos::Request::RemoveTodo(ref todo) if todo == "x"), "{name}");
+            // the request is always sent with the name of the method
+            assert_eq!(request.method_name(), "removeTodo");
+        }
+        let request: Request = recode(&Cbor, old::Request::Delete("x".to_string()));
+        assert!(matches!(request, todos::Request::RemoveTodo(ref todo) if todo == "x"));
+        // the name of the method in the trait is not one of its names
+        assert!(serde_json::from_value::<Request>(json!({ "method": "find_todo", "args": "x" })).is_err());
+    }
+}